@@ -25,7 +25,7 @@ pub fn tcpserver(unit: u8, listen: &str) {
                 if stream.read(&mut buf).unwrap_or(0) == 0 {
                     return;
                 }
-                let mut frame = ModbusFrame::new(unit, &buf, ModbusProto::TcpUdp, &mut response);
+                let mut frame = ModbusFrame::new(unit, &buf, ModbusProto::Tcp, &mut response);
                 if frame.parse().is_err() {
                     println!("server error");
                     return;
@@ -13,10 +13,10 @@ pub fn udpserver(unit: u8, listen: &str) {
     let socket = UdpSocket::bind(listen).unwrap();
     loop {
         let mut buf: ModbusFrameBuf = [0; 256];
-        let (_amt, src) = socket.recv_from(&mut buf).unwrap();
+        let (amt, src) = socket.recv_from(&mut buf).unwrap();
         println!("got packet");
         let mut response = Vec::new();
-        let mut frame = ModbusFrame::new(unit, &buf, ModbusProto::TcpUdp, &mut response);
+        let mut frame = ModbusFrame::new(unit, &buf[..amt], ModbusProto::Udp, &mut response);
         if frame.parse().is_err() {
             println!("server error");
             continue;
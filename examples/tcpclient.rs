@@ -13,7 +13,7 @@ fn main() {
     stream.set_write_timeout(Some(timeout)).unwrap();
 
     // create request object
-    let mut mreq = ModbusRequest::new(1, ModbusProto::TcpUdp);
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
     mreq.tr_id = 2; // just for test, default tr_id is 1
 
     // set 2 coils
@@ -29,7 +29,7 @@ fn main() {
     stream.read_exact(&mut buf).unwrap();
     let mut response = Vec::new();
     response.extend_from_slice(&buf);
-    let len = guess_response_frame_len(&buf, ModbusProto::TcpUdp).unwrap();
+    let len = guess_response_frame_len(&buf, ModbusProto::Tcp).unwrap();
     // read rest of response frame
     if len > 6 {
         let mut rest = vec![0u8; (len - 6) as usize];
@@ -46,7 +46,7 @@ fn main() {
     stream.read_exact(&mut buf).unwrap();
     let mut response = Vec::new();
     response.extend_from_slice(&buf);
-    let len = guess_response_frame_len(&buf, ModbusProto::TcpUdp).unwrap();
+    let len = guess_response_frame_len(&buf, ModbusProto::Tcp).unwrap();
     if len > 6 {
         let mut rest = vec![0u8; (len - 6) as usize];
         stream.read_exact(&mut rest).unwrap();
@@ -0,0 +1,174 @@
+use crate::{ascii_hex_to_nibble, guess_request_frame_len, guess_response_frame_len};
+use crate::{ModbusFrameBuf, ModbusProto};
+
+/// See [`FrameAccumulator::feed`]
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FeedResult<'a> {
+    /// the frame is incomplete
+    NeedMore,
+    /// the frame is complete (for ASCII: decoded to binary, including LRC)
+    Complete(&'a [u8]),
+    /// the frame is broken, the accumulator is reset
+    Broken,
+}
+
+/// Streaming frame reader, useful when frames are read byte-by-byte (e.g. from a serial port)
+///
+/// Uses [`guess_request_frame_len`] / [`guess_response_frame_len`] for TCP/UDP and RTU frames,
+/// ASCII frames are decoded on the fly and are complete when LF is received.
+///
+/// After a frame is complete or broken, the next fed byte starts a new frame.
+///
+/// ```
+/// use rmodbus::{FeedResult, FrameAccumulator, ModbusProto};
+///
+/// let mut acc = FrameAccumulator::new(ModbusProto::Rtu);
+/// let data = [1, 3, 0, 0, 0, 1, 0x84, 0x0a];
+/// for b in &data[..7] {
+///     assert_eq!(acc.feed(*b), FeedResult::NeedMore);
+/// }
+/// assert_eq!(acc.feed(data[7]), FeedResult::Complete(&data));
+/// ```
+#[derive(Debug)]
+pub struct FrameAccumulator {
+    proto: ModbusProto,
+    response: bool,
+    buf: ModbusFrameBuf,
+    pos: usize,
+    expected: Option<usize>,
+    // ASCII: the high nibble of the current byte
+    nibble: Option<u8>,
+    done: bool,
+}
+
+impl FrameAccumulator {
+    /// Create accumulator for request frames
+    pub fn new(proto: ModbusProto) -> Self {
+        Self {
+            proto,
+            response: false,
+            buf: [0; 256],
+            pos: 0,
+            expected: None,
+            nibble: None,
+            done: false,
+        }
+    }
+
+    /// Create accumulator for response frames
+    pub fn new_response(proto: ModbusProto) -> Self {
+        let mut acc = Self::new(proto);
+        acc.response = true;
+        acc
+    }
+
+    /// Reset the accumulator, dropping the current frame
+    pub fn reset(&mut self) {
+        self.pos = 0;
+        self.expected = None;
+        self.nibble = None;
+        self.done = false;
+    }
+
+    /// Feed a byte
+    pub fn feed(&mut self, byte: u8) -> FeedResult<'_> {
+        if self.done {
+            self.reset();
+        }
+        match self.proto {
+            ModbusProto::Ascii => self.feed_ascii(byte),
+            ModbusProto::Tcp | ModbusProto::Udp | ModbusProto::Rtu | ModbusProto::RtuOverTcp => {
+                self.feed_binary(byte)
+            }
+        }
+    }
+
+    fn feed_binary(&mut self, byte: u8) -> FeedResult<'_> {
+        if self.pos == self.buf.len() {
+            return self.broken();
+        }
+        self.buf[self.pos] = byte;
+        self.pos += 1;
+        if self.expected.is_none() && self.pos >= self.header_len() {
+            let guessed = if self.response {
+                guess_response_frame_len(&self.buf[..self.pos], self.proto)
+            } else {
+                guess_request_frame_len(&self.buf[..self.pos], self.proto)
+            };
+            match guessed {
+                Ok(len) => self.expected = Some(len as usize),
+                Err(_) => return self.broken(),
+            }
+        }
+        match self.expected {
+            Some(len) if self.pos >= len => {
+                self.done = true;
+                FeedResult::Complete(&self.buf[..self.pos])
+            }
+            _ => FeedResult::NeedMore,
+        }
+    }
+
+    fn feed_ascii(&mut self, byte: u8) -> FeedResult<'_> {
+        match byte {
+            b':' => {
+                // frame start
+                self.reset();
+                self.expected = Some(0);
+                FeedResult::NeedMore
+            }
+            _ if self.expected.is_none() => self.broken(),
+            b'\r' => FeedResult::NeedMore,
+            b'\n' => {
+                if self.pos == 0 || self.nibble.is_some() {
+                    return self.broken();
+                }
+                self.done = true;
+                FeedResult::Complete(&self.buf[..self.pos])
+            }
+            _ => {
+                let h = match ascii_hex_to_nibble(byte) {
+                    Ok(h) => h,
+                    Err(_) => return self.broken(),
+                };
+                if let Some(high) = self.nibble.take() {
+                    if self.pos == self.buf.len() {
+                        return self.broken();
+                    }
+                    self.buf[self.pos] = high * 0x10 + h;
+                    self.pos += 1;
+                } else {
+                    self.nibble = Some(h);
+                }
+                FeedResult::NeedMore
+            }
+        }
+    }
+
+    /// Bytes required to guess the frame length
+    fn header_len(&self) -> usize {
+        if self.proto.has_mbap_header() {
+            // the length is in the MBAP header
+            return 6;
+        }
+        if self.pos < 2 {
+            return 2;
+        }
+        if self.response {
+            // function + byte count
+            return 3;
+        }
+        match self.buf[1] {
+            15 | 16 => 7,
+            23 => 11,
+            43 => 3,
+            _ => 2,
+        }
+    }
+
+    fn broken(&mut self) -> FeedResult<'_> {
+        self.reset();
+        FeedResult::Broken
+    }
+}
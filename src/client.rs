@@ -1,7 +1,12 @@
 use crate::consts::{
-    MODBUS_GET_COILS, MODBUS_GET_DISCRETES, MODBUS_GET_HOLDINGS, MODBUS_GET_INPUTS,
-    MODBUS_SET_COIL, MODBUS_SET_COILS_BULK, MODBUS_SET_HOLDING, MODBUS_SET_HOLDINGS_BULK,
+    MODBUS_DIAGNOSTICS, MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT, MODBUS_FILE_RECORD_REFERENCE_TYPE,
+    MODBUS_GET_COILS, MODBUS_GET_COMM_EVENT_COUNTER, MODBUS_GET_DISCRETES, MODBUS_GET_HOLDINGS,
+    MODBUS_GET_INPUTS, MODBUS_MASK_WRITE_REGISTER, MODBUS_MEI_READ_DEVICE_ID,
+    MODBUS_READ_EXCEPTION_STATUS, MODBUS_READ_FILE_RECORD, MODBUS_READ_WRITE_MULTIPLE_REGISTERS,
+    MODBUS_REPORT_SERVER_ID, MODBUS_SET_COIL, MODBUS_SET_COILS_BULK, MODBUS_SET_HOLDING,
+    MODBUS_SET_HOLDINGS_BULK,
 };
+use crate::server::Write;
 use crate::{calc_crc16, calc_lrc, ErrorKind, ModbusFrameBuf, ModbusProto, VectorTrait};
 
 /// Modbus client generator/processor
@@ -10,22 +15,241 @@ use crate::{calc_crc16, calc_lrc, ErrorKind, ModbusFrameBuf, ModbusProto, Vector
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ModbusRequest {
-    /// transaction id, (TCP/UDP only), default: 1. To change, set the value manually
+    /// transaction id, (TCP/UDP only), default: 1. To change, set the value manually or enable
+    /// [`auto_increment_tr_id`](ModbusRequest::auto_increment_tr_id)
     pub tr_id: u16,
     pub unit_id: u8,
     pub func: u8,
     pub reg: u16,
     pub count: u16,
     pub proto: ModbusProto,
+    auto_tr_id: bool,
+    enron: bool,
+    any_unit_id: bool,
+    retries: u8,
+    attempt: u8,
+}
+
+/// Fluent [`ModbusRequest`] configuration
+///
+/// Defaults: unit id 1, [`ModbusProto::Tcp`], transaction id 1
+///
+/// ```
+/// use rmodbus::{client::ModbusRequestBuilder, ModbusProto};
+///
+/// let mut mreq = ModbusRequestBuilder::new()
+///     .unit(2)
+///     .proto(ModbusProto::Tcp)
+///     .tr_id(0x77)
+///     .build();
+/// let mut request = Vec::new();
+/// mreq.generate_get_holdings(10, 3, &mut request).unwrap();
+/// assert_eq!(request, [0, 0x77, 0, 0, 0, 6, 2, 3, 0, 10, 0, 3]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ModbusRequestBuilder {
+    unit_id: u8,
+    proto: ModbusProto,
+    tr_id: u16,
+    auto_tr_id: bool,
+    retries: u8,
+}
+
+impl Default for ModbusRequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModbusRequestBuilder {
+    pub fn new() -> Self {
+        Self {
+            unit_id: 1,
+            proto: ModbusProto::Tcp,
+            tr_id: 1,
+            auto_tr_id: false,
+            retries: 0,
+        }
+    }
+    pub fn unit(mut self, unit_id: u8) -> Self {
+        self.unit_id = unit_id;
+        self
+    }
+    pub fn proto(mut self, proto: ModbusProto) -> Self {
+        self.proto = proto;
+        self
+    }
+    /// Transaction id (TCP/UDP only)
+    pub fn tr_id(mut self, tr_id: u16) -> Self {
+        self.tr_id = tr_id;
+        self
+    }
+    /// See [`ModbusRequest::auto_increment_tr_id`]
+    pub fn auto_increment_tr_id(mut self, value: bool) -> Self {
+        self.auto_tr_id = value;
+        self
+    }
+    /// See [`ModbusRequest::set_retries`]
+    pub fn retries(mut self, n: u8) -> Self {
+        self.retries = n;
+        self
+    }
+    pub fn build(self) -> ModbusRequest {
+        let mut mreq = ModbusRequest::new(self.unit_id, self.proto);
+        mreq.tr_id = self.tr_id;
+        mreq.auto_increment_tr_id(self.auto_tr_id);
+        mreq.set_retries(self.retries);
+        mreq
+    }
+}
+
+/// Read Device Identification response header, see
+/// [`parse_device_id`](ModbusRequest::parse_device_id)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceIdInfo {
+    pub read_code: u8,
+    pub conformity_level: u8,
+    /// if true, the remaining objects should be requested starting from next_object_id
+    pub more_follows: bool,
+    pub next_object_id: u8,
+}
+
+/// Read File Record sub-request (a group), see
+/// [`generate_read_file_record`](ModbusRequest::generate_read_file_record)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FileRecordRequest {
+    pub file_number: u16,
+    /// the starting record (register) in the file, 0-9999
+    pub record_number: u16,
+    /// the number of records (registers) to read
+    pub record_length: u16,
+}
+
+/// See [`parse_ok_retryable`](ModbusRequest::parse_ok_retryable)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RetryDecision {
+    /// the request should be sent again
+    pub retry: bool,
+    /// the failed attempt number (starting from 1)
+    pub attempt: u8,
+    pub error: ErrorKind,
+}
+
+/// Response frame header, see [`parse_header`](ModbusRequest::parse_header)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ResponseHeader {
+    pub unit_id: u8,
+    pub func: u8,
+    /// response byte count, for functions which responses have it
+    pub byte_count: Option<u8>,
+    /// frame start (unit id) offset in the buffer
+    pub frame_start: usize,
+    /// frame end offset in the buffer (without checksum)
+    pub frame_end: usize,
+}
+
+impl ResponseHeader {
+    /// Response data offset in the buffer (after the byte count for functions which responses
+    /// have it), for functions, unknown to the client, the data is empty
+    #[inline]
+    pub fn data_start(&self) -> usize {
+        response_data_offset(self.func).map_or(self.frame_end, |offset| {
+            (self.frame_start + offset).min(self.frame_end)
+        })
+    }
+}
+
+/// Response data, decoded according to the request function, see
+/// [`parse_response_typed_ref`](ModbusRequest::parse_response_typed_ref)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ResponseDataRef<'a> {
+    /// packed bits (LSB first)
+    Coils(&'a [u8]),
+    /// packed bits (LSB first)
+    Discretes(&'a [u8]),
+    /// register bytes (big-endian), also returned for read/write multiple registers
+    Holdings(&'a [u8]),
+    /// register bytes (big-endian)
+    Inputs(&'a [u8]),
+    /// write functions
+    WriteOk,
+    /// sub-function, data
+    Diagnostics(u16, u16),
+    /// exception status byte
+    ExceptionStatus(u8),
+    /// status word, event count
+    CommEventCounter(u16, u16),
+    /// server id, run indicator status
+    ServerId(&'a [u8], bool),
+    /// header, encoded objects (object id, length, value)
+    DeviceId(DeviceIdInfo, &'a [u8]),
+}
+
+/// Response data, decoded according to the request function, see
+/// [`parse_response_typed`](ModbusRequest::parse_response_typed)
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ResponseData {
+    Coils(Vec<bool>),
+    Discretes(Vec<bool>),
+    /// also returned for read/write multiple registers
+    Holdings(Vec<u16>),
+    Inputs(Vec<u16>),
+    /// write functions
+    WriteOk,
+    /// sub-function, data
+    Diagnostics(u16, u16),
+    /// exception status byte
+    ExceptionStatus(u8),
+    /// status word, event count
+    CommEventCounter(u16, u16),
+    /// server id, run indicator status
+    ServerId(Vec<u8>, bool),
+    /// header, (object id, value) pairs
+    DeviceId(DeviceIdInfo, Vec<(u8, Vec<u8>)>),
+}
+
+/// Response data offset from the frame start (unit id), depending on the function
+///
+/// Every function, generated by [`ModbusRequest`], must be listed explicitly (checked by
+/// test_std_client_response_data_offset), unknown functions are not guessed
+pub(crate) fn response_data_offset(func: u8) -> Option<usize> {
+    match func {
+        // byte count, then data
+        MODBUS_GET_COILS
+        | MODBUS_GET_DISCRETES
+        | MODBUS_GET_HOLDINGS
+        | MODBUS_GET_INPUTS
+        | MODBUS_REPORT_SERVER_ID
+        | MODBUS_READ_WRITE_MULTIPLE_REGISTERS
+        | MODBUS_READ_FILE_RECORD => Some(3),
+        // data follows the function code
+        MODBUS_SET_COIL
+        | MODBUS_SET_HOLDING
+        | MODBUS_SET_COILS_BULK
+        | MODBUS_SET_HOLDINGS_BULK
+        | MODBUS_MASK_WRITE_REGISTER
+        | MODBUS_DIAGNOSTICS
+        | MODBUS_READ_EXCEPTION_STATUS
+        | MODBUS_GET_COMM_EVENT_COUNTER
+        | MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT => Some(2),
+        _ => None,
+    }
 }
 
 macro_rules! parse_reg {
     ($self: expr, $buf: expr, $result: expr, $t: ty) => {{
-        let (frame_start, frame_end) = $self.parse_response($buf)?;
-        let mut pos = frame_start + 3;
-        while pos < frame_end - 1 {
+        let header = $self.parse_header($buf)?;
+        let mut pos = header.data_start();
+        while pos < header.frame_end - 1 {
             let value = <$t>::from_be_bytes([$buf[pos], $buf[pos + 1]]);
-            if $result.len() >= usize::from($self.count) {
+            if $result.len() >= $self.reg_data_len() / 2 {
                 break;
             }
             $result.push(value)?;
@@ -34,6 +258,22 @@ macro_rules! parse_reg {
     }};
 }
 
+macro_rules! parse_regs {
+    ($self: expr, $buf: expr, $result: expr, $t: ty, $size: expr) => {{
+        let header = $self.parse_header($buf)?;
+        let mut pos = header.data_start();
+        while pos + $size <= header.frame_end {
+            if $result.len() >= $self.reg_data_len() / $size {
+                break;
+            }
+            let mut bytes = [0u8; $size];
+            bytes.copy_from_slice(&$buf[pos..pos + $size]);
+            $result.push(<$t>::from_be_bytes(bytes))?;
+            pos += $size;
+        }
+    }};
+}
+
 impl ModbusRequest {
     /// Crate new Modbus client
     pub fn new(unit_id: u8, proto: ModbusProto) -> Self {
@@ -44,6 +284,11 @@ impl ModbusRequest {
             reg: 0,
             count: 0,
             proto,
+            auto_tr_id: false,
+            enron: false,
+            any_unit_id: false,
+            retries: 0,
+            attempt: 0,
         }
     }
 
@@ -54,8 +299,148 @@ impl ModbusRequest {
             func: 0,
             reg: 0,
             count: 0,
-            proto: ModbusProto::TcpUdp,
+            proto: ModbusProto::Tcp,
+            auto_tr_id: false,
+            enron: false,
+            any_unit_id: false,
+            retries: 0,
+            attempt: 0,
+        }
+    }
+
+    /// If enabled, the transaction id is incremented (see
+    /// [`next_transaction`](ModbusRequest::next_transaction)) every time a TCP/UDP request is
+    /// generated, so each request gets an unique id. RTU/ASCII ignore the transaction id.
+    pub fn auto_increment_tr_id(&mut self, value: bool) {
+        self.auto_tr_id = value;
+    }
+
+    /// Enron (Daniel) Modbus mode, used by some flow computers: registers read with functions 3-4
+    /// are 32-bit, so count is a number of 32-bit registers (max 62 per request) and each one is
+    /// transmitted as 4 bytes. Non-standard, disabled by default.
+    ///
+    /// ```
+    /// use rmodbus::{client::ModbusRequest, ModbusProto};
+    ///
+    /// let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    /// mreq.enron_mode(true);
+    /// let mut request = Vec::new();
+    /// // two 32-bit registers
+    /// mreq.generate_get_holdings(7001, 2, &mut request).unwrap();
+    /// let response = [
+    ///     1, 3, 8, 0, 1, 0xe2, 0x40, 0xff, 0xff, 0xff, 0xfe, 0x53, 0x0e,
+    /// ];
+    /// let mut result = Vec::new();
+    /// mreq.parse_u32(&response, &mut result).unwrap();
+    /// assert_eq!(result, [123_456, 0xffff_fffe]);
+    /// ```
+    pub fn enron_mode(&mut self, value: bool) {
+        self.enron = value;
+    }
+
+    /// Accept responses with any unit id (some serial devices reply with 0 or their own id on
+    /// point-to-point links). Function code and CRC/LRC are still checked. Non-standard,
+    /// disabled by default.
+    pub fn accept_any_unit_id(&mut self, value: bool) {
+        self.any_unit_id = value;
+    }
+
+    /// Retry a request up to n times on transmission errors (CRC / LRC mismatch) and retryable
+    /// exceptions (see [`ErrorKind::is_retryable`]), see
+    /// [`parse_ok_retryable`](ModbusRequest::parse_ok_retryable). Default: 0 (no retries)
+    ///
+    /// The I/O is performed by the caller, the request only classifies errors and counts
+    /// attempts
+    pub fn set_retries(&mut self, n: u8) {
+        self.retries = n;
+        self.attempt = 0;
+    }
+
+    /// The number of failed attempts of the current request (see
+    /// [`parse_ok_retryable`](ModbusRequest::parse_ok_retryable))
+    pub fn attempt(&self) -> u8 {
+        self.attempt
+    }
+
+    /// Reset the state of the last generated request (function, register, count and the retry
+    /// attempt counter), the unit id, transaction id, protocol and options are kept
+    ///
+    /// Each `generate_*` / `parse_*` pair should bracket a transaction: responses are parsed
+    /// according to the last generated request. After the reset, all responses are rejected
+    /// until a new request is generated.
+    ///
+    /// ```
+    /// use rmodbus::{client::ModbusRequest, ModbusProto};
+    ///
+    /// let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    /// let mut request = Vec::new();
+    /// mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    /// mreq.reset();
+    /// let response = [1, 3, 2, 0, 7, 0xf9, 0x86];
+    /// let mut result = Vec::new();
+    /// assert!(mreq.parse_u16(&response, &mut result).is_err());
+    /// ```
+    pub fn reset(&mut self) {
+        self.func = 0;
+        self.reg = 0;
+        self.count = 0;
+        self.attempt = 0;
+    }
+
+    /// Increment the transaction id (wrapping at u16::MAX) and return the new value
+    pub fn next_transaction(&mut self) -> u16 {
+        self.tr_id = self.tr_id.wrapping_add(1);
+        self.tr_id
+    }
+
+    /// Update the transaction id of a previously generated request frame in place, to repeat the
+    /// request (e.g. polling) without generating it again
+    ///
+    /// If [`auto_increment_tr_id`](ModbusRequest::auto_increment_tr_id) is enabled, the
+    /// transaction id is incremented first. Only the first two bytes of the frame are rewritten.
+    /// RTU/ASCII frames have no transaction id and are left untouched.
+    ///
+    /// ```
+    /// use rmodbus::{client::ModbusRequest, ModbusProto};
+    ///
+    /// let mut mreq = ModbusRequest::new_tcp_udp(1, 1);
+    /// mreq.auto_increment_tr_id(true);
+    /// let mut request = Vec::new();
+    /// mreq.generate_get_holdings(0, 2, &mut request).unwrap();
+    /// assert_eq!(request[..2], [0, 2]);
+    /// mreq.refresh_tr_id(&mut request).unwrap();
+    /// assert_eq!(request[..2], [0, 3]);
+    /// ```
+    pub fn refresh_tr_id(&mut self, frame: &mut [u8]) -> Result<(), ErrorKind> {
+        if !self.proto.has_mbap_header() {
+            return Ok(());
         }
+        if frame.len() < 2 {
+            return Err(ErrorKind::OOB);
+        }
+        if self.auto_tr_id {
+            self.next_transaction();
+        }
+        frame[..2].copy_from_slice(&self.tr_id.to_be_bytes());
+        Ok(())
+    }
+
+    /// Generate an exception response (function + 0x80, error code) to the last generated
+    /// request, useful to test error handling without a server
+    ///
+    /// For ASCII, the response is generated as binary (with LRC), the same as requests
+    pub fn generate_error_response<V: VectorTrait<u8>>(
+        &self,
+        code: u8,
+        response: &mut V,
+    ) -> Result<(), ErrorKind> {
+        response.clear();
+        if self.proto.has_mbap_header() {
+            response.extend(&self.tr_id.to_be_bytes())?;
+            response.extend(&[0u8, 0, 0, 0])?;
+        }
+        response.extend(&[self.unit_id, self.func | 0x80, code])?;
+        self.finalize_frame(response)
     }
 
     pub fn generate_get_coils<V: VectorTrait<u8>>(
@@ -64,6 +449,9 @@ impl ModbusRequest {
         count: u16,
         request: &mut V,
     ) -> Result<(), ErrorKind> {
+        if count > 2000 {
+            return Err(ErrorKind::OOB);
+        }
         self.reg = reg;
         self.count = count;
         self.func = MODBUS_GET_COILS;
@@ -76,6 +464,9 @@ impl ModbusRequest {
         count: u16,
         request: &mut V,
     ) -> Result<(), ErrorKind> {
+        if count > 2000 {
+            return Err(ErrorKind::OOB);
+        }
         self.reg = reg;
         self.count = count;
         self.func = MODBUS_GET_DISCRETES;
@@ -88,18 +479,56 @@ impl ModbusRequest {
         count: u16,
         request: &mut V,
     ) -> Result<(), ErrorKind> {
+        if count > self.max_read_regs() {
+            return Err(ErrorKind::OOB);
+        }
         self.reg = reg;
         self.count = count;
         self.func = MODBUS_GET_HOLDINGS;
         self.generate(&[], request)
     }
 
+    /// Generate requests to get holdings, split into chunks of max 125 registers (62 in
+    /// [Enron mode](ModbusRequest::enron_mode)), as a single read request can not get more
+    ///
+    /// After the call, reg and count are set to the whole range, the responses (in the same
+    /// order as requests) can be parsed with
+    /// [`parse_u16_chunked`](ModbusRequest::parse_u16_chunked)
+    #[cfg(feature = "std")]
+    pub fn generate_get_holdings_chunked(
+        &mut self,
+        reg: u16,
+        count: u16,
+        requests: &mut Vec<Vec<u8>>,
+    ) -> Result<(), ErrorKind> {
+        if u32::from(reg) + u32::from(count) > u32::from(u16::MAX) + 1 {
+            return Err(ErrorKind::OOB);
+        }
+        requests.clear();
+        let max = self.max_read_regs();
+        let mut offset = 0;
+        while offset < count {
+            let chunk = (count - offset).min(max);
+            let mut request = Vec::new();
+            self.generate_get_holdings(reg + offset, chunk, &mut request)?;
+            requests.push(request);
+            offset += chunk;
+        }
+        self.reg = reg;
+        self.count = count;
+        self.func = MODBUS_GET_HOLDINGS;
+        Ok(())
+    }
+
     pub fn generate_get_inputs<V: VectorTrait<u8>>(
         &mut self,
         reg: u16,
         count: u16,
         request: &mut V,
     ) -> Result<(), ErrorKind> {
+        if count > self.max_read_regs() {
+            return Err(ErrorKind::OOB);
+        }
         self.reg = reg;
         self.count = count;
         self.func = MODBUS_GET_INPUTS;
@@ -141,6 +570,32 @@ impl ModbusRequest {
         self.generate(&value.to_be_bytes(), request)
     }
 
+    #[allow(clippy::cast_sign_loss)]
+    pub fn generate_set_holding_i16<V: VectorTrait<u8>>(
+        &mut self,
+        reg: u16,
+        value: i16,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.generate_set_holding(reg, value as u16, request)
+    }
+
+    /// The holding is set to (current AND and_mask) OR (or_mask AND (NOT and_mask))
+    pub fn generate_mask_write_register<V: VectorTrait<u8>>(
+        &mut self,
+        reg: u16,
+        and_mask: u16,
+        or_mask: u16,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.reg = reg;
+        self.count = 1;
+        self.func = MODBUS_MASK_WRITE_REGISTER;
+        let a = and_mask.to_be_bytes();
+        let o = or_mask.to_be_bytes();
+        self.generate(&[a[0], a[1], o[0], o[1]], request)
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     pub fn generate_set_holdings_bulk<V: VectorTrait<u8>>(
         &mut self,
@@ -148,7 +603,7 @@ impl ModbusRequest {
         values: &[u16],
         request: &mut V,
     ) -> Result<(), ErrorKind> {
-        if values.len() > 125 {
+        if values.len() > 123 {
             return Err(ErrorKind::OOB);
         }
         self.reg = reg;
@@ -164,6 +619,54 @@ impl ModbusRequest {
         self.generate(&data[..values.len() * 2], request)
     }
 
+    #[allow(clippy::cast_sign_loss)]
+    pub fn generate_set_holdings_bulk_i16<V: VectorTrait<u8>>(
+        &mut self,
+        reg: u16,
+        values: &[i16],
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        if values.len() > 123 {
+            return Err(ErrorKind::OOB);
+        }
+        let mut data = [0u16; 123];
+        for (d, v) in data.iter_mut().zip(values) {
+            *d = *v as u16;
+        }
+        self.generate_set_holdings_bulk(reg, &data[..values.len()], request)
+    }
+
+    /// Writes holdings starting from write_reg, then reads count holdings starting from read_reg
+    ///
+    /// The response can be parsed with the same methods as for getting holdings
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn generate_read_write_multiple_registers<V: VectorTrait<u8>>(
+        &mut self,
+        read_reg: u16,
+        count: u16,
+        write_reg: u16,
+        values: &[u16],
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        if count > 125 || values.len() > 121 {
+            return Err(ErrorKind::OOB);
+        }
+        self.reg = read_reg;
+        self.count = count;
+        self.func = MODBUS_READ_WRITE_MULTIPLE_REGISTERS;
+        let mut data: ModbusFrameBuf = [0; 256];
+        data[0..2].copy_from_slice(&write_reg.to_be_bytes());
+        data[2..4].copy_from_slice(&(values.len() as u16).to_be_bytes());
+        data[4] = (values.len() * 2) as u8;
+        let mut pos = 5;
+        for v in values {
+            data[pos] = (v >> 8) as u8;
+            data[pos + 1] = *v as u8;
+            pos += 2;
+        }
+        self.generate(&data[..pos], request)
+    }
+
     pub fn generate_set_holdings_bulk_from_slice<V: VectorTrait<u8>>(
         &mut self,
         reg: u16,
@@ -183,16 +686,27 @@ impl ModbusRequest {
         self.generate(&data[..values.len()], request)
     }
 
-    #[allow(clippy::cast_possible_truncation)]
     pub fn generate_set_holdings_string<V: VectorTrait<u8>>(
         &mut self,
         reg: u16,
         values: &str,
         request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.generate_set_holdings_string_with_swap(reg, values, false, request)
+    }
+
+    /// If swap is true, the string is packed low-byte-first within each register
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn generate_set_holdings_string_with_swap<V: VectorTrait<u8>>(
+        &mut self,
+        reg: u16,
+        values: &str,
+        swap: bool,
+        request: &mut V,
     ) -> Result<(), ErrorKind> {
         let values = values.as_bytes();
         let length = values.len() + values.len() % 2;
-        if length > 250 {
+        if length > 246 {
             return Err(ErrorKind::OOB);
         }
         self.reg = reg;
@@ -202,6 +716,11 @@ impl ModbusRequest {
         for (pos, v) in values.iter().enumerate() {
             data[pos] = *v;
         }
+        if swap {
+            for pair in data[..length].chunks_exact_mut(2) {
+                pair.swap(0, 1);
+            }
+        }
         self.generate(&data[..length], request)
     }
 
@@ -212,13 +731,30 @@ impl ModbusRequest {
         reg: u16,
         values: &[S],
         request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        if values.len() > 1968 {
+            return Err(ErrorKind::OOB);
+        }
+        self.generate_set_coils_bulk_count(reg, values, values.len() as u16, request)
+    }
+
+    /// Sets count coils, which may be less than the number of values (all values are packed and
+    /// sent)
+    ///
+    /// values can be u8 or bool
+    pub fn generate_set_coils_bulk_count<V: VectorTrait<u8>, S: Into<u8> + Copy>(
+        &mut self,
+        reg: u16,
+        values: &[S],
+        count: u16,
+        request: &mut V,
     ) -> Result<(), ErrorKind> {
         let l = values.len();
-        if l > 4000 {
+        if l > 1968 || usize::from(count) > l {
             return Err(ErrorKind::OOB);
         }
         self.reg = reg;
-        self.count = l as u16;
+        self.count = count;
         self.func = MODBUS_SET_COILS_BULK;
         let mut data: ModbusFrameBuf = [0; 256];
         let mut pos = 0;
@@ -246,9 +782,175 @@ impl ModbusRequest {
         self.generate(&data[..len], request)
     }
 
+    /// Sets count coils from already packed bytes (LSB first), which are sent as-is (e.g. when
+    /// forwarding a payload from another frame)
+    ///
+    /// packed length must be exactly count / 8, rounded up
+    pub fn generate_set_coils_bulk_from_bytes<V: VectorTrait<u8>>(
+        &mut self,
+        reg: u16,
+        count: u16,
+        packed: &[u8],
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        if count > 1968 || packed.len() != usize::from((count + 7) / 8) {
+            return Err(ErrorKind::OOB);
+        }
+        self.reg = reg;
+        self.count = count;
+        self.func = MODBUS_SET_COILS_BULK;
+        self.generate(packed, request)
+    }
+
+    /// Generate a request from a server-side write descriptor (see
+    /// [`ModbusFrame::get_external_write`](crate::server::ModbusFrame::get_external_write)), e.g.
+    /// to forward writes to a downstream device
+    ///
+    /// The descriptor has no function code, so single coil / register writes are generated as
+    /// functions 5 / 6, multiple ones as functions 15 / 16
+    ///
+    /// Errors:
+    ///
+    /// * **OOB** the descriptor data does not match the count
+    pub fn generate_from_write<V: VectorTrait<u8>>(
+        &mut self,
+        write: &Write,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        match write {
+            Write::Bits(bits) if bits.count == 1 => {
+                let value = bits.data.first().ok_or(ErrorKind::OOB)?;
+                self.generate_set_coil(bits.address, value & 1, request)
+            }
+            Write::Bits(bits) => self.generate_set_coils_bulk_from_bytes(
+                bits.address,
+                bits.count,
+                bits.data,
+                request,
+            ),
+            Write::Words(words) => {
+                if words.data.len() != usize::from(words.count) * 2 {
+                    return Err(ErrorKind::OOB);
+                }
+                if words.count == 1 {
+                    let value = u16::from_be_bytes([words.data[0], words.data[1]]);
+                    self.generate_set_holding(words.address, value, request)
+                } else {
+                    self.generate_set_holdings_bulk_from_slice(words.address, words.data, request)
+                }
+            }
+        }
+    }
+
+    /// Diagnostics (function 0x08)
+    ///
+    /// See `MODBUS_DIAG_*` in [`consts`](crate::consts) for sub-functions
+    pub fn generate_diagnostics<V: VectorTrait<u8>>(
+        &mut self,
+        sub_function: u16,
+        data: u16,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.reg = sub_function;
+        self.count = 1;
+        self.func = MODBUS_DIAGNOSTICS;
+        self.generate(&data.to_be_bytes(), request)
+    }
+
+    /// Read Exception Status (function 0x07)
+    pub fn generate_read_exception_status<V: VectorTrait<u8>>(
+        &mut self,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.reg = 0;
+        self.count = 0;
+        self.func = MODBUS_READ_EXCEPTION_STATUS;
+        self.generate(&[], request)
+    }
+
+    /// Get Comm Event Counter (function 0x0B)
+    pub fn generate_get_comm_event_counter<V: VectorTrait<u8>>(
+        &mut self,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.reg = 0;
+        self.count = 0;
+        self.func = MODBUS_GET_COMM_EVENT_COUNTER;
+        self.generate(&[], request)
+    }
+
+    /// Report Server ID (function 0x11)
+    pub fn generate_report_server_id<V: VectorTrait<u8>>(
+        &mut self,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.reg = 0;
+        self.count = 0;
+        self.func = MODBUS_REPORT_SERVER_ID;
+        self.generate(&[], request)
+    }
+
+    /// Read Device Identification (function 0x2B, MEI type 0x0E)
+    ///
+    /// read_code - 1-3 for stream access (basic, regular, extended), 4 for individual access
+    /// (see `MODBUS_DEVICE_ID_READ_*` in [`consts`](crate::consts))
+    ///
+    /// object_id - the object to start from (0 to read from the beginning)
+    pub fn generate_read_device_id<V: VectorTrait<u8>>(
+        &mut self,
+        read_code: u8,
+        object_id: u8,
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.reg = u16::from(object_id);
+        self.count = 1;
+        self.func = MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT;
+        self.generate(&[MODBUS_MEI_READ_DEVICE_ID, read_code, object_id], request)
+    }
+
+    /// Read File Record (function 0x14), each group reads record_length registers of a file
+    ///
+    /// Errors:
+    ///
+    /// * **OOB** no groups, a record number above 9999 or the response would not fit into a
+    ///   frame
+    pub fn generate_read_file_record<V: VectorTrait<u8>>(
+        &mut self,
+        groups: &[FileRecordRequest],
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
+        // byte count, then 7 bytes per group, the byte count is 0x07-0xF5
+        let mut data = [0u8; 246];
+        let mut len = 1;
+        // the response data length, 2 bytes (length, reference type) + data per group
+        let mut response_len = 0;
+        for group in groups {
+            if len + 7 > data.len() || group.record_number > 9999 {
+                return Err(ErrorKind::OOB);
+            }
+            response_len += 2 + usize::from(group.record_length) * 2;
+            data[len] = MODBUS_FILE_RECORD_REFERENCE_TYPE;
+            data[len + 1..len + 3].copy_from_slice(&group.file_number.to_be_bytes());
+            data[len + 3..len + 5].copy_from_slice(&group.record_number.to_be_bytes());
+            data[len + 5..len + 7].copy_from_slice(&group.record_length.to_be_bytes());
+            len += 7;
+        }
+        if groups.is_empty() || response_len > 0xF5 {
+            return Err(ErrorKind::OOB);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            data[0] = (len - 1) as u8;
+            self.count = groups.len() as u16;
+        }
+        self.reg = 0;
+        self.func = MODBUS_READ_FILE_RECORD;
+        self.generate(&data[..len], request)
+    }
+
     fn parse_response(&self, buf: &[u8]) -> Result<(usize, usize), ErrorKind> {
         let (frame_start, frame_end) = match self.proto {
-            ModbusProto::TcpUdp => {
+            ModbusProto::Tcp | ModbusProto::Udp => {
                 let l = buf.len();
                 if l < 9 {
                     return Err(ErrorKind::FrameBroken);
@@ -258,9 +960,15 @@ impl ModbusRequest {
                 if tr_id != self.tr_id || proto != 0 {
                     return Err(ErrorKind::FrameBroken);
                 }
+                // UDP datagrams must contain exactly one frame
+                if self.proto == ModbusProto::Udp
+                    && usize::from(u16::from_be_bytes([buf[4], buf[5]])) + 6 != l
+                {
+                    return Err(ErrorKind::FrameBroken);
+                }
                 (6, l)
             }
-            ModbusProto::Rtu => {
+            ModbusProto::Rtu | ModbusProto::RtuOverTcp => {
                 let mut l = buf.len();
                 if l < 5 {
                     return Err(ErrorKind::FrameBroken);
@@ -288,29 +996,60 @@ impl ModbusRequest {
                 #[allow(clippy::cast_possible_truncation)]
                 let lrc = calc_lrc(buf, l as u8);
                 if lrc != buf[l] {
-                    return Err(ErrorKind::FrameCRCError);
+                    return Err(ErrorKind::FrameLRCError);
                 }
                 (0, l)
             }
         };
         let unit_id = buf[frame_start];
         let func = buf[frame_start + 1];
-        if unit_id != self.unit_id {
+        if unit_id != self.unit_id && !self.any_unit_id {
             return Err(ErrorKind::FrameBroken);
         }
         if func != self.func {
             // func-0x80 but some servers respond any shit
             return Err(ErrorKind::from_modbus_error(buf[frame_start + 2]));
         }
-        if self.func > 0 && self.func < 5 {
+        let expected_len = match self.func {
+            MODBUS_GET_COILS | MODBUS_GET_DISCRETES => Some((usize::from(self.count) + 7) / 8),
+            MODBUS_GET_HOLDINGS | MODBUS_GET_INPUTS | MODBUS_READ_WRITE_MULTIPLE_REGISTERS => {
+                Some(self.reg_data_len())
+            }
+            _ => None,
+        };
+        if let Some(expected_len) = expected_len {
+            // the byte count must match the requested count and the data must be complete
             let len = buf[frame_start + 2] as usize;
-            if len * 2 < (frame_end - frame_start) - 3 {
+            if len != expected_len || len > (frame_end - frame_start) - 3 {
                 return Err(ErrorKind::FrameBroken);
             }
         }
         Ok((frame_start, frame_end))
     }
 
+    /// Parse response, make sure there's no Modbus error inside and return the response header
+    ///
+    /// Returns IllegalFunction if the response data layout of the function is unknown
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_header(&self, buf: &[u8]) -> Result<ResponseHeader, ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        let func = buf[frame_start + 1];
+        let offset = response_data_offset(func).ok_or(ErrorKind::IllegalFunction)?;
+        let byte_count = if offset == 3 && frame_end > frame_start + 2 {
+            Some(buf[frame_start + 2])
+        } else {
+            None
+        };
+        Ok(ResponseHeader {
+            unit_id: buf[frame_start],
+            func,
+            byte_count,
+            frame_start,
+            frame_end,
+        })
+    }
+
     /// Parse response and make sure there's no Modbus error inside
     ///
     /// The input buffer SHOULD be cut to actual response length
@@ -319,6 +1058,65 @@ impl ModbusRequest {
         Ok(())
     }
 
+    /// Same as [`parse_ok`](ModbusRequest::parse_ok), but returns the raw exception code along
+    /// with the error, e.g. to log vendor-specific codes, which are mapped to
+    /// [`ErrorKind::UnknownError`]
+    ///
+    /// The code is zero if the error is not a Modbus exception (e.g. the frame is broken)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_ok_detailed(&self, buf: &[u8]) -> Result<(), (ErrorKind, u8)> {
+        match self.parse_response(buf) {
+            Ok(_) => Ok(()),
+            Err(e) if e.is_modbus_error() || e == ErrorKind::UnknownError => {
+                let frame_start = if self.proto.has_mbap_header() { 6 } else { 0 };
+                Err((e, buf[frame_start + 2]))
+            }
+            Err(e) => Err((e, 0)),
+        }
+    }
+
+    /// Same as [`parse_ok`](ModbusRequest::parse_ok), but classifies errors for retries (see
+    /// [`set_retries`](ModbusRequest::set_retries)): if the error is retryable and the retry
+    /// limit is not reached yet, the returned decision asks to send the same request again
+    ///
+    /// The attempt counter is reset on success and when the decision is not to retry
+    ///
+    /// ```
+    /// use rmodbus::{client::ModbusRequest, ModbusProto};
+    ///
+    /// let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    /// mreq.set_retries(1);
+    /// let mut request = Vec::new();
+    /// mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    /// // the CRC is broken
+    /// let response = [1, 3, 2, 0, 5, 0, 0];
+    /// assert!(mreq.parse_ok_retryable(&response).unwrap_err().retry);
+    /// assert!(!mreq.parse_ok_retryable(&response).unwrap_err().retry);
+    /// ```
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_ok_retryable(&mut self, buf: &[u8]) -> Result<(), RetryDecision> {
+        match self.parse_response(buf) {
+            Ok(_) => {
+                self.attempt = 0;
+                Ok(())
+            }
+            Err(error) => {
+                let attempt = self.attempt.saturating_add(1);
+                let retry = attempt <= self.retries
+                    && (error.is_retryable()
+                        || matches!(error, ErrorKind::FrameCRCError | ErrorKind::FrameLRCError));
+                self.attempt = if retry { attempt } else { 0 };
+                Err(RetryDecision {
+                    retry,
+                    attempt,
+                    error,
+                })
+            }
+        }
+    }
+
     /// Parse response, make sure there's no Modbus error inside, plus parse response data as u16
     /// (getting holdings, inputs)
     ///
@@ -332,6 +1130,44 @@ impl ModbusRequest {
         Ok(())
     }
 
+    /// Parse responses to requests, generated with
+    /// [`generate_get_holdings_chunked`](ModbusRequest::generate_get_holdings_chunked), and
+    /// reassemble the data as u16
+    ///
+    /// The responses must be in the same order as requests and SHOULD be cut to actual response
+    /// lengths
+    #[cfg(feature = "std")]
+    pub fn parse_u16_chunked<R: AsRef<[u8]>>(
+        &self,
+        responses: &[R],
+        result: &mut Vec<u16>,
+    ) -> Result<(), ErrorKind> {
+        let max = self.max_read_regs();
+        let chunks = self.count / max + u16::from(self.count % max > 0);
+        if responses.len() != usize::from(chunks) {
+            return Err(ErrorKind::OOB);
+        }
+        let mut offset = 0;
+        for (n, response) in (1..=chunks).rev().zip(responses) {
+            let chunk = ModbusRequest {
+                // with auto-increment, the request object keeps the id of the last chunk
+                tr_id: if self.auto_tr_id {
+                    self.tr_id.wrapping_sub(n - 1)
+                } else {
+                    self.tr_id
+                },
+                reg: self.reg + offset,
+                count: (self.count - offset).min(max),
+                ..*self
+            };
+            let mut values = Vec::new();
+            chunk.parse_u16(response.as_ref(), &mut values)?;
+            result.extend_from_slice(&values);
+            offset += chunk.count;
+        }
+        Ok(())
+    }
+
     /// Parse response, make sure there's no Modbus error inside, plus parse response data as i16
     /// (getting holdings, inputs)
     ///
@@ -345,14 +1181,135 @@ impl ModbusRequest {
         Ok(())
     }
 
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as
+    /// u32, 2 registers per value (getting holdings, inputs)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_u32<V: VectorTrait<u32>>(
+        &self,
+        buf: &[u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        parse_regs!(self, buf, result, u32, 4);
+        Ok(())
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as
+    /// i32, 2 registers per value (getting holdings, inputs)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_i32<V: VectorTrait<i32>>(
+        &self,
+        buf: &[u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        parse_regs!(self, buf, result, i32, 4);
+        Ok(())
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as
+    /// IEEE754 f32, 2 registers per value (getting holdings, inputs)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_f32<V: VectorTrait<f32>>(
+        &self,
+        buf: &[u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        parse_regs!(self, buf, result, f32, 4);
+        Ok(())
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as
+    /// fixed-point values: each register is read as u16 and multiplied by scale (e.g. 0.1 for
+    /// devices, which store value * 10)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_scaled_f32<V: VectorTrait<f32>>(
+        &self,
+        buf: &[u8],
+        scale: f32,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        let header = self.parse_header(buf)?;
+        let mut pos = header.data_start();
+        while pos < header.frame_end - 1 {
+            if result.len() >= self.reg_data_len() / 2 {
+                break;
+            }
+            let value = u16::from_be_bytes([buf[pos], buf[pos + 1]]);
+            result.push(f32::from(value) * scale)?;
+            pos += 2;
+        }
+        Ok(())
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as
+    /// u64, 4 registers per value (getting holdings, inputs)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_u64<V: VectorTrait<u64>>(
+        &self,
+        buf: &[u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        parse_regs!(self, buf, result, u64, 8);
+        Ok(())
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as
+    /// i64, 4 registers per value (getting holdings, inputs)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_i64<V: VectorTrait<i64>>(
+        &self,
+        buf: &[u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        parse_regs!(self, buf, result, i64, 8);
+        Ok(())
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as
+    /// IEEE754 f64, 4 registers per value (getting holdings, inputs)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_f64<V: VectorTrait<f64>>(
+        &self,
+        buf: &[u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        parse_regs!(self, buf, result, f64, 8);
+        Ok(())
+    }
+
     /// Parse response, make sure there's no Modbus error inside, plus parse response data as u16
     /// (getting holdings, inputs)
     ///
     /// The input buffer SHOULD be cut to actual response length
     #[cfg(feature = "std")]
     pub fn parse_string(&self, buf: &[u8], result: &mut String) -> Result<(), ErrorKind> {
+        self.parse_string_with_swap(buf, false, result)
+    }
+
+    /// Same as [`parse_string`](ModbusRequest::parse_string), if swap is true, the string is
+    /// considered to be packed low-byte-first within each register
+    #[cfg(feature = "std")]
+    pub fn parse_string_with_swap(
+        &self,
+        buf: &[u8],
+        swap: bool,
+        result: &mut String,
+    ) -> Result<(), ErrorKind> {
         let (frame_start, frame_end) = self.parse_response(buf)?;
-        let val = &buf[frame_start + 3..frame_end];
+        let mut data: ModbusFrameBuf = [0; 256];
+        let val = &mut data[..frame_end - frame_start - 3];
+        val.copy_from_slice(&buf[frame_start + 3..frame_end]);
+        if swap {
+            for pair in val.chunks_exact_mut(2) {
+                pair.swap(0, 1);
+            }
+        }
         let vl = val.iter().position(|&c| c == b'\0').unwrap_or(val.len());
         *result = match std::str::from_utf8(&val[..vl]) {
             Ok(v) => v.to_string(),
@@ -362,35 +1319,28 @@ impl ModbusRequest {
     }
 
     /// Parse response, make sure there's no Modbus error inside
-    /// Returns a raw data slice
+    /// Returns a raw data slice (after the byte count for functions which responses have it)
     ///
     /// The input buffer SHOULD be cut to actual response length
     pub fn parse_slice<'a>(&'a self, buf: &'a [u8]) -> Result<&'a [u8], ErrorKind> {
-        let (frame_start, frame_end) = self.parse_response(buf)?;
-        let val = match self.func {
-            MODBUS_SET_COIL
-            | MODBUS_SET_COILS_BULK
-            | MODBUS_SET_HOLDING
-            | MODBUS_SET_HOLDINGS_BULK => {
-                // no data bytes count byte -> skip 1 fewer byte
-                &buf[frame_start + 2..frame_end]
-            }
-            _ => &buf[frame_start + 3..frame_end],
-        };
-        Ok(val)
+        let header = self.parse_header(buf)?;
+        Ok(&buf[header.data_start()..header.frame_end])
     }
 
     /// Parse response, make sure there's no Modbus error inside, plus parse response data as bools
     /// (getting coils, discretes)
     ///
+    /// Padding bits of the last data byte (beyond count) are ignored, see
+    /// [`parse_bool_exact`](ModbusRequest::parse_bool_exact) for the strict version
+    ///
     /// The input buffer SHOULD be cut to actual response length
     pub fn parse_bool<V: VectorTrait<bool>>(
         &self,
         buf: &[u8],
         result: &mut V,
     ) -> Result<(), ErrorKind> {
-        let (frame_start, frame_end) = self.parse_response(buf)?;
-        for b in buf.iter().take(frame_end).skip(frame_start + 3) {
+        let header = self.parse_header(buf)?;
+        for b in &buf[header.data_start()..header.frame_end] {
             for i in 0..8 {
                 if result.len() >= self.count as usize {
                     break;
@@ -401,17 +1351,45 @@ impl ModbusRequest {
         Ok(())
     }
 
+    /// Same as [`parse_bool`](ModbusRequest::parse_bool), but the response is rejected with
+    /// FrameBroken error if any padding bit of the last data byte (beyond count) is set, as the
+    /// padding must be zero-filled by the server (for conformance testing)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_bool_exact<V: VectorTrait<bool>>(
+        &self,
+        buf: &[u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        let header = self.parse_header(buf)?;
+        let data = &buf[header.data_start()..header.frame_end];
+        let count = usize::from(self.count);
+        if count % 8 != 0 {
+            if let Some(last) = data.get(count / 8) {
+                if last >> (count % 8) != 0 {
+                    return Err(ErrorKind::FrameBroken);
+                }
+            }
+        }
+        for i in 0..count.min(data.len() * 8) {
+            result.push(data[i / 8] >> (i % 8) & 1 == 1)?;
+        }
+        Ok(())
+    }
+
     /// Parse response, make sure there's no Modbus error inside, plus parse response data as bools
     /// represented as u8 (getting coils, discretes)
     ///
+    /// Padding bits of the last data byte (beyond count) are ignored
+    ///
     /// The input buffer SHOULD be cut to actual response length
     pub fn parse_bool_u8<V: VectorTrait<u8>>(
         &self,
         buf: &[u8],
         result: &mut V,
     ) -> Result<(), ErrorKind> {
-        let (frame_start, frame_end) = self.parse_response(buf)?;
-        for b in buf.iter().take(frame_end).skip(frame_start + 3) {
+        let header = self.parse_header(buf)?;
+        for b in &buf[header.data_start()..header.frame_end] {
             for i in 0..8 {
                 if result.len() >= self.count as usize {
                     break;
@@ -422,23 +1400,360 @@ impl ModbusRequest {
         Ok(())
     }
 
-    fn generate<V: VectorTrait<u8>>(&self, data: &[u8], request: &mut V) -> Result<(), ErrorKind> {
+    /// Parse response, make sure there's no Modbus error inside, plus parse response data as bools
+    /// into a slice (getting coils, discretes)
+    ///
+    /// Up to count values are written (limited by the slice length), the remaining slice items
+    /// are not modified. Returns the number of values written.
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_bool_into(&self, buf: &[u8], out: &mut [bool]) -> Result<usize, ErrorKind> {
+        let header = self.parse_header(buf)?;
+        let data = &buf[header.data_start()..header.frame_end];
+        let count = usize::from(self.count).min(out.len()).min(data.len() * 8);
+        for (i, v) in out.iter_mut().take(count).enumerate() {
+            *v = data[i / 8] >> (i % 8) & 1 == 1;
+        }
+        Ok(count)
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus copy the packed coils /
+    /// discretes data bytes as received (LSB first, 1 byte = 8 values)
+    ///
+    /// Returns the number of values (the requested count), unused bits of the last byte are
+    /// zero-padded by the server.
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_coils_packed<V: VectorTrait<u8>>(
+        &self,
+        buf: &[u8],
+        out: &mut V,
+    ) -> Result<usize, ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        let len = usize::from(buf[frame_start + 2]);
+        if frame_start + 3 + len > frame_end {
+            return Err(ErrorKind::FrameBroken);
+        }
+        out.extend(&buf[frame_start + 3..frame_start + 3 + len])?;
+        Ok(usize::from(self.count))
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse the write echo
+    ///
+    /// Returns the echoed register and count (functions 15, 16) or value (functions 5, 6), so
+    /// the caller can make sure they match the request
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_write_ack(&self, buf: &[u8]) -> Result<(u16, u16), ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        if frame_end < frame_start + 6 {
+            return Err(ErrorKind::FrameBroken);
+        }
+        Ok((
+            u16::from_be_bytes([buf[frame_start + 2], buf[frame_start + 3]]),
+            u16::from_be_bytes([buf[frame_start + 4], buf[frame_start + 5]]),
+        ))
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse Diagnostics data
+    ///
+    /// Returns the sub-function and the data word
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_diagnostics(&self, buf: &[u8]) -> Result<(u16, u16), ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        if frame_end < frame_start + 6 {
+            return Err(ErrorKind::FrameBroken);
+        }
+        Ok((
+            u16::from_be_bytes([buf[frame_start + 2], buf[frame_start + 3]]),
+            u16::from_be_bytes([buf[frame_start + 4], buf[frame_start + 5]]),
+        ))
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse Read Exception
+    /// Status data
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_exception_status(&self, buf: &[u8]) -> Result<u8, ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        if frame_end < frame_start + 3 {
+            return Err(ErrorKind::FrameBroken);
+        }
+        Ok(buf[frame_start + 2])
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse Get Comm Event
+    /// Counter data
+    ///
+    /// Returns the status word (0xFFFF if the server is busy) and the event count
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_comm_event_counter(&self, buf: &[u8]) -> Result<(u16, u16), ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        if frame_end < frame_start + 6 {
+            return Err(ErrorKind::FrameBroken);
+        }
+        Ok((
+            u16::from_be_bytes([buf[frame_start + 2], buf[frame_start + 3]]),
+            u16::from_be_bytes([buf[frame_start + 4], buf[frame_start + 5]]),
+        ))
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse Report Server ID
+    /// data
+    ///
+    /// Returns the server id bytes and the run indicator status (assuming the indicator is the
+    /// last data byte)
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_server_id<'b>(&self, buf: &'b [u8]) -> Result<(&'b [u8], bool), ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        let data_end = frame_start + 3 + buf[frame_start + 2] as usize;
+        if data_end > frame_end || data_end == frame_start + 3 {
+            return Err(ErrorKind::FrameBroken);
+        }
+        Ok((
+            &buf[frame_start + 3..data_end - 1],
+            buf[data_end - 1] == 0xff,
+        ))
+    }
+
+    fn parse_device_id_header(
+        &self,
+        buf: &[u8],
+    ) -> Result<(DeviceIdInfo, usize, usize), ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        // unit, func, MEI type, read code, conformity level, more follows, next id, count
+        if frame_end < frame_start + 8 || buf[frame_start + 2] != MODBUS_MEI_READ_DEVICE_ID {
+            return Err(ErrorKind::FrameBroken);
+        }
+        let info = DeviceIdInfo {
+            read_code: buf[frame_start + 3],
+            conformity_level: buf[frame_start + 4],
+            more_follows: buf[frame_start + 5] == 0xff,
+            next_object_id: buf[frame_start + 6],
+        };
+        Ok((info, frame_start + 8, frame_end))
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse Read Device
+    /// Identification objects as (object id, value) pairs
+    ///
+    /// If more_follows is set in the returned header, the remaining objects can be read with
+    /// another request, starting from next_object_id
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_device_id<'b, V: VectorTrait<(u8, &'b [u8])>>(
+        &self,
+        buf: &'b [u8],
+        result: &mut V,
+    ) -> Result<DeviceIdInfo, ErrorKind> {
+        let (info, objects_start, frame_end) = self.parse_device_id_header(buf)?;
+        // the number of objects precedes the object list
+        let count = buf[objects_start - 1];
+        let mut pos = objects_start;
+        for _ in 0..count {
+            if pos + 2 > frame_end {
+                return Err(ErrorKind::FrameBroken);
+            }
+            let id = buf[pos];
+            let len = buf[pos + 1] as usize;
+            pos += 2;
+            if pos + len > frame_end {
+                return Err(ErrorKind::FrameBroken);
+            }
+            result.push((id, &buf[pos..pos + len]))?;
+            pos += len;
+        }
+        Ok(info)
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus parse Read File Record
+    /// data: the record data (2 big-endian bytes per register) of each group, in the order of
+    /// the request groups
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_file_records<'b, V: VectorTrait<&'b [u8]>>(
+        &self,
+        buf: &'b [u8],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        if frame_end < frame_start + 3 {
+            return Err(ErrorKind::FrameBroken);
+        }
+        let mut pos = frame_start + 3;
+        let end = pos + usize::from(buf[frame_start + 2]);
+        if end > frame_end {
+            return Err(ErrorKind::FrameBroken);
+        }
+        let mut groups = 0;
+        while pos < end {
+            // the length includes the reference type
+            let len = usize::from(buf[pos]);
+            if pos + 2 > end
+                || len % 2 == 0
+                || buf[pos + 1] != MODBUS_FILE_RECORD_REFERENCE_TYPE
+                || pos + 1 + len > end
+            {
+                return Err(ErrorKind::FrameBroken);
+            }
+            result.push(&buf[pos + 2..pos + 1 + len])?;
+            pos += 1 + len;
+            groups += 1;
+        }
+        if groups != self.count {
+            return Err(ErrorKind::FrameBroken);
+        }
+        Ok(())
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus decode response data
+    /// according to the request function
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    pub fn parse_response_typed_ref<'b>(
+        &self,
+        buf: &'b [u8],
+    ) -> Result<ResponseDataRef<'b>, ErrorKind> {
+        let (frame_start, frame_end) = self.parse_response(buf)?;
+        let data = if frame_end > frame_start + 3 {
+            &buf[frame_start + 3..frame_end]
+        } else {
+            &[]
+        };
+        Ok(match self.func {
+            MODBUS_GET_COILS => ResponseDataRef::Coils(data),
+            MODBUS_GET_DISCRETES => ResponseDataRef::Discretes(data),
+            MODBUS_GET_HOLDINGS | MODBUS_READ_WRITE_MULTIPLE_REGISTERS => {
+                ResponseDataRef::Holdings(data)
+            }
+            MODBUS_GET_INPUTS => ResponseDataRef::Inputs(data),
+            MODBUS_SET_COIL
+            | MODBUS_SET_HOLDING
+            | MODBUS_SET_COILS_BULK
+            | MODBUS_SET_HOLDINGS_BULK
+            | MODBUS_MASK_WRITE_REGISTER => ResponseDataRef::WriteOk,
+            MODBUS_DIAGNOSTICS => {
+                let (sub_function, data) = self.parse_diagnostics(buf)?;
+                ResponseDataRef::Diagnostics(sub_function, data)
+            }
+            MODBUS_READ_EXCEPTION_STATUS => {
+                ResponseDataRef::ExceptionStatus(self.parse_exception_status(buf)?)
+            }
+            MODBUS_GET_COMM_EVENT_COUNTER => {
+                let (status, count) = self.parse_comm_event_counter(buf)?;
+                ResponseDataRef::CommEventCounter(status, count)
+            }
+            MODBUS_REPORT_SERVER_ID => {
+                let (server_id, running) = self.parse_server_id(buf)?;
+                ResponseDataRef::ServerId(server_id, running)
+            }
+            MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT => {
+                let (info, objects_start, objects_end) = self.parse_device_id_header(buf)?;
+                ResponseDataRef::DeviceId(info, &buf[objects_start..objects_end])
+            }
+            _ => return Err(ErrorKind::FrameBroken),
+        })
+    }
+
+    /// Parse response, make sure there's no Modbus error inside, plus decode response data
+    /// according to the request function
+    ///
+    /// The input buffer SHOULD be cut to actual response length
+    #[cfg(feature = "std")]
+    pub fn parse_response_typed(&self, buf: &[u8]) -> Result<ResponseData, ErrorKind> {
+        Ok(match self.parse_response_typed_ref(buf)? {
+            ResponseDataRef::Coils(_) | ResponseDataRef::Discretes(_) => {
+                let mut result = Vec::new();
+                self.parse_bool(buf, &mut result)?;
+                if self.func == MODBUS_GET_COILS {
+                    ResponseData::Coils(result)
+                } else {
+                    ResponseData::Discretes(result)
+                }
+            }
+            ResponseDataRef::Holdings(_) | ResponseDataRef::Inputs(_) => {
+                let mut result = Vec::new();
+                self.parse_u16(buf, &mut result)?;
+                if self.func == MODBUS_GET_INPUTS {
+                    ResponseData::Inputs(result)
+                } else {
+                    ResponseData::Holdings(result)
+                }
+            }
+            ResponseDataRef::WriteOk => ResponseData::WriteOk,
+            ResponseDataRef::Diagnostics(sub_function, data) => {
+                ResponseData::Diagnostics(sub_function, data)
+            }
+            ResponseDataRef::ExceptionStatus(status) => ResponseData::ExceptionStatus(status),
+            ResponseDataRef::CommEventCounter(status, count) => {
+                ResponseData::CommEventCounter(status, count)
+            }
+            ResponseDataRef::ServerId(server_id, running) => {
+                ResponseData::ServerId(server_id.to_vec(), running)
+            }
+            ResponseDataRef::DeviceId(_, _) => {
+                let mut objects = Vec::new();
+                let info = self.parse_device_id(buf, &mut objects)?;
+                ResponseData::DeviceId(
+                    info,
+                    objects
+                        .into_iter()
+                        .map(|(id, value)| (id, value.to_vec()))
+                        .collect(),
+                )
+            }
+        })
+    }
+
+    fn generate<V: VectorTrait<u8>>(
+        &mut self,
+        data: &[u8],
+        request: &mut V,
+    ) -> Result<(), ErrorKind> {
         request.clear();
-        if self.proto == ModbusProto::TcpUdp {
+        if self.proto.has_mbap_header() {
+            if self.auto_tr_id {
+                self.next_transaction();
+            }
             request.extend(&self.tr_id.to_be_bytes())?;
             request.extend(&[0u8, 0, 0, 0])?;
         }
         request.extend(&[self.unit_id, self.func])?;
-        request.extend(&self.reg.to_be_bytes())?;
+        if !matches!(
+            self.func,
+            MODBUS_READ_EXCEPTION_STATUS
+                | MODBUS_GET_COMM_EVENT_COUNTER
+                | MODBUS_REPORT_SERVER_ID
+                | MODBUS_READ_FILE_RECORD
+                | MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT
+        ) {
+            request.extend(&self.reg.to_be_bytes())?;
+        }
         match self.func {
             MODBUS_GET_COILS | MODBUS_GET_DISCRETES | MODBUS_GET_HOLDINGS | MODBUS_GET_INPUTS => {
                 request.extend(&self.count.to_be_bytes())?;
             }
-            MODBUS_SET_COIL | MODBUS_SET_HOLDING => {
+            MODBUS_SET_COIL
+            | MODBUS_SET_HOLDING
+            | MODBUS_DIAGNOSTICS
+            | MODBUS_MASK_WRITE_REGISTER
+            | MODBUS_READ_FILE_RECORD
+            | MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT => {
                 for v in data {
                     request.push(*v)?;
                 }
             }
+            MODBUS_READ_EXCEPTION_STATUS
+            | MODBUS_GET_COMM_EVENT_COUNTER
+            | MODBUS_REPORT_SERVER_ID => {}
+            MODBUS_READ_WRITE_MULTIPLE_REGISTERS => {
+                // data contains write reg, write count, byte count and values
+                request.extend(&self.count.to_be_bytes())?;
+                request.extend(data)?;
+            }
             MODBUS_SET_COILS_BULK | MODBUS_SET_HOLDINGS_BULK => {
                 request.extend(&self.count.to_be_bytes())?;
                 let l = data.len();
@@ -453,8 +1768,32 @@ impl ModbusRequest {
             }
             _ => unimplemented!(),
         };
+        self.finalize_frame(request)
+    }
+
+    fn max_read_regs(&self) -> u16 {
+        if self.enron {
+            62
+        } else {
+            125
+        }
+    }
+
+    /// Register data length (in bytes) of a read response
+    fn reg_data_len(&self) -> usize {
+        let reg_size = if self.enron && matches!(self.func, MODBUS_GET_HOLDINGS | MODBUS_GET_INPUTS)
+        {
+            4
+        } else {
+            2
+        };
+        usize::from(self.count) * reg_size
+    }
+
+    /// Set TCP length or append CRC/LRC
+    fn finalize_frame<V: VectorTrait<u8>>(&self, request: &mut V) -> Result<(), ErrorKind> {
         match self.proto {
-            ModbusProto::TcpUdp => {
+            ModbusProto::Tcp | ModbusProto::Udp => {
                 let mut l = request.len();
                 if l < 6 {
                     return Err(ErrorKind::OOB);
@@ -468,7 +1807,7 @@ impl ModbusRequest {
                 request.replace(4, len_buf[0]);
                 request.replace(5, len_buf[1]);
             }
-            ModbusProto::Rtu => {
+            ModbusProto::Rtu | ModbusProto::RtuOverTcp => {
                 let l = request.len();
                 if l > u8::MAX as usize {
                     return Err(ErrorKind::OOB);
@@ -7,10 +7,56 @@ pub const MODBUS_GET_HOLDINGS: u8 = 3;
 pub const MODBUS_GET_INPUTS: u8 = 4;
 pub const MODBUS_SET_COIL: u8 = 5;
 pub const MODBUS_SET_HOLDING: u8 = 6;
+pub const MODBUS_READ_EXCEPTION_STATUS: u8 = 7;
+pub const MODBUS_DIAGNOSTICS: u8 = 8;
+pub const MODBUS_GET_COMM_EVENT_COUNTER: u8 = 11;
+pub const MODBUS_GET_COMM_EVENT_LOG: u8 = 12;
 pub const MODBUS_SET_COILS_BULK: u8 = 15;
 pub const MODBUS_SET_HOLDINGS_BULK: u8 = 16;
+pub const MODBUS_REPORT_SERVER_ID: u8 = 17;
+pub const MODBUS_READ_FILE_RECORD: u8 = 20;
+pub const MODBUS_MASK_WRITE_REGISTER: u8 = 22;
+pub const MODBUS_READ_WRITE_MULTIPLE_REGISTERS: u8 = 23;
+pub const MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT: u8 = 43;
+
+// MODBUS Diagnostics sub-functions
+pub const MODBUS_DIAG_RETURN_QUERY_DATA: u16 = 0x00;
+pub const MODBUS_DIAG_CLEAR_COUNTERS: u16 = 0x0A;
+pub const MODBUS_DIAG_RETURN_BUS_MESSAGE_COUNT: u16 = 0x0B;
+pub const MODBUS_DIAG_RETURN_BUS_COMMUNICATION_ERROR_COUNT: u16 = 0x0C;
+pub const MODBUS_DIAG_RETURN_BUS_EXCEPTION_ERROR_COUNT: u16 = 0x0D;
+pub const MODBUS_DIAG_RETURN_SERVER_MESSAGE_COUNT: u16 = 0x0E;
+pub const MODBUS_DIAG_RETURN_SERVER_NO_RESPONSE_COUNT: u16 = 0x0F;
+
+// MODBUS Encapsulated Interface (MEI) types
+pub const MODBUS_MEI_READ_DEVICE_ID: u8 = 0x0E;
+
+// Read File Record reference type
+pub const MODBUS_FILE_RECORD_REFERENCE_TYPE: u8 = 6;
+
+// MODBUS Read Device ID codes
+pub const MODBUS_DEVICE_ID_READ_BASIC: u8 = 1;
+pub const MODBUS_DEVICE_ID_READ_REGULAR: u8 = 2;
+pub const MODBUS_DEVICE_ID_READ_EXTENDED: u8 = 3;
+pub const MODBUS_DEVICE_ID_READ_INDIVIDUAL: u8 = 4;
+
+// MODBUS Device ID objects
+pub const MODBUS_DEVICE_ID_VENDOR_NAME: u8 = 0x00;
+pub const MODBUS_DEVICE_ID_PRODUCT_CODE: u8 = 0x01;
+pub const MODBUS_DEVICE_ID_MAJOR_MINOR_REVISION: u8 = 0x02;
+pub const MODBUS_DEVICE_ID_VENDOR_URL: u8 = 0x03;
+pub const MODBUS_DEVICE_ID_PRODUCT_NAME: u8 = 0x04;
+pub const MODBUS_DEVICE_ID_MODEL_NAME: u8 = 0x05;
+pub const MODBUS_DEVICE_ID_USER_APPLICATION_NAME: u8 = 0x06;
 
 // MODBUS Errors
 pub const MODBUS_ERROR_ILLEGAL_FUNCTION: u8 = 1;
 pub const MODBUS_ERROR_ILLEGAL_DATA_ADDRESS: u8 = 2;
 pub const MODBUS_ERROR_ILLEGAL_DATA_VALUE: u8 = 3;
+pub const MODBUS_ERROR_SERVER_DEVICE_FAILURE: u8 = 4;
+pub const MODBUS_ERROR_ACKNOWLEDGE: u8 = 5;
+pub const MODBUS_ERROR_SERVER_DEVICE_BUSY: u8 = 6;
+pub const MODBUS_ERROR_NEGATIVE_ACKNOWLEDGE: u8 = 7;
+pub const MODBUS_ERROR_MEMORY_PARITY_ERROR: u8 = 8;
+pub const MODBUS_ERROR_GATEWAY_PATH_UNAVAILABLE: u8 = 0x0A;
+pub const MODBUS_ERROR_GATEWAY_TARGET_FAILED: u8 = 0x0B;
@@ -8,6 +8,8 @@ pub enum ErrorKind {
     OOBContext,
     FrameBroken,
     FrameCRCError,
+    /// ASCII frame LRC mismatch
+    FrameLRCError,
     IllegalFunction,
     IllegalDataAddress,
     IllegalDataValue,
@@ -36,8 +38,8 @@ impl ErrorKind {
             0x06 => ErrorKind::SlaveDeviceBusy,
             0x07 => ErrorKind::NegativeAcknowledge,
             0x08 => ErrorKind::MemoryParityError,
-            0x09 => ErrorKind::GatewayPathUnavailable,
-            0x10 => ErrorKind::GatewayTargetFailed,
+            0x0A => ErrorKind::GatewayPathUnavailable,
+            0x0B => ErrorKind::GatewayTargetFailed,
             _ => ErrorKind::UnknownError,
         }
     }
@@ -61,6 +63,12 @@ impl ErrorKind {
         )
     }
 
+    /// Is the error a Modbus exception, asking the client to retry the request later
+    /// (Acknowledge, Slave Device Busy)
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ErrorKind::Acknowledge | ErrorKind::SlaveDeviceBusy)
+    }
+
     pub fn to_modbus_error(&self) -> Result<u8, ErrorKind> {
         #[allow(clippy::enum_glob_use)]
         use ErrorKind::*;
@@ -74,8 +82,8 @@ impl ErrorKind {
             SlaveDeviceBusy => Ok(6),
             NegativeAcknowledge => Ok(7),
             MemoryParityError => Ok(8),
-            GatewayPathUnavailable => Ok(9),
-            GatewayTargetFailed => Ok(10),
+            GatewayPathUnavailable => Ok(0x0A),
+            GatewayTargetFailed => Ok(0x0B),
             _ => Err(*self),
         }
     }
@@ -88,6 +96,7 @@ impl core::fmt::Display for ErrorKind {
             ErrorKind::OOBContext => "OUT OF BUFFER IN CONTEXT",
             ErrorKind::FrameBroken => "FRAME BROKEN",
             ErrorKind::FrameCRCError => "FRAME CRC ERROR",
+            ErrorKind::FrameLRCError => "FRAME LRC ERROR",
             ErrorKind::IllegalFunction => "MODBUS ERROR CODE 01 - ILLEGAL FUNCTION",
             ErrorKind::IllegalDataAddress => "MODBUS ERROR CODE 02 - ILLEGAL DATA ADDRESS",
             ErrorKind::IllegalDataValue => "MODBUS ERROR CODE 03 - ILLEGAL DATA VALUE",
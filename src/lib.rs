@@ -7,24 +7,50 @@ pub mod consts;
 pub mod server;
 
 mod vector;
-pub use vector::VectorTrait;
+pub use vector::{ResponseBuf, SliceWriter, VectorTrait};
 
 mod error;
 pub use error::ErrorKind;
 
+use core::time::Duration;
+
+mod accumulator;
+pub use accumulator::{FeedResult, FrameAccumulator};
+
 #[cfg(test)]
 mod tests;
 
 /// Modbus protocol selection for frame processing
 ///
-/// * for **TcpUdp**, Modbus TCP headers are parsed / added to replies
+/// * for **Tcp** and **Udp**, Modbus TCP (MBAP) headers are parsed / added to replies
 /// * for **Rtu**, frame checksums are verified / added to replies
+/// * **RtuOverTcp** is the same as **Rtu**, for raw RTU frames (with CRC, without MBAP headers),
+///   tunneled over TCP streams by some gateways, see [`next_rtu_request_frame`] /
+///   [`next_rtu_response_frame`] to split the stream into frames
+///
+/// UDP datagrams are self-delimiting, so for **Udp** the frame buffer must be the received
+/// datagram only (e.g. `&buf[..len]`), a datagram which length does not match the MBAP header is
+/// considered broken. For **Tcp** the buffer may be longer than the frame.
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ModbusProto {
     Rtu,
     Ascii,
-    TcpUdp,
+    Tcp,
+    Udp,
+    RtuOverTcp,
+}
+
+impl ModbusProto {
+    #[deprecated(note = "use ModbusProto::Tcp or ModbusProto::Udp")]
+    #[allow(non_upper_case_globals)]
+    pub const TcpUdp: ModbusProto = ModbusProto::Tcp;
+
+    /// Is the protocol Tcp or Udp (frames have MBAP headers)
+    #[inline]
+    pub fn has_mbap_header(self) -> bool {
+        matches!(self, ModbusProto::Tcp | ModbusProto::Udp)
+    }
 }
 
 /// Standard Modbus frame buffer
@@ -40,41 +66,73 @@ pub type ModbusFrameBuf = [u8; 256];
 /// frame_buf - frame buffer to write output
 /// frame_pos - position in frame buffer to write
 ///
-/// The frame can be parsed fully or partially (use frame_pos)
+/// The frame must be terminated with CR, LF or NUL within data_len, use
+/// [`parse_ascii_frame_partial`] to parse frames partially
 ///
 /// Returns number of bytes parsed
 ///
 /// Errors:
 ///
 /// * **OOB** input is larger than frame buffer (starting from frame_pos)
-/// * **FrameBroken** unable to decode input hex string
+/// * **FrameBroken** unable to decode input hex string or the terminator is missing
 pub fn parse_ascii_frame(
     data: &[u8],
     data_len: usize,
     frame_buf: &mut [u8],
     frame_pos: u8,
+) -> Result<u8, ErrorKind> {
+    parse_ascii(data, data_len, frame_buf, frame_pos, true)
+}
+
+/// Parse ASCII Modbus frame partially
+///
+/// The same as [`parse_ascii_frame`] but stops at the end of input if there is no terminator, so
+/// the frame can be parsed in chunks (use frame_pos)
+pub fn parse_ascii_frame_partial(
+    data: &[u8],
+    data_len: usize,
+    frame_buf: &mut [u8],
+    frame_pos: u8,
+) -> Result<u8, ErrorKind> {
+    parse_ascii(data, data_len, frame_buf, frame_pos, false)
+}
+
+fn parse_ascii(
+    data: &[u8],
+    data_len: usize,
+    frame_buf: &mut [u8],
+    frame_pos: u8,
+    terminated: bool,
 ) -> Result<u8, ErrorKind> {
     let mut data_pos = usize::from(data[0] == 58);
     let mut cpos = frame_pos;
     while data_pos < data_len {
-        if cpos == 255 {
-            return Err(ErrorKind::OOB);
-        }
         let ch = data[data_pos];
         if ch == 10 || ch == 13 || ch == 0 {
-            break;
+            return Ok(cpos - frame_pos);
         }
-        let c = chr_to_hex(data[data_pos])?;
+        if cpos == 255 {
+            return Err(ErrorKind::OOB);
+        }
+        let c = ascii_hex_to_nibble(ch)?;
         data_pos += 1;
         if data_pos >= data_len {
-            return Err(ErrorKind::OOB);
+            return Err(if terminated {
+                ErrorKind::FrameBroken
+            } else {
+                ErrorKind::OOB
+            });
         }
-        let c2 = chr_to_hex(data[data_pos])?;
+        let c2 = ascii_hex_to_nibble(data[data_pos])?;
         frame_buf[cpos as usize] = c * 0x10 + c2;
         data_pos += 1;
         cpos += 1;
     }
-    Ok(cpos - frame_pos)
+    if terminated {
+        Err(ErrorKind::FrameBroken)
+    } else {
+        Ok(cpos - frame_pos)
+    }
 }
 
 /// Generate ASCII frame
@@ -88,16 +146,82 @@ pub fn generate_ascii_frame<V: VectorTrait<u8>>(
     result.clear();
     result.push(58)?;
     for d in data {
-        result.push(hex_to_chr(d >> 4))?;
-        result.push(hex_to_chr(*d & 0xf))?;
+        result.push(nibble_to_ascii_hex(d >> 4))?;
+        result.push(nibble_to_ascii_hex(*d & 0xf))?;
     }
     result.push(0x0D)?;
     result.push(0x0A)
 }
 
-fn calc_crc16(frame: &[u8], data_length: u8) -> u16 {
+/// Incremental Modbus ASCII frame encoder
+///
+/// Encodes a binary frame (without LRC) byte-by-byte, so the output can be sent (e.g. to a
+/// UART) while encoding, without a full second buffer. The LRC is calculated on the fly and
+/// appended by [`finish`](AsciiEncoder::finish). The output vector is never cleared by the
+/// encoder, so it can be drained between the calls.
+///
+/// ```
+/// use rmodbus::{generate_ascii_frame, lrc, AsciiEncoder};
+///
+/// let frame = [1, 3, 2, 0, 5];
+/// let mut out = Vec::new();
+/// let mut encoder = AsciiEncoder::new(&mut out);
+/// encoder.start().unwrap();
+/// for b in frame {
+///     encoder.push_byte(b).unwrap();
+/// }
+/// encoder.finish().unwrap();
+/// let mut expected = Vec::new();
+/// generate_ascii_frame(&[1, 3, 2, 0, 5, lrc(&frame)], &mut expected).unwrap();
+/// assert_eq!(out, expected);
+/// ```
+#[derive(Debug)]
+pub struct AsciiEncoder<'a, V: VectorTrait<u8>> {
+    out: &'a mut V,
+    lrc: u8,
+}
+
+impl<'a, V: VectorTrait<u8>> AsciiEncoder<'a, V> {
+    pub fn new(out: &'a mut V) -> Self {
+        Self { out, lrc: 0 }
+    }
+    /// The output vector, e.g. to drain the encoded bytes
+    pub fn output(&mut self) -> &mut V {
+        self.out
+    }
+    /// Emit the frame start character and reset the LRC
+    pub fn start(&mut self) -> Result<(), ErrorKind> {
+        self.lrc = 0;
+        self.out.push(b':')
+    }
+    /// Emit a frame byte as a hex pair
+    pub fn push_byte(&mut self, b: u8) -> Result<(), ErrorKind> {
+        self.lrc = self.lrc.wrapping_sub(b);
+        self.out.push(nibble_to_ascii_hex(b >> 4))?;
+        self.out.push(nibble_to_ascii_hex(b & 0xf))
+    }
+    /// Emit the frame LRC and CR/LF
+    pub fn finish(&mut self) -> Result<(), ErrorKind> {
+        self.out.push(nibble_to_ascii_hex(self.lrc >> 4))?;
+        self.out.push(nibble_to_ascii_hex(self.lrc & 0xf))?;
+        self.out.push(0x0D)?;
+        self.out.push(0x0A)
+    }
+}
+
+/// Calculate Modbus RTU CRC16 of the frame
+///
+/// The result should be appended to the frame as little-endian bytes
+///
+/// ```
+/// let request = [1, 3, 0, 0, 0, 1];
+/// let crc = rmodbus::crc16(&request);
+/// assert_eq!(crc, 0x0A84);
+/// assert_eq!(crc.to_le_bytes(), [0x84, 0x0A]);
+/// ```
+pub fn crc16(frame: &[u8]) -> u16 {
     let mut crc: u16 = 0xffff;
-    for i in frame.iter().take(data_length as usize) {
+    for i in frame {
         crc ^= u16::from(*i);
         for _ in (0..8).rev() {
             if (crc & 0x0001) == 0 {
@@ -111,30 +235,329 @@ fn calc_crc16(frame: &[u8], data_length: u8) -> u16 {
     crc
 }
 
-#[allow(clippy::cast_possible_truncation)]
-#[allow(clippy::cast_sign_loss)]
-fn calc_lrc(frame: &[u8], data_length: u8) -> u8 {
-    let mut lrc: i32 = 0;
-    for i in 0..data_length {
-        lrc -= i32::from(frame[i as usize]);
+/// Calculate Modbus ASCII LRC of the (binary) frame
+///
+/// ```
+/// let request = [1, 3, 0, 0, 0, 1];
+/// assert_eq!(rmodbus::lrc(&request), 0xFB);
+/// ```
+pub fn lrc(frame: &[u8]) -> u8 {
+    frame.iter().fold(0, |lrc: u8, i| lrc.wrapping_sub(*i))
+}
+
+/// Split the first TCP (MBAP) frame from a stream buffer, which may contain several pipelined
+/// frames, returning the frame and the rest of the buffer
+///
+/// Returns `None` if the buffer does not contain a complete frame (read more data) or the MBAP
+/// header is invalid (protocol id is not zero or the length is zero)
+///
+/// ```
+/// use rmodbus::next_tcp_frame;
+///
+/// let mut buf: &[u8] = &[
+///     0, 1, 0, 0, 0, 6, 1, 3, 0, 0, 0, 1, // the first frame
+///     0, 2, 0, 0, 0, 6, 1, 3, 0, 1, 0, 1, // the second frame
+/// ];
+/// let mut frames = 0;
+/// while let Some((frame, rest)) = next_tcp_frame(buf) {
+///     assert_eq!(frame.len(), 12);
+///     frames += 1;
+///     buf = rest;
+/// }
+/// assert_eq!(frames, 2);
+/// assert!(buf.is_empty());
+/// ```
+pub fn next_tcp_frame(buf: &[u8]) -> Option<(&[u8], &[u8])> {
+    if buf.len() < 6 || buf[2] != 0 || buf[3] != 0 {
+        return None;
     }
-    lrc as u8
+    let len = usize::from(u16::from_be_bytes([buf[4], buf[5]]));
+    if len == 0 || buf.len() < len + 6 {
+        return None;
+    }
+    Some(buf.split_at(len + 6))
 }
 
-fn chr_to_hex(c: u8) -> Result<u8, ErrorKind> {
+/// Split the first RTU request frame from a stream buffer (e.g. raw RTU frames tunneled over
+/// TCP, see [`ModbusProto::RtuOverTcp`]), returning the frame and the rest of the buffer
+///
+/// RTU frames have no length field, the frame length is guessed from the function code (and
+/// the byte count for functions 15, 16 and 23) with [`guess_request_frame_len`], the frame
+/// boundary is confirmed by the CRC16 only
+///
+/// Returns `Ok(None)` if the buffer does not contain a complete frame (read more data).
+///
+/// Errors:
+///
+/// * **FrameCRCError** the CRC of the guessed frame does not match
+/// * **FrameBroken** the frame length can not be guessed
+///
+/// In both cases the stream is out of sync and the buffer should be dropped.
+///
+/// ```
+/// use rmodbus::next_rtu_request_frame;
+///
+/// let mut buf: &[u8] = &[
+///     1, 3, 0, 0, 0, 1, 0x84, 0x0a, // the first frame
+///     1, 6, 0, 1, 0, 3, 0x98, 0x0b, // the second frame
+/// ];
+/// let mut frames = 0;
+/// while let Some((frame, rest)) = next_rtu_request_frame(buf).unwrap() {
+///     assert_eq!(frame.len(), 8);
+///     frames += 1;
+///     buf = rest;
+/// }
+/// assert_eq!(frames, 2);
+/// assert!(buf.is_empty());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn next_rtu_request_frame(buf: &[u8]) -> Result<Option<(&[u8], &[u8])>, ErrorKind> {
+    let Some(func) = buf.get(1) else {
+        return Ok(None);
+    };
+    let header_len = match func {
+        15 | 16 => 7,
+        23 => 11,
+        43 => 3,
+        _ => 2,
+    };
+    if buf.len() < header_len {
+        return Ok(None);
+    }
+    let mut header = [0u8; 11];
+    header[..header_len].copy_from_slice(&buf[..header_len]);
+    let len = guess_request_frame_len(&header, ModbusProto::RtuOverTcp)?;
+    split_rtu_frame(buf, len.into())
+}
+
+/// Split the first RTU response frame from a stream buffer, the same as
+/// [`next_rtu_request_frame`], the frame length is guessed with [`guess_response_frame_len`]
+#[allow(clippy::type_complexity)]
+pub fn next_rtu_response_frame(buf: &[u8]) -> Result<Option<(&[u8], &[u8])>, ErrorKind> {
+    if buf.len() < 3 {
+        return Ok(None);
+    }
+    let len = guess_response_frame_len(&buf[..3], ModbusProto::RtuOverTcp)?;
+    split_rtu_frame(buf, len.into())
+}
+
+#[allow(clippy::type_complexity)]
+fn split_rtu_frame(buf: &[u8], len: usize) -> Result<Option<(&[u8], &[u8])>, ErrorKind> {
+    if len < 4 {
+        return Err(ErrorKind::FrameBroken);
+    }
+    if buf.len() < len {
+        return Ok(None);
+    }
+    let (frame, rest) = buf.split_at(len);
+    let (data, crc) = frame.split_at(len - 2);
+    if crc16(data) == u16::from_le_bytes([crc[0], crc[1]]) {
+        Ok(Some((frame, rest)))
+    } else {
+        Err(ErrorKind::FrameCRCError)
+    }
+}
+
+/// Verify a complete frame without parsing it: the trailing CRC16 for RTU, the trailing LRC for
+/// ASCII (the frame must be decoded to binary) and MBAP header fields (protocol id, length) for
+/// TCP/UDP
+///
+/// Errors:
+///
+/// * **FrameBroken** the frame is too short or the MBAP header is invalid
+/// * **FrameCRCError** CRC16 mismatch (RTU)
+/// * **FrameLRCError** LRC mismatch (ASCII)
+///
+/// ```
+/// use rmodbus::{verify_frame_checksum, ErrorKind, ModbusProto};
+///
+/// let frame = [1, 3, 0, 0, 0, 1, 0x84, 0x0a];
+/// assert!(verify_frame_checksum(&frame, ModbusProto::Rtu).is_ok());
+/// assert_eq!(
+///     verify_frame_checksum(&frame[..7], ModbusProto::Rtu),
+///     Err(ErrorKind::FrameCRCError)
+/// );
+/// ```
+pub fn verify_frame_checksum(buf: &[u8], proto: ModbusProto) -> Result<(), ErrorKind> {
+    match proto {
+        ModbusProto::Tcp | ModbusProto::Udp => {
+            if buf.len() < 8
+                || u16::from_be_bytes([buf[2], buf[3]]) != 0
+                || usize::from(u16::from_be_bytes([buf[4], buf[5]])) + 6 != buf.len()
+            {
+                return Err(ErrorKind::FrameBroken);
+            }
+        }
+        ModbusProto::Rtu | ModbusProto::RtuOverTcp => {
+            if buf.len() < 4 {
+                return Err(ErrorKind::FrameBroken);
+            }
+            let (data, crc) = buf.split_at(buf.len() - 2);
+            if crc16(data) != u16::from_le_bytes([crc[0], crc[1]]) {
+                return Err(ErrorKind::FrameCRCError);
+            }
+        }
+        ModbusProto::Ascii => {
+            if buf.len() < 3 {
+                return Err(ErrorKind::FrameBroken);
+            }
+            let (data, lrc_byte) = buf.split_at(buf.len() - 1);
+            if lrc(data) != lrc_byte[0] {
+                return Err(ErrorKind::FrameLRCError);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Translate a complete frame between protocols (e.g. for a TCP-RTU gateway), keeping the unit id
+/// and PDU
+///
+/// The source frame is verified with [`verify_frame_checksum`]. The MBAP header is stripped /
+/// synthesized, the CRC16 (RTU) or LRC (ASCII, binary frames) is removed / appended. The
+/// transaction id is lost when translating from TCP/UDP, `tr_id` is used if the target
+/// protocol is TCP/UDP (ignored otherwise).
+///
+/// Note: the output vector is cleared
+///
+/// ```
+/// use rmodbus::{translate_frame, ModbusProto};
+///
+/// let tcp = [0, 7, 0, 0, 0, 6, 1, 3, 0, 0, 0, 1];
+/// let mut rtu = Vec::new();
+/// translate_frame(&tcp, ModbusProto::Tcp, ModbusProto::Rtu, 0, &mut rtu).unwrap();
+/// assert_eq!(rtu, [1, 3, 0, 0, 0, 1, 0x84, 0x0a]);
+/// let mut tcp_back = Vec::new();
+/// translate_frame(&rtu, ModbusProto::Rtu, ModbusProto::Tcp, 7, &mut tcp_back).unwrap();
+/// assert_eq!(tcp_back, tcp);
+/// ```
+pub fn translate_frame<V: VectorTrait<u8>>(
+    src: &[u8],
+    from: ModbusProto,
+    to: ModbusProto,
+    tr_id: u16,
+    out: &mut V,
+) -> Result<(), ErrorKind> {
+    verify_frame_checksum(src, from)?;
+    let adu = match from {
+        ModbusProto::Tcp | ModbusProto::Udp => &src[6..],
+        ModbusProto::Rtu | ModbusProto::RtuOverTcp => &src[..src.len() - 2],
+        ModbusProto::Ascii => &src[..src.len() - 1],
+    };
+    if adu.len() < 2 {
+        return Err(ErrorKind::FrameBroken);
+    }
+    out.clear();
+    match to {
+        ModbusProto::Tcp | ModbusProto::Udp => {
+            out.extend(&tr_id.to_be_bytes())?;
+            out.extend(&[0, 0])?;
+            out.extend(&u16::try_from(adu.len())?.to_be_bytes())?;
+            out.extend(adu)
+        }
+        ModbusProto::Rtu | ModbusProto::RtuOverTcp => {
+            out.extend(adu)?;
+            out.extend(&crc16(adu).to_le_bytes())
+        }
+        ModbusProto::Ascii => {
+            out.extend(adu)?;
+            out.push(lrc(adu))
+        }
+    }
+}
+
+#[inline]
+/// RTU character time (11 bits: start, 8 data, parity/stop, stop) for the baud rate
+///
+/// # Panics
+///
+/// The function panics if baud is zero
+pub fn rtu_char_time(baud: u32) -> Duration {
+    Duration::from_nanos(11_000_000_000 / u64::from(baud))
+}
+
+/// RTU inter-character timeout (T1.5): 1.5 character times, fixed 750µs above 19200 baud
+///
+/// # Panics
+///
+/// The function panics if baud is zero
+pub fn rtu_char_timeout(baud: u32) -> Duration {
+    if baud > 19200 {
+        Duration::from_micros(750)
+    } else {
+        Duration::from_nanos(16_500_000_000 / u64::from(baud))
+    }
+}
+
+/// RTU inter-frame silent interval (T3.5): 3.5 character times, fixed 1750µs above 19200 baud
+///
+/// ```
+/// use core::time::Duration;
+///
+/// assert_eq!(rmodbus::rtu_frame_timeout(115_200), Duration::from_micros(1750));
+/// ```
+///
+/// # Panics
+///
+/// The function panics if baud is zero
+pub fn rtu_frame_timeout(baud: u32) -> Duration {
+    if baud > 19200 {
+        Duration::from_micros(1750)
+    } else {
+        Duration::from_nanos(38_500_000_000 / u64::from(baud))
+    }
+}
+
+fn calc_crc16(frame: &[u8], data_length: u8) -> u16 {
+    crc16(frame.get(..data_length as usize).unwrap_or(frame))
+}
+
+#[inline]
+fn calc_lrc(frame: &[u8], data_length: u8) -> u8 {
+    lrc(&frame[..data_length as usize])
+}
+
+/// Decode a Modbus ASCII hex character into a nibble
+///
+/// Modbus ASCII frames use uppercase hex digits (`0-9`, `A-F`), lowercase `a-f` are accepted as
+/// well, as sent by some non-compliant masters
+///
+/// ```
+/// use rmodbus::{ascii_hex_to_nibble, ErrorKind};
+///
+/// assert_eq!(ascii_hex_to_nibble(b'0'), Ok(0));
+/// assert_eq!(ascii_hex_to_nibble(b'9'), Ok(9));
+/// assert_eq!(ascii_hex_to_nibble(b'A'), Ok(0x0a));
+/// assert_eq!(ascii_hex_to_nibble(b'F'), Ok(0x0f));
+/// assert_eq!(ascii_hex_to_nibble(b'f'), Ok(0x0f));
+/// assert_eq!(ascii_hex_to_nibble(b'G'), Err(ErrorKind::FrameBroken));
+/// ```
+pub fn ascii_hex_to_nibble(c: u8) -> Result<u8, ErrorKind> {
     match c {
-        48..=57 => Ok(c - 48),
-        65..=70 => Ok(c - 55),
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
         _ => Err(ErrorKind::FrameBroken),
     }
 }
 
+/// Encode a nibble (the lower 4 bits of n) as a Modbus ASCII (uppercase) hex character
+///
+/// ```
+/// use rmodbus::nibble_to_ascii_hex;
+///
+/// assert_eq!(nibble_to_ascii_hex(0), b'0');
+/// assert_eq!(nibble_to_ascii_hex(9), b'9');
+/// assert_eq!(nibble_to_ascii_hex(0x0a), b'A');
+/// assert_eq!(nibble_to_ascii_hex(0x0f), b'F');
+/// ```
 #[inline]
-fn hex_to_chr(h: u8) -> u8 {
-    if h < 10 {
-        h + 48
+pub fn nibble_to_ascii_hex(n: u8) -> u8 {
+    let n = n & 0x0f;
+    if n < 10 {
+        n + b'0'
     } else {
-        h + 55
+        n - 10 + b'A'
     }
 }
 
@@ -145,7 +568,7 @@ fn hex_to_chr(h: u8) -> u8 {
 ///
 /// How to use: read at least first 6 bytes (3 for RTU, 7 for ASCII) into buffer and call the
 /// function to guess the total frame length. The remaining amount of bytes to read will be
-/// function result - 7. 8 bytes is also fine, as that's the minimal correct frame length.
+/// function result - bytes read, see also [`response_bytes_remaining`].
 ///
 /// * the function may return wrong result for broken frames
 ///
@@ -157,11 +580,11 @@ fn hex_to_chr(h: u8) -> u8 {
 pub fn guess_response_frame_len(buf: &[u8], proto: ModbusProto) -> Result<u8, ErrorKind> {
     let mut b: ModbusFrameBuf = [0; 256];
     let (f, multiplier, extra) = match proto {
-        ModbusProto::TcpUdp => {
+        ModbusProto::Tcp | ModbusProto::Udp => {
             let proto = u16::from_be_bytes([buf[2], buf[3]]);
             if proto == 0 {
-                let len = u16::from_be_bytes([buf[4], buf[5]]) + 6;
-                if len > u16::from(u8::MAX) {
+                let len = usize::from(u16::from_be_bytes([buf[4], buf[5]])) + 6;
+                if len > usize::from(u8::MAX) {
                     return Err(ErrorKind::FrameBroken);
                 }
                 #[allow(clippy::cast_possible_truncation)]
@@ -169,17 +592,19 @@ pub fn guess_response_frame_len(buf: &[u8], proto: ModbusProto) -> Result<u8, Er
             }
             return Err(ErrorKind::FrameBroken);
         }
-        ModbusProto::Rtu => (buf, 1, 2), // two bytes CRC16
+        ModbusProto::Rtu | ModbusProto::RtuOverTcp => (buf, 1, 2), // two bytes CRC16
         ModbusProto::Ascii => {
-            parse_ascii_frame(buf, buf.len(), &mut b, 0)?;
+            parse_ascii_frame_partial(buf, buf.len(), &mut b, 0)?;
             (&b[..], 2, 5) // : + two chars LRC + \r\n
         }
     };
     let func = f[1];
     let len: usize = if func < 0x80 {
         match func {
-            1..=4 => (f[2] as usize + 3) * multiplier + extra,
-            5 | 6 | 15 | 16 => 6 * multiplier + extra,
+            1..=4 | 17 | 20 | 23 => (f[2] as usize + 3) * multiplier + extra,
+            5 | 6 | 8 | 11 | 15 | 16 => 6 * multiplier + extra,
+            7 => 3 * multiplier + extra,
+            22 => 8 * multiplier + extra,
             _ => {
                 return Err(ErrorKind::FrameBroken);
             }
@@ -195,6 +620,39 @@ pub fn guess_response_frame_len(buf: &[u8], proto: ModbusProto) -> Result<u8, Er
     }
 }
 
+/// How many more bytes of a response frame must be read, having already_read bytes in the buffer
+///
+/// Returns 0 if the frame is complete (or already_read exceeds the frame length)
+///
+/// Errors:
+///
+/// * **FrameBroken** the header is not complete yet (less than 6 bytes read for TCP/UDP, 3 for
+///   RTU, 7 for ASCII) or the frame is broken
+///
+/// ```
+/// use rmodbus::{response_bytes_remaining, ModbusProto};
+///
+/// let buf = [1, 3, 2, 0, 1, 0x79, 0x84];
+/// assert_eq!(response_bytes_remaining(&buf, 3, ModbusProto::Rtu).unwrap(), 4);
+/// assert_eq!(response_bytes_remaining(&buf, 7, ModbusProto::Rtu).unwrap(), 0);
+/// ```
+pub fn response_bytes_remaining(
+    buf: &[u8],
+    already_read: usize,
+    proto: ModbusProto,
+) -> Result<usize, ErrorKind> {
+    let header_len = match proto {
+        ModbusProto::Tcp | ModbusProto::Udp => 6,
+        ModbusProto::Rtu | ModbusProto::RtuOverTcp => 3,
+        ModbusProto::Ascii => 7,
+    };
+    if already_read < header_len || buf.len() < header_len {
+        return Err(ErrorKind::FrameBroken);
+    }
+    let len = guess_response_frame_len(&buf[..header_len], proto)?;
+    Ok(usize::from(len).saturating_sub(already_read))
+}
+
 /// Guess request frame length
 ///
 /// Frames are often read byte-by-byte. The function allows to guess total frame length, having
@@ -208,22 +666,25 @@ pub fn guess_response_frame_len(buf: &[u8], proto: ModbusProto) -> Result<u8, Er
 ///
 /// * the function may return ErrorKind::FrameBroken for broken ASCII frames
 ///
+/// * for function 0x17 at least 11 bytes (24 for ASCII) must be read to get the byte count
+///
 /// # Panics
 ///
-/// The function panics if the buffer length is less than 7 (for ASCII - 16)
+/// The function panics if the buffer length is less than 7 (for ASCII - 16), for function 0x17
+/// if it is less than 11 (for ASCII - 24)
 pub fn guess_request_frame_len(frame: &[u8], proto: ModbusProto) -> Result<u8, ErrorKind> {
     let mut buf: ModbusFrameBuf = [0; 256];
     let (f, extra, multiplier) = match proto {
-        ModbusProto::Rtu => (frame, 2, 1),
+        ModbusProto::Rtu | ModbusProto::RtuOverTcp => (frame, 2, 1),
         ModbusProto::Ascii => {
-            parse_ascii_frame(frame, frame.len(), &mut buf, 0)?;
+            parse_ascii_frame_partial(frame, frame.len(), &mut buf, 0)?;
             (&buf[..], 5, 2)
         }
-        ModbusProto::TcpUdp => {
+        ModbusProto::Tcp | ModbusProto::Udp => {
             let proto = u16::from_be_bytes([frame[2], frame[3]]);
             if proto == 0 {
-                let len = u16::from_be_bytes([frame[4], frame[5]]) + 6;
-                if len > u16::from(u8::MAX) {
+                let len = usize::from(u16::from_be_bytes([frame[4], frame[5]])) + 6;
+                if len > usize::from(u8::MAX) {
                     return Err(ErrorKind::FrameBroken);
                 }
                 #[allow(clippy::cast_possible_truncation)]
@@ -234,6 +695,10 @@ pub fn guess_request_frame_len(frame: &[u8], proto: ModbusProto) -> Result<u8, E
     };
     let len: usize = match f[1] {
         15 | 16 => (f[6] as usize + 7) * multiplier + extra,
+        7 | 11 | 17 => 2 * multiplier + extra,
+        22 => 8 * multiplier + extra,
+        23 => (f[10] as usize + 11) * multiplier + extra,
+        43 if f[2] == 14 => 5 * multiplier + extra,
         _ => 6 * multiplier + extra,
     };
     if len > u8::MAX as usize {
@@ -0,0 +1,77 @@
+use core::future::Future;
+use core::pin::Pin;
+
+use crate::ErrorKind;
+
+/// A boxed future, returned by [`AsyncModbusContext`] methods
+pub type ContextFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, ErrorKind>> + Send + 'a>>;
+
+/// Asynchronous context for registers, which live in an external storage (e.g. a database)
+///
+/// Used by [`ModbusFrame::process_read_async`](super::ModbusFrame::process_read_async) /
+/// [`process_write_async`](super::ModbusFrame::process_write_async), which support functions
+/// 1-6, 15 and 16, the framing stays synchronous. The methods have the same semantics as the
+/// corresponding [`ModbusContext`](super::context::ModbusContext) ones, e.g. `OOBContext` is
+/// returned for registers out of the storage and is reported to the client as Illegal Data
+/// Address.
+///
+/// Unlike [`ModbusContext`](super::context::ModbusContext), the trait covers only the range
+/// access, required by the supported functions: there are no single-register, typed (u32, f32
+/// etc.), input / discrete setters or other helper methods.
+///
+/// As async trait methods are not available, the methods return boxed futures:
+///
+/// ```rust
+/// use rmodbus::{
+///     server::async_context::{AsyncModbusContext, ContextFuture},
+///     ErrorKind,
+/// };
+///
+/// struct Db;
+///
+/// impl AsyncModbusContext for Db {
+///     fn get_coils_into<'a>(&'a self, reg: u16, out: &'a mut [bool]) -> ContextFuture<'a, ()> {
+///         Box::pin(async move { Err(ErrorKind::OOBContext) })
+///     }
+///     fn get_discretes_into<'a>(&'a self, reg: u16, out: &'a mut [bool]) -> ContextFuture<'a, ()> {
+///         Box::pin(async move { Err(ErrorKind::OOBContext) })
+///     }
+///     fn get_inputs_into<'a>(&'a self, reg: u16, out: &'a mut [u16]) -> ContextFuture<'a, ()> {
+///         Box::pin(async move { Err(ErrorKind::OOBContext) })
+///     }
+///     fn get_holdings_into<'a>(&'a self, reg: u16, out: &'a mut [u16]) -> ContextFuture<'a, ()> {
+///         Box::pin(async move {
+///             // e.g. db.fetch(reg, out.len()).await
+///             for (i, v) in out.iter_mut().enumerate() {
+///                 *v = reg + i as u16;
+///             }
+///             Ok(())
+///         })
+///     }
+///     fn set_coils_bulk<'a>(&'a mut self, reg: u16, values: &'a [bool]) -> ContextFuture<'a, ()> {
+///         Box::pin(async move { Err(ErrorKind::OOBContext) })
+///     }
+///     fn set_holdings_bulk<'a>(&'a mut self, reg: u16, values: &'a [u16]) -> ContextFuture<'a, ()> {
+///         Box::pin(async move { Err(ErrorKind::OOBContext) })
+///     }
+/// }
+/// ```
+pub trait AsyncModbusContext {
+    /// Get out.len() coils starting from reg
+    fn get_coils_into<'a>(&'a self, reg: u16, out: &'a mut [bool]) -> ContextFuture<'a, ()>;
+
+    /// Get out.len() discretes starting from reg
+    fn get_discretes_into<'a>(&'a self, reg: u16, out: &'a mut [bool]) -> ContextFuture<'a, ()>;
+
+    /// Get out.len() inputs starting from reg
+    fn get_inputs_into<'a>(&'a self, reg: u16, out: &'a mut [u16]) -> ContextFuture<'a, ()>;
+
+    /// Get out.len() holdings starting from reg
+    fn get_holdings_into<'a>(&'a self, reg: u16, out: &'a mut [u16]) -> ContextFuture<'a, ()>;
+
+    /// Bulk set coils
+    fn set_coils_bulk<'a>(&'a mut self, reg: u16, values: &'a [bool]) -> ContextFuture<'a, ()>;
+
+    /// Bulk set holdings
+    fn set_holdings_bulk<'a>(&'a mut self, reg: u16, values: &'a [u16]) -> ContextFuture<'a, ()>;
+}
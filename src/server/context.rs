@@ -2,6 +2,133 @@ use crate::{ErrorKind, VectorTrait};
 
 use super::representable::RegisterRepresentable;
 
+/// Order of 16-bit words (registers) for values which take more than one register
+///
+/// The byte order inside each word is always big-endian
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WordOrder {
+    /// the most significant word goes first (standard Modbus)
+    BigEndian,
+    /// the least significant word goes first
+    LittleEndian,
+}
+
+impl WordOrder {
+    /// Converts a big-endian word-ordered value to the order and vice-versa
+    #[inline]
+    fn swap_u32(self, value: u32) -> u32 {
+        match self {
+            WordOrder::BigEndian => value,
+            WordOrder::LittleEndian => value.rotate_left(16),
+        }
+    }
+
+    /// Converts a big-endian word-ordered value to the order and vice-versa
+    #[inline]
+    fn swap_u64(self, value: u64) -> u64 {
+        match self {
+            WordOrder::BigEndian => value,
+            WordOrder::LittleEndian => {
+                (value << 48)
+                    | ((value & 0xFFFF_0000) << 16)
+                    | ((value >> 16) & 0xFFFF_0000)
+                    | (value >> 48)
+            }
+        }
+    }
+
+    /// Split u32 into 2 registers (from lower to higher addresses)
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn u32_to_registers(self, value: u32) -> [u16; 2] {
+        let value = self.swap_u32(value);
+        [(value >> 16) as u16, value as u16]
+    }
+
+    /// Join u32 from 2 registers (from lower to higher addresses)
+    pub fn u32_from_registers(self, regs: &[u16; 2]) -> u32 {
+        self.swap_u32(u32::from(regs[0]) << 16 | u32::from(regs[1]))
+    }
+
+    /// Split u64 into 4 registers (from lower to higher addresses)
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn u64_to_registers(self, value: u64) -> [u16; 4] {
+        let value = self.swap_u64(value);
+        [
+            (value >> 48) as u16,
+            (value >> 32) as u16,
+            (value >> 16) as u16,
+            value as u16,
+        ]
+    }
+
+    /// Join u64 from 4 registers (from lower to higher addresses)
+    pub fn u64_from_registers(self, regs: &[u16; 4]) -> u64 {
+        self.swap_u64(
+            u64::from(regs[0]) << 48
+                | u64::from(regs[1]) << 32
+                | u64::from(regs[2]) << 16
+                | u64::from(regs[3]),
+        )
+    }
+}
+
+/// Byte order of IEEE 754 f32 values in two registers, `A` is the most significant byte
+///
+/// Unlike [`WordOrder`], covers byte swaps inside the words as well
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FloatByteOrder {
+    /// big-endian (standard Modbus)
+    #[default]
+    ABCD,
+    /// big-endian bytes, the least significant word goes first
+    CDAB,
+    /// the most significant word goes first, bytes inside the words are swapped
+    BADC,
+    /// little-endian
+    DCBA,
+}
+
+impl FloatByteOrder {
+    /// Converts a big-endian value to the order and vice-versa
+    #[inline]
+    fn swap_u32(self, value: u32) -> u32 {
+        match self {
+            FloatByteOrder::ABCD => value,
+            FloatByteOrder::CDAB => value.rotate_left(16),
+            FloatByteOrder::BADC => ((value & 0x00FF_00FF) << 8) | ((value >> 8) & 0x00FF_00FF),
+            FloatByteOrder::DCBA => value.swap_bytes(),
+        }
+    }
+}
+
+/// The last register of the range (None for empty ranges)
+#[allow(clippy::cast_possible_truncation)]
+fn last_reg(reg: u16, count: usize) -> Result<Option<u16>, ErrorKind> {
+    if count == 0 {
+        Ok(None)
+    } else if reg as usize + count > u16::MAX as usize + 1 {
+        Err(ErrorKind::OOBContext)
+    } else {
+        Ok(Some(reg + (count - 1) as u16))
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn get_into<T, F>(reg: u16, out: &mut [T], get: F) -> Result<(), ErrorKind>
+where
+    F: Fn(u16) -> Result<T, ErrorKind>,
+{
+    if reg as usize + out.len() > u16::MAX as usize + 1 {
+        return Err(ErrorKind::OOBContext);
+    }
+    for (i, v) in out.iter_mut().enumerate() {
+        *v = get(reg + i as u16)?;
+    }
+    Ok(())
+}
+
 #[allow(clippy::module_name_repetitions)]
 pub trait ModbusContext {
     /// Get inputs as Vec of u8
@@ -12,7 +139,9 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Get holdings as Vec of u8
     ///
@@ -22,7 +151,45 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
+
+    /// Get inputs as Vec of u8 (raw bytes, 2 big-endian bytes per register)
+    ///
+    /// The same as [`get_inputs_as_u8`](ModbusContext::get_inputs_as_u8), unlike coils and
+    /// discretes, registers are never packed
+    ///
+    /// Note: Vec is always appended
+    fn get_inputs_as_u8_bytes<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
+        self.get_inputs_as_u8(reg, count, result)
+    }
+
+    /// Get holdings as Vec of u8 (raw bytes, 2 big-endian bytes per register)
+    ///
+    /// The same as [`get_holdings_as_u8`](ModbusContext::get_holdings_as_u8), unlike coils and
+    /// discretes, registers are never packed
+    ///
+    /// Note: Vec is always appended
+    fn get_holdings_as_u8_bytes<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
+        self.get_holdings_as_u8(reg, count, result)
+    }
 
     /// Set inputs from Vec of u8
     fn set_inputs_from_u8(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind>;
@@ -32,13 +199,18 @@ pub trait ModbusContext {
 
     /// Get coils as Vec of u8 (packed as BITS, 1 byte = 8 coils)
     ///
+    /// `count` coils are packed into `ceil(count / 8)` bytes, LSB first, unused bits of the last
+    /// byte are zero. If `reg + count` is above the context size, nothing is appended
+    ///
     /// Note: Vec is always appended
     fn get_coils_as_u8<V: VectorTrait<u8>>(
         &self,
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Get coils as Vec of u8 (packed as BYTES)
     ///
@@ -48,17 +220,24 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Get discretes as Vec of u8 (packed as BITS, 1 byte = 8 discretes)
     ///
+    /// `count` discretes are packed into `ceil(count / 8)` bytes, LSB first, unused bits of the last
+    /// byte are zero. If `reg + count` is above the context size, nothing is appended
+    ///
     /// Note: Vec is always appended
     fn get_discretes_as_u8<V: VectorTrait<u8>>(
         &self,
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Get discretes as Vec of u8 (packed as BYTES)
     ///
@@ -68,18 +247,25 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Set coils from Vec of u8 (packed as BITS, 1 byte = 8 coils)
     ///
     /// As coils are packed in u8, parameter *count* specifies how many coils are actually needed
-    /// to set, extra bits are ignored
+    /// to set, extra bits are ignored (high bits of the last byte, if count is not a multiple of
+    /// 8, and extra bytes)
+    ///
+    /// Errors (nothing is set):
+    ///
+    /// * **OOBContext** `reg + count` is above the context size
+    /// * **OOB** values contain less than count bits
     fn set_coils_from_u8(&mut self, reg: u16, count: u16, values: &[u8]) -> Result<(), ErrorKind>;
 
     /// Set discretes from Vec of u8 (packed as BITS, 1 byte = 8 discretes)
     ///
-    /// As discretes are packed in u8, parameter *count* specifies how many coils are actually
-    /// needed to set, extra bits are ignored
+    /// The same as [`set_coils_from_u8`](ModbusContext::set_coils_from_u8)
     fn set_discretes_from_u8(
         &mut self,
         reg: u16,
@@ -101,7 +287,9 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Bulk get discretes
     ///
@@ -111,7 +299,9 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Bulk get inputs
     ///
@@ -121,7 +311,9 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
 
     /// Bulk get holdings
     ///
@@ -131,7 +323,37 @@ pub trait ModbusContext {
         reg: u16,
         count: u16,
         result: &mut V,
-    ) -> Result<(), ErrorKind>;
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized;
+
+    /// Get coils into a slice (out.len() coils starting from reg)
+    ///
+    /// Note: the slice may be partially filled on errors
+    fn get_coils_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        get_into(reg, out, |r| self.get_coil(r))
+    }
+
+    /// Get discretes into a slice (out.len() discretes starting from reg)
+    ///
+    /// Note: the slice may be partially filled on errors
+    fn get_discretes_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        get_into(reg, out, |r| self.get_discrete(r))
+    }
+
+    /// Get inputs into a slice (out.len() inputs starting from reg)
+    ///
+    /// Note: the slice may be partially filled on errors
+    fn get_inputs_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        get_into(reg, out, |r| self.get_input(r))
+    }
+
+    /// Get holdings into a slice (out.len() holdings starting from reg)
+    ///
+    /// Note: the slice may be partially filled on errors
+    fn get_holdings_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        get_into(reg, out, |r| self.get_holding(r))
+    }
 
     /// Bulk set coils
     fn set_coils_bulk(&mut self, reg: u16, values: &[bool]) -> Result<(), ErrorKind>;
@@ -169,6 +391,56 @@ pub trait ModbusContext {
     /// Set a single holding
     fn set_holding(&mut self, reg: u16, value: u16) -> Result<(), ErrorKind>;
 
+    /// Set a holding to new value only if its current value is expected (e.g. for command
+    /// registers), under the same lock as the check
+    ///
+    /// Returns true if the value has been set
+    fn compare_and_set_holding(
+        &mut self,
+        reg: u16,
+        expected: u16,
+        new: u16,
+    ) -> Result<bool, ErrorKind> {
+        if self.get_holding(reg)? == expected {
+            self.set_holding(reg, new)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Get a BCD-encoded holding (e.g. 0x1234 is 1234)
+    ///
+    /// Errors:
+    ///
+    /// * **IllegalDataValue** the holding contains a nibble above 9
+    fn get_holding_bcd(&self, reg: u16) -> Result<u16, ErrorKind> {
+        let raw = self.get_holding(reg)?;
+        let mut value = 0;
+        for shift in [12, 8, 4, 0] {
+            let digit = (raw >> shift) & 0xf;
+            if digit > 9 {
+                return Err(ErrorKind::IllegalDataValue);
+            }
+            value = value * 10 + digit;
+        }
+        Ok(value)
+    }
+
+    /// Set a holding to BCD-encoded value (e.g. 1234 is set as 0x1234)
+    ///
+    /// Errors:
+    ///
+    /// * **IllegalDataValue** the value is above 9999
+    fn set_holding_bcd(&mut self, reg: u16, value: u16) -> Result<(), ErrorKind> {
+        if value > 9999 {
+            return Err(ErrorKind::IllegalDataValue);
+        }
+        let raw =
+            (value / 1000) << 12 | (value / 100 % 10) << 8 | (value / 10 % 10) << 4 | (value % 10);
+        self.set_holding(reg, raw)
+    }
+
     /// Get two inputs as u32
     ///
     /// Returns 32-bit value (big-endian)
@@ -221,19 +493,335 @@ pub trait ModbusContext {
     /// Set IEEE 754 f32 to two holding registers
     fn set_holdings_from_f32(&mut self, reg: u16, value: f32) -> Result<(), ErrorKind>;
 
+    /// Get four input registers as IEEE754 64-bit float
+    fn get_inputs_as_f64(&self, reg: u16) -> Result<f64, ErrorKind> {
+        Ok(f64::from_bits(self.get_inputs_as_u64(reg)?))
+    }
+
+    /// Get four holding registers as IEEE754 64-bit float
+    fn get_holdings_as_f64(&self, reg: u16) -> Result<f64, ErrorKind> {
+        Ok(f64::from_bits(self.get_holdings_as_u64(reg)?))
+    }
+
+    /// Set IEEE 754 f64 to four input registers
+    fn set_inputs_from_f64(&mut self, reg: u16, value: f64) -> Result<(), ErrorKind> {
+        self.set_inputs_from_u64(reg, value.to_bits())
+    }
+
+    /// Set IEEE 754 f64 to four holding registers
+    fn set_holdings_from_f64(&mut self, reg: u16, value: f64) -> Result<(), ErrorKind> {
+        self.set_holdings_from_u64(reg, value.to_bits())
+    }
+
+    /// Set u32 values to holdings, 2 registers per value
+    ///
+    /// The range is checked before writing
+    #[allow(clippy::cast_possible_truncation)]
+    fn set_holdings_from_u32_bulk(&mut self, reg: u16, values: &[u32]) -> Result<(), ErrorKind> {
+        if let Some(last) = last_reg(reg, values.len() * 2)? {
+            self.get_holding(last)?;
+        }
+        for (i, value) in values.iter().enumerate() {
+            self.set_holdings_from_u32(reg + i as u16 * 2, *value)?;
+        }
+        Ok(())
+    }
+
+    /// Set IEEE 754 f32 values to holdings, 2 registers per value
+    ///
+    /// The range is checked before writing
+    #[allow(clippy::cast_possible_truncation)]
+    fn set_holdings_from_f32_bulk(&mut self, reg: u16, values: &[f32]) -> Result<(), ErrorKind> {
+        if let Some(last) = last_reg(reg, values.len() * 2)? {
+            self.get_holding(last)?;
+        }
+        for (i, value) in values.iter().enumerate() {
+            self.set_holdings_from_f32(reg + i as u16 * 2, *value)?;
+        }
+        Ok(())
+    }
+
+    /// Set holdings sequentially from an iterator, starting from reg
+    ///
+    /// Unlike bulk methods, the range can not be checked before writing: if the iterator yields
+    /// more values than the context can hold, OOBContext is returned and the values before the
+    /// failed one stay written
+    fn set_holdings_from_iter<I: Iterator<Item = u16>>(
+        &mut self,
+        reg: u16,
+        iter: I,
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
+        let mut next = Some(reg);
+        for value in iter {
+            let r = next.ok_or(ErrorKind::OOBContext)?;
+            self.set_holding(r, value)?;
+            next = r.checked_add(1);
+        }
+        Ok(())
+    }
+
+    /// Get count u32 values from holdings, 2 registers per value
+    ///
+    /// Note: Vec is always appended
+    fn get_holdings_as_u32_bulk<V: VectorTrait<u32>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
+        if let Some(last) = last_reg(reg, usize::from(count) * 2)? {
+            self.get_holding(last)?;
+        }
+        for i in 0..count {
+            result.push(self.get_holdings_as_u32(reg + i * 2)?)?;
+        }
+        Ok(())
+    }
+
+    /// Get count IEEE 754 f32 values from holdings, 2 registers per value
+    ///
+    /// Note: Vec is always appended
+    fn get_holdings_as_f32_bulk<V: VectorTrait<f32>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
+        if let Some(last) = last_reg(reg, usize::from(count) * 2)? {
+            self.get_holding(last)?;
+        }
+        for i in 0..count {
+            result.push(self.get_holdings_as_f32(reg + i * 2)?)?;
+        }
+        Ok(())
+    }
+
+    /// Get count inputs as signed i16 values (two's complement)
+    ///
+    /// Note: Vec is always appended
+    #[allow(clippy::cast_possible_wrap)]
+    fn get_inputs_as_i16_bulk<V: VectorTrait<i16>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
+        if let Some(last) = last_reg(reg, usize::from(count))? {
+            self.get_input(last)?;
+        }
+        for i in 0..count {
+            result.push(self.get_input(reg + i)? as i16)?;
+        }
+        Ok(())
+    }
+
+    /// Get count holdings as signed i16 values (two's complement)
+    ///
+    /// Note: Vec is always appended
+    #[allow(clippy::cast_possible_wrap)]
+    fn get_holdings_as_i16_bulk<V: VectorTrait<i16>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
+        if let Some(last) = last_reg(reg, usize::from(count))? {
+            self.get_holding(last)?;
+        }
+        for i in 0..count {
+            result.push(self.get_holding(reg + i)? as i16)?;
+        }
+        Ok(())
+    }
+
+    /// Get two inputs as u32 with the specified word order
+    fn get_inputs_as_u32_with_order(&self, reg: u16, order: WordOrder) -> Result<u32, ErrorKind> {
+        Ok(order.swap_u32(self.get_inputs_as_u32(reg)?))
+    }
+
+    /// Get two holdings as u32 with the specified word order
+    fn get_holdings_as_u32_with_order(&self, reg: u16, order: WordOrder) -> Result<u32, ErrorKind> {
+        Ok(order.swap_u32(self.get_holdings_as_u32(reg)?))
+    }
+
+    /// Set two inputs from u32 with the specified word order
+    fn set_inputs_from_u32_with_order(
+        &mut self,
+        reg: u16,
+        value: u32,
+        order: WordOrder,
+    ) -> Result<(), ErrorKind> {
+        self.set_inputs_from_u32(reg, order.swap_u32(value))
+    }
+
+    /// Set two holdings from u32 with the specified word order
+    fn set_holdings_from_u32_with_order(
+        &mut self,
+        reg: u16,
+        value: u32,
+        order: WordOrder,
+    ) -> Result<(), ErrorKind> {
+        self.set_holdings_from_u32(reg, order.swap_u32(value))
+    }
+
+    /// Get four inputs as u64 with the specified word order
+    fn get_inputs_as_u64_with_order(&self, reg: u16, order: WordOrder) -> Result<u64, ErrorKind> {
+        Ok(order.swap_u64(self.get_inputs_as_u64(reg)?))
+    }
+
+    /// Get four holdings as u64 with the specified word order
+    fn get_holdings_as_u64_with_order(&self, reg: u16, order: WordOrder) -> Result<u64, ErrorKind> {
+        Ok(order.swap_u64(self.get_holdings_as_u64(reg)?))
+    }
+
+    /// Set four inputs from u64 with the specified word order
+    fn set_inputs_from_u64_with_order(
+        &mut self,
+        reg: u16,
+        value: u64,
+        order: WordOrder,
+    ) -> Result<(), ErrorKind> {
+        self.set_inputs_from_u64(reg, order.swap_u64(value))
+    }
+
+    /// Set four holdings from u64 with the specified word order
+    fn set_holdings_from_u64_with_order(
+        &mut self,
+        reg: u16,
+        value: u64,
+        order: WordOrder,
+    ) -> Result<(), ErrorKind> {
+        self.set_holdings_from_u64(reg, order.swap_u64(value))
+    }
+
+    /// Get two input registers as IEEE754 32-bit float with the specified word order
+    fn get_inputs_as_f32_with_order(&self, reg: u16, order: WordOrder) -> Result<f32, ErrorKind> {
+        Ok(f32::from_bits(
+            self.get_inputs_as_u32_with_order(reg, order)?,
+        ))
+    }
+
+    /// Get two holding registers as IEEE754 32-bit float with the specified word order
+    fn get_holdings_as_f32_with_order(&self, reg: u16, order: WordOrder) -> Result<f32, ErrorKind> {
+        Ok(f32::from_bits(
+            self.get_holdings_as_u32_with_order(reg, order)?,
+        ))
+    }
+
+    /// Set IEEE 754 f32 to two input registers with the specified word order
+    fn set_inputs_from_f32_with_order(
+        &mut self,
+        reg: u16,
+        value: f32,
+        order: WordOrder,
+    ) -> Result<(), ErrorKind> {
+        self.set_inputs_from_u32_with_order(reg, value.to_bits(), order)
+    }
+
+    /// Set IEEE 754 f32 to two holding registers with the specified word order
+    fn set_holdings_from_f32_with_order(
+        &mut self,
+        reg: u16,
+        value: f32,
+        order: WordOrder,
+    ) -> Result<(), ErrorKind> {
+        self.set_holdings_from_u32_with_order(reg, value.to_bits(), order)
+    }
+
+    /// Get two holding registers as IEEE754 32-bit float with the specified byte order
+    fn get_holdings_as_f32_ordered(
+        &self,
+        reg: u16,
+        order: FloatByteOrder,
+    ) -> Result<f32, ErrorKind> {
+        Ok(f32::from_bits(
+            order.swap_u32(self.get_holdings_as_u32(reg)?),
+        ))
+    }
+
+    /// Set IEEE 754 f32 to two holding registers with the specified byte order
+    fn set_holdings_from_f32_ordered(
+        &mut self,
+        reg: u16,
+        value: f32,
+        order: FloatByteOrder,
+    ) -> Result<(), ErrorKind> {
+        self.set_holdings_from_u32(reg, order.swap_u32(value.to_bits()))
+    }
+
+    /// Get count holdings as a string (2 bytes per register, big-endian), the string is trimmed
+    /// at the first null byte, the same as
+    /// [`ModbusRequest::parse_string`](crate::client::ModbusRequest::parse_string)
+    ///
+    /// Note: the result is replaced
+    #[cfg(feature = "std")]
+    fn get_holdings_as_string(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut String,
+    ) -> Result<(), ErrorKind> {
+        if let Some(last) = last_reg(reg, usize::from(count))? {
+            self.get_holding(last)?;
+        }
+        let mut data = Vec::with_capacity(usize::from(count) * 2);
+        for i in 0..count {
+            data.extend_from_slice(&self.get_holding(reg + i)?.to_be_bytes());
+        }
+        let len = data.iter().position(|&c| c == b'\0').unwrap_or(data.len());
+        *result = match std::str::from_utf8(&data[..len]) {
+            Ok(v) => v.to_string(),
+            Err(_) => return Err(ErrorKind::Utf8Error),
+        };
+        Ok(())
+    }
+
+    /// Set holdings from a string (2 bytes per register, big-endian), the last register is
+    /// padded with a null byte if the string length is odd
+    ///
+    /// The range is checked before writing
+    #[allow(clippy::cast_possible_truncation)]
+    fn set_holdings_from_str(&mut self, reg: u16, value: &str) -> Result<(), ErrorKind> {
+        let bytes = value.as_bytes();
+        if let Some(last) = last_reg(reg, (bytes.len() + 1) / 2)? {
+            self.get_holding(last)?;
+        }
+        for (i, pair) in bytes.chunks(2).enumerate() {
+            let value = u16::from_be_bytes([pair[0], pair.get(1).copied().unwrap_or(0)]);
+            self.set_holding(reg + i as u16, value)?;
+        }
+        Ok(())
+    }
+
     /// Get N inputs represented as some [`RegisterRepresentable`] type T
     ///
     /// Returns the [`RegisterRepresentable`] once converted using
     /// [`RegisterRepresentable::from_registers_sequential`]
-    #[allow(clippy::cast_possible_truncation)]
     fn get_inputs_as_representable<const N: usize, T: RegisterRepresentable<N>>(
         &self,
         reg: u16,
-    ) -> Result<T, ErrorKind> {
+    ) -> Result<T, ErrorKind>
+    where
+        Self: Sized,
+    {
         let mut regs: [u16; N] = [0u16; N];
-        for (i, r) in regs.iter_mut().enumerate().take(N) {
-            *r = self.get_input(reg + i as u16)?;
-        }
+        // the range is checked in usize, so it never overflows
+        self.get_inputs_into(reg, &mut regs)?;
         Ok(T::from_registers_sequential(&regs))
     }
 
@@ -241,15 +829,16 @@ pub trait ModbusContext {
     ///
     /// Returns the [`RegisterRepresentable`] once converted using
     /// [`RegisterRepresentable::from_registers_sequential`]
-    #[allow(clippy::cast_possible_truncation)]
     fn get_holdings_as_representable<const N: usize, T: RegisterRepresentable<N>>(
         &self,
         reg: u16,
-    ) -> Result<T, ErrorKind> {
+    ) -> Result<T, ErrorKind>
+    where
+        Self: Sized,
+    {
         let mut regs: [u16; N] = [0u16; N];
-        for (i, r) in regs.iter_mut().enumerate().take(N) {
-            *r = self.get_holding(reg + i as u16)?;
-        }
+        // the range is checked in usize, so it never overflows
+        self.get_holdings_into(reg, &mut regs)?;
         Ok(T::from_registers_sequential(&regs))
     }
 
@@ -261,7 +850,10 @@ pub trait ModbusContext {
         &mut self,
         reg: u16,
         value: &T,
-    ) -> Result<(), ErrorKind> {
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
         let regs = value.to_registers_sequential();
         self.set_inputs_bulk(reg, &regs)
     }
@@ -274,7 +866,10 @@ pub trait ModbusContext {
         &mut self,
         reg: u16,
         value: &T,
-    ) -> Result<(), ErrorKind> {
+    ) -> Result<(), ErrorKind>
+    where
+        Self: Sized,
+    {
         let regs = value.to_registers_sequential();
         self.set_holdings_bulk(reg, &regs)
     }
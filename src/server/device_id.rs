@@ -0,0 +1,98 @@
+use crate::consts::{
+    MODBUS_DEVICE_ID_MAJOR_MINOR_REVISION, MODBUS_DEVICE_ID_PRODUCT_CODE,
+    MODBUS_DEVICE_ID_VENDOR_NAME,
+};
+use crate::ErrorKind;
+
+/// Max number of device identification objects
+pub const DEVICE_ID_MAX_OBJECTS: usize = 16;
+/// Max object value length (a single object must fit in a response)
+pub const DEVICE_ID_MAX_OBJECT_LEN: usize = 244;
+
+/// Device identification objects, returned by Read Device Identification (function 0x2B, MEI
+/// type 0x0E) requests
+///
+/// Objects 0x00-0x02 (basic) are mandatory, 0x03-0x06 are regular, 0x80-0xFF are extended
+/// (vendor-specific). The conformity level is calculated automatically from the registered
+/// objects.
+///
+/// ```
+/// use rmodbus::{consts::MODBUS_DEVICE_ID_PRODUCT_NAME, server::device_id::DeviceIdentification};
+///
+/// let mut device_id = DeviceIdentification::new(b"Vendor", b"PC-1", b"v1.0").unwrap();
+/// device_id.set_object(MODBUS_DEVICE_ID_PRODUCT_NAME, b"Product").unwrap();
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceIdentification<'a> {
+    objects: [(u8, &'a [u8]); DEVICE_ID_MAX_OBJECTS],
+    len: usize,
+}
+
+impl<'a> DeviceIdentification<'a> {
+    /// Create device identification with the basic objects
+    pub fn new(
+        vendor_name: &'a [u8],
+        product_code: &'a [u8],
+        revision: &'a [u8],
+    ) -> Result<Self, ErrorKind> {
+        let mut device_id = Self {
+            objects: [(0, &[]); DEVICE_ID_MAX_OBJECTS],
+            len: 0,
+        };
+        device_id.set_object(MODBUS_DEVICE_ID_VENDOR_NAME, vendor_name)?;
+        device_id.set_object(MODBUS_DEVICE_ID_PRODUCT_CODE, product_code)?;
+        device_id.set_object(MODBUS_DEVICE_ID_MAJOR_MINOR_REVISION, revision)?;
+        Ok(device_id)
+    }
+
+    /// Set (register or replace) an object
+    ///
+    /// Errors:
+    ///
+    /// * **OOB** the value is longer than [`DEVICE_ID_MAX_OBJECT_LEN`] or there is no space
+    ///   for a new object
+    pub fn set_object(&mut self, id: u8, value: &'a [u8]) -> Result<(), ErrorKind> {
+        if value.len() > DEVICE_ID_MAX_OBJECT_LEN {
+            return Err(ErrorKind::OOB);
+        }
+        // objects are kept sorted by id
+        let pos = self.objects[..self.len]
+            .iter()
+            .position(|(i, _)| *i >= id)
+            .unwrap_or(self.len);
+        if pos < self.len && self.objects[pos].0 == id {
+            self.objects[pos].1 = value;
+            return Ok(());
+        }
+        if self.len == DEVICE_ID_MAX_OBJECTS {
+            return Err(ErrorKind::OOB);
+        }
+        self.objects.copy_within(pos..self.len, pos + 1);
+        self.objects[pos] = (id, value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Get an object value
+    pub fn get_object(&self, id: u8) -> Option<&'a [u8]> {
+        self.objects()
+            .iter()
+            .find(|(i, _)| *i == id)
+            .map(|(_, value)| *value)
+    }
+
+    /// Registered objects, sorted by id
+    pub fn objects(&self) -> &[(u8, &'a [u8])] {
+        &self.objects[..self.len]
+    }
+
+    /// Conformity level (basic, regular or extended, individual access is always supported)
+    pub fn conformity_level(&self) -> u8 {
+        match self.objects().last() {
+            Some((id, _)) if *id >= 0x80 => 0x83,
+            Some((id, _)) if *id >= 0x03 => 0x82,
+            _ => 0x81,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use super::ModbusFrame;
+use crate::consts::{
+    MODBUS_DIAG_CLEAR_COUNTERS, MODBUS_DIAG_RETURN_BUS_COMMUNICATION_ERROR_COUNT,
+    MODBUS_DIAG_RETURN_BUS_EXCEPTION_ERROR_COUNT, MODBUS_DIAG_RETURN_BUS_MESSAGE_COUNT,
+    MODBUS_DIAG_RETURN_SERVER_MESSAGE_COUNT, MODBUS_DIAG_RETURN_SERVER_NO_RESPONSE_COUNT,
+};
+use crate::{ErrorKind, VectorTrait};
+
+/// Diagnostic counters, can be returned to clients with Diagnostics (function 0x08)
+/// sub-functions 0x0B-0x0F
+///
+/// ```no_run
+/// use rmodbus::{ModbusFrameBuf, ModbusProto, server::{ModbusFrame, diagnostics::DiagnosticCounters}};
+///
+/// let mut counters = DiagnosticCounters::default();
+/// let framebuf: ModbusFrameBuf = [0; 256];
+/// let mut response = Vec::new();
+/// let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut response);
+/// let result = frame.parse();
+/// if result.is_ok() {
+///     frame.process_diagnostics(|sub_function, data| counters.process(sub_function, data)).unwrap();
+/// }
+/// counters.count(&frame, &result);
+/// ```
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DiagnosticCounters {
+    /// messages detected on the bus
+    pub bus_messages: u16,
+    /// CRC/LRC errors
+    pub bus_communication_errors: u16,
+    /// exception responses
+    pub bus_exception_errors: u16,
+    /// messages addressed to the server (including broadcasts)
+    pub server_messages: u16,
+    /// messages the server has not responded to (broadcasts)
+    pub server_no_responses: u16,
+}
+
+impl DiagnosticCounters {
+    /// Update the counters
+    ///
+    /// Should be called after the frame is parsed and processed
+    pub fn count<V: VectorTrait<u8>>(
+        &mut self,
+        frame: &ModbusFrame<V>,
+        parse_result: &Result<(), ErrorKind>,
+    ) {
+        self.bus_messages = self.bus_messages.wrapping_add(1);
+        if matches!(
+            parse_result,
+            Err(ErrorKind::FrameCRCError | ErrorKind::FrameLRCError)
+        ) {
+            self.bus_communication_errors = self.bus_communication_errors.wrapping_add(1);
+            return;
+        }
+        if parse_result.is_err() || frame.func == 0 {
+            // broken or not addressed to the server
+            return;
+        }
+        self.server_messages = self.server_messages.wrapping_add(1);
+        if !frame.response_required {
+            self.server_no_responses = self.server_no_responses.wrapping_add(1);
+        } else if frame.error > 0 {
+            self.bus_exception_errors = self.bus_exception_errors.wrapping_add(1);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Diagnostics handler for counter sub-functions, see
+    /// [`process_diagnostics`](ModbusFrame::process_diagnostics)
+    ///
+    /// Returns IllegalFunction error for unsupported sub-functions
+    pub fn process(&mut self, sub_function: u16, data: u16) -> Result<u16, ErrorKind> {
+        match sub_function {
+            MODBUS_DIAG_CLEAR_COUNTERS => {
+                self.clear();
+                Ok(data)
+            }
+            MODBUS_DIAG_RETURN_BUS_MESSAGE_COUNT => Ok(self.bus_messages),
+            MODBUS_DIAG_RETURN_BUS_COMMUNICATION_ERROR_COUNT => Ok(self.bus_communication_errors),
+            MODBUS_DIAG_RETURN_BUS_EXCEPTION_ERROR_COUNT => Ok(self.bus_exception_errors),
+            MODBUS_DIAG_RETURN_SERVER_MESSAGE_COUNT => Ok(self.server_messages),
+            MODBUS_DIAG_RETURN_SERVER_NO_RESPONSE_COUNT => Ok(self.server_no_responses),
+            _ => Err(ErrorKind::IllegalFunction),
+        }
+    }
+}
+
+/// Communication event counters, returned to clients with Get Comm Event Counter (function 0x0B)
+///
+/// Attach the counters to frames with [`with_comm_counters`](ModbusFrame::with_comm_counters) to
+/// update them and to process function 0x0B (otherwise the function is not supported)
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CommEventCounters {
+    /// if set, the status word is 0xFFFF (a previously issued command is being processed)
+    pub busy: bool,
+    /// successfully completed requests (except exceptions and counter/log polls)
+    pub event_count: u16,
+    /// requests addressed to the server (including broadcasts)
+    pub message_count: u16,
+}
+
+impl CommEventCounters {
+    /// Status word, as returned by Get Comm Event Counter
+    pub fn status(&self) -> u16 {
+        if self.busy {
+            0xffff
+        } else {
+            0x0000
+        }
+    }
+}
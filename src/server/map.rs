@@ -0,0 +1,150 @@
+/// Defines a typed register map
+///
+/// Each entry `Name: kind @ reg as type;` generates a unit struct `Name` with `REG` (the first
+/// register) and `COUNT` (the number of registers taken) constants and typed `get` / `set`
+/// accessors, which call the corresponding [`ModbusContext`](crate::server::context::ModbusContext)
+/// methods.
+///
+/// Supported kinds and types:
+///
+/// * **coil**, **discrete**: `bool`
+/// * **input**, **holding**: `u16`, `u32`, `u64`, `f32`, `f64` (multi-register values are
+///   big-endian)
+///
+/// An unsupported kind / type combination, entries of the same kind with overlapping registers
+/// and entries above the register space are compile-time errors:
+///
+/// ```rust,compile_fail
+/// use rmodbus::modbus_map;
+///
+/// modbus_map! {
+///     Temperature: holding @ 100 as f32;
+///     Mode: holding @ 101 as u16;
+/// }
+/// ```
+///
+/// ```rust
+/// use rmodbus::{modbus_map, server::storage::ModbusStorageSmall};
+///
+/// modbus_map! {
+///     Temperature: holding @ 100 as f32;
+///     Mode: holding @ 102 as u16;
+///     Running: coil @ 0 as bool;
+/// }
+///
+/// let mut ctx = ModbusStorageSmall::new();
+/// Temperature::set(&mut ctx, 21.5).unwrap();
+/// Mode::set(&mut ctx, 3).unwrap();
+/// assert_eq!(Temperature::get(&ctx).unwrap(), 21.5);
+/// assert_eq!(Temperature::COUNT, 2);
+/// assert_eq!(ctx.holdings[Mode::REG as usize], 3);
+/// assert!(!Running::get(&ctx).unwrap());
+/// ```
+#[macro_export]
+macro_rules! modbus_map {
+    (@get coil bool, $ctx:ident, $reg:expr) => { $ctx.get_coil($reg) };
+    (@get discrete bool, $ctx:ident, $reg:expr) => { $ctx.get_discrete($reg) };
+    (@get input u16, $ctx:ident, $reg:expr) => { $ctx.get_input($reg) };
+    (@get input u32, $ctx:ident, $reg:expr) => { $ctx.get_inputs_as_u32($reg) };
+    (@get input u64, $ctx:ident, $reg:expr) => { $ctx.get_inputs_as_u64($reg) };
+    (@get input f32, $ctx:ident, $reg:expr) => { $ctx.get_inputs_as_f32($reg) };
+    (@get input f64, $ctx:ident, $reg:expr) => { $ctx.get_inputs_as_f64($reg) };
+    (@get holding u16, $ctx:ident, $reg:expr) => { $ctx.get_holding($reg) };
+    (@get holding u32, $ctx:ident, $reg:expr) => { $ctx.get_holdings_as_u32($reg) };
+    (@get holding u64, $ctx:ident, $reg:expr) => { $ctx.get_holdings_as_u64($reg) };
+    (@get holding f32, $ctx:ident, $reg:expr) => { $ctx.get_holdings_as_f32($reg) };
+    (@get holding f64, $ctx:ident, $reg:expr) => { $ctx.get_holdings_as_f64($reg) };
+    (@set coil bool, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_coil($reg, $v) };
+    (@set discrete bool, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_discrete($reg, $v) };
+    (@set input u16, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_input($reg, $v) };
+    (@set input u32, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_inputs_from_u32($reg, $v) };
+    (@set input u64, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_inputs_from_u64($reg, $v) };
+    (@set input f32, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_inputs_from_f32($reg, $v) };
+    (@set input f64, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_inputs_from_f64($reg, $v) };
+    (@set holding u16, $ctx:ident, $reg:expr, $v:ident) => { $ctx.set_holding($reg, $v) };
+    (@set holding u32, $ctx:ident, $reg:expr, $v:ident) => {
+        $ctx.set_holdings_from_u32($reg, $v)
+    };
+    (@set holding u64, $ctx:ident, $reg:expr, $v:ident) => {
+        $ctx.set_holdings_from_u64($reg, $v)
+    };
+    (@set holding f32, $ctx:ident, $reg:expr, $v:ident) => {
+        $ctx.set_holdings_from_f32($reg, $v)
+    };
+    (@set holding f64, $ctx:ident, $reg:expr, $v:ident) => {
+        $ctx.set_holdings_from_f64($reg, $v)
+    };
+    (@kind coil) => { 0 };
+    (@kind discrete) => { 1 };
+    (@kind input) => { 2 };
+    (@kind holding) => { 3 };
+    (@count bool) => { 1 };
+    (@count u16) => { 1 };
+    (@count u32) => { 2 };
+    (@count f32) => { 2 };
+    (@count u64) => { 4 };
+    (@count f64) => { 4 };
+    ($($(#[$meta:meta])* $vis:vis $name:ident: $kind:ident @ $reg:literal as $ty:ident;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Copy, Clone)]
+            $vis struct $name;
+
+            #[allow(dead_code)]
+            impl $name {
+                /// The first register
+                pub const REG: u16 = $reg;
+                /// The number of registers
+                pub const COUNT: u16 = $crate::modbus_map!(@count $ty);
+
+                pub fn get<C: $crate::server::context::ModbusContext>(
+                    ctx: &C,
+                ) -> Result<$ty, $crate::ErrorKind> {
+                    $crate::modbus_map!(@get $kind $ty, ctx, $reg)
+                }
+
+                pub fn set<C: $crate::server::context::ModbusContext>(
+                    ctx: &mut C,
+                    value: $ty,
+                ) -> Result<(), $crate::ErrorKind> {
+                    $crate::modbus_map!(@set $kind $ty, ctx, $reg, value)
+                }
+            }
+        )*
+
+        const _: () = assert!(
+            $crate::server::map::check(&[$((
+                $crate::modbus_map!(@kind $kind),
+                $reg,
+                $crate::modbus_map!(@count $ty),
+            )),*]),
+            "modbus_map: overlapping registers or registers out of range"
+        );
+    };
+}
+
+/// Used by [`modbus_map!`]: returns false if entries (kind, reg, count) of the same kind overlap
+/// or an entry is above the register space
+#[allow(clippy::cast_lossless)]
+pub const fn check(entries: &[(u8, u16, u16)]) -> bool {
+    let mut i = 0;
+    while i < entries.len() {
+        let (kind, reg, count) = entries[i];
+        if reg as u32 + count as u32 > u16::MAX as u32 + 1 {
+            return false;
+        }
+        let mut j = i + 1;
+        while j < entries.len() {
+            let (other_kind, other_reg, other_count) = entries[j];
+            if kind == other_kind
+                && (reg as u32) < other_reg as u32 + other_count as u32
+                && (other_reg as u32) < reg as u32 + count as u32
+            {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
@@ -1,4 +1,11 @@
+#[cfg(feature = "async")]
+pub mod async_context;
 pub mod context;
+pub mod device_id;
+pub mod diagnostics;
+#[doc(hidden)]
+pub mod map;
+pub mod offset;
 pub mod representable;
 pub mod storage;
 
@@ -7,7 +14,138 @@ pub use representable::representations;
 
 #[allow(clippy::wildcard_imports)]
 use crate::consts::*;
-use crate::{calc_crc16, calc_lrc, ErrorKind, ModbusProto, VectorTrait};
+use crate::{calc_crc16, calc_lrc, ErrorKind, ModbusProto, SliceWriter, VectorTrait};
+
+/// Frame processing observer, e.g. to collect per-function metrics, see
+/// [`ModbusFrame::with_observer`]
+///
+/// All methods do nothing by default
+pub trait FrameObserver {
+    /// Called by [`parse`](ModbusFrame::parse) for frames addressed to the server (including
+    /// broadcasts), reg and count are the parsed ones (see [`ModbusFrame`] fields)
+    fn on_request(&mut self, _func: u8, _reg: u16, _count: u16) {}
+    /// Called by [`finalize_response`](ModbusFrame::finalize_response), error is the Modbus
+    /// exception code, if set
+    fn on_response(&mut self, _func: u8, _error: Option<u8>) {}
+}
+
+struct ObserverRef<'a>(&'a mut (dyn FrameObserver + Send));
+
+impl core::fmt::Debug for ObserverRef<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("FrameObserver")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ObserverRef<'_> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "FrameObserver");
+    }
+}
+
+/// Reads registers as u8 for both generic contexts and context trait objects (the generic
+/// [`ModbusContext`](context::ModbusContext) methods are not available for trait objects)
+trait ContextRead: context::ModbusContext {
+    /// Appends coils, discretes (packed), inputs or holdings, depending on the function
+    fn read_as_u8<V: VectorTrait<u8>>(
+        &self,
+        func: u8,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind>;
+
+    /// The same as [`ContextRead::read_as_u8`], but registers are read one by one
+    ///
+    /// If zero_fill is set, out-of-context registers are read as zeros, otherwise the first error
+    /// is returned (the result may contain partial data)
+    fn read_as_u8_by_reg<V: VectorTrait<u8>>(
+        &self,
+        func: u8,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+        zero_fill: bool,
+    ) -> Result<(), ErrorKind> {
+        let mut cbyte = 0;
+        for i in 0..count {
+            let r = reg.checked_add(i).ok_or(ErrorKind::OOBContext);
+            match func {
+                MODBUS_GET_COILS | MODBUS_GET_DISCRETES => {
+                    let value = r.and_then(|r| {
+                        if func == MODBUS_GET_COILS {
+                            self.get_coil(r)
+                        } else {
+                            self.get_discrete(r)
+                        }
+                    });
+                    let value = match value {
+                        Err(_) if zero_fill => false,
+                        v => v?,
+                    };
+                    if value {
+                        cbyte |= 1 << (i % 8);
+                    }
+                    if i % 8 == 7 || i == count - 1 {
+                        result.push(cbyte)?;
+                        cbyte = 0;
+                    }
+                }
+                _ => {
+                    let value = r.and_then(|r| {
+                        if func == MODBUS_GET_INPUTS {
+                            self.get_input(r)
+                        } else {
+                            self.get_holding(r)
+                        }
+                    });
+                    let value = match value {
+                        Err(_) if zero_fill => 0,
+                        v => v?,
+                    };
+                    result.extend(&value.to_be_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C: context::ModbusContext> ContextRead for C {
+    fn read_as_u8<V: VectorTrait<u8>>(
+        &self,
+        func: u8,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        match func {
+            MODBUS_GET_COILS => self.get_coils_as_u8(reg, count, result),
+            MODBUS_GET_DISCRETES => self.get_discretes_as_u8(reg, count, result),
+            MODBUS_GET_INPUTS => self.get_inputs_as_u8(reg, count, result),
+            _ => self.get_holdings_as_u8(reg, count, result),
+        }
+    }
+}
+
+impl ContextRead for dyn context::ModbusContext + '_ {
+    fn read_as_u8<V: VectorTrait<u8>>(
+        &self,
+        func: u8,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        let len = result.len();
+        let res = self.read_as_u8_by_reg(func, reg, count, result, false);
+        // the result must not contain partial data on errors
+        if res.is_err() {
+            result.cut_end(result.len() - len, 0);
+        }
+        res
+    }
+}
 
 /// Modbus frame processor
 ///
@@ -27,7 +165,7 @@ use crate::{calc_crc16, calc_lrc, ErrorKind, ModbusProto, VectorTrait};
 ///     let mut mem = alloc_stack!([u8; 256]);
 ///     let mut response = FixedVec::new(&mut mem);
 ///     // create new frame processor object
-///     let mut frame = ModbusFrame::new(unit_id, &framebuf, ModbusProto::TcpUdp, &mut response);
+///     let mut frame = ModbusFrame::new(unit_id, &framebuf, ModbusProto::Tcp, &mut response);
 ///     // parse frame buffer
 ///     if frame.parse().is_ok() {
 ///         // parsed ok
@@ -57,7 +195,7 @@ use crate::{calc_crc16, calc_lrc, ErrorKind, ModbusProto, VectorTrait};
 /// ```
 macro_rules! tcp_response_set_data_len {
     ($self: expr, $len:expr) => {
-        if $self.proto == ModbusProto::TcpUdp {
+        if $self.proto.has_mbap_header() {
             $self.response.extend(&($len as u16).to_be_bytes())?;
         }
     };
@@ -74,18 +212,31 @@ pub struct ModbusFrame<'a, V: VectorTrait<u8>> {
     pub processing_required: bool,
     /// is response required
     pub response_required: bool,
+    /// after parse: the frame is a broadcast (unit id 0 or 255), no response is sent
+    pub was_broadcast: bool,
     /// is request read-only
     pub readonly: bool,
     /// Modbus frame start in buf (0 for RTU/ASCII, 6 for TCP)
     pub frame_start: usize,
+    /// after parse: TCP transaction id (None for RTU/ASCII)
+    pub transaction_id: Option<u16>,
     /// function requested
     pub func: u8,
-    /// starting register
+    /// starting register (for read/write multiple registers: the read one)
     pub reg: u16,
-    /// registers to process
+    /// registers to process (for read/write multiple registers: registers to read)
     pub count: u16,
     /// error code
     pub error: u8,
+    custom: bool,
+    lenient_oob: bool,
+    input_window: Option<(u16, u16)>,
+    enron: bool,
+    aligned_window: Option<(u16, u16)>,
+    max_pdu: usize,
+    unit_ids: Option<&'a [u8]>,
+    comm_counters: Option<&'a mut diagnostics::CommEventCounters>,
+    observer: Option<ObserverRef<'a>>,
 }
 
 impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
@@ -94,29 +245,170 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
         Self {
             unit_id,
             buf,
+            transaction_id: None,
             func: 0,
             proto,
             response,
             processing_required: false,
             readonly: true,
             response_required: false,
+            was_broadcast: false,
             frame_start: 0,
             count: 1,
             reg: 0,
             error: 0,
+            custom: false,
+            lenient_oob: false,
+            input_window: None,
+            enron: false,
+            aligned_window: None,
+            max_pdu: 253,
+            unit_ids: None,
+            comm_counters: None,
+            observer: None,
+        }
+    }
+    /// Create a frame which accepts requests for several unit ids (e.g. a gateway serving
+    /// multiple logical devices over a single port)
+    ///
+    /// After [`parse`](ModbusFrame::parse), `unit_id` is set to the matched one, so the response
+    /// echoes the correct id
+    pub fn new_multi(
+        unit_ids: &'a [u8],
+        buf: &'a [u8],
+        proto: ModbusProto,
+        response: &'a mut V,
+    ) -> Self {
+        let mut frame = Self::new(unit_ids.first().copied().unwrap_or(0), buf, proto, response);
+        frame.unit_ids = Some(unit_ids);
+        frame
+    }
+    /// Is the unit id served by the frame (broadcasts are not checked)
+    pub fn accepts_unit(&self, unit_id: u8) -> bool {
+        if let Some(unit_ids) = self.unit_ids {
+            unit_ids.contains(&unit_id)
+        } else {
+            unit_id == self.unit_id
+        }
+    }
+    /// Attach communication event counters, which are updated by
+    /// [`parse`](ModbusFrame::parse) and [`finalize_response`](ModbusFrame::finalize_response)
+    /// and enable Get Comm Event Counter (function 0x0B) processing
+    pub fn with_comm_counters(mut self, counters: &'a mut diagnostics::CommEventCounters) -> Self {
+        self.comm_counters = Some(counters);
+        self
+    }
+    /// Attach a [`FrameObserver`], which is notified by [`parse`](ModbusFrame::parse) and
+    /// [`finalize_response`](ModbusFrame::finalize_response)
+    pub fn with_observer(mut self, observer: &'a mut (dyn FrameObserver + Send)) -> Self {
+        self.observer = Some(ObserverRef(observer));
+        self
+    }
+    /// Lenient mode for reading functions (1-4): registers which are out of the context are
+    /// returned as zeros instead of Illegal Data Address error (some legacy clients expect this)
+    ///
+    /// Use with care: clients can not distinguish missing registers from zero values
+    pub fn lenient_oob(mut self, lenient: bool) -> Self {
+        self.lenient_oob = lenient;
+        self
+    }
+    /// Non-standard, for simulators and test harnesses only: write multiple registers (function
+    /// 16) requests, which fully fit the window of count registers starting from reg, set inputs
+    /// instead of holdings in [`process_write`](ModbusFrame::process_write)
+    ///
+    /// Such writes are not reported by [`changes`](ModbusFrame::changes) and
+    /// [`changed_values`](ModbusFrame::changed_values), as holdings are not changed
+    pub fn allow_input_writes(mut self, reg: u16, count: u16) -> Self {
+        self.input_window = Some((reg, count));
+        self
+    }
+    /// Enron (Daniel) Modbus mode for reading functions 3-4: the request count is a number of
+    /// 32-bit registers (max 62), each one is read from two consecutive context registers,
+    /// starting from the requested one (big word first), so the response carries count * 4 bytes
+    ///
+    /// Non-standard, used by some flow computers, see
+    /// [`ModbusRequest::enron_mode`](crate::client::ModbusRequest::enron_mode)
+    pub fn enron(mut self, enabled: bool) -> Self {
+        self.enron = enabled;
+        self
+    }
+    /// A safety aid for typed register maps (e.g. f32 values at even addresses): multi-register
+    /// access (functions 3, 4, 16 and 23) to holdings / inputs, which intersects the window of
+    /// count registers starting from reg, must start at an even address and have an even count,
+    /// otherwise [`parse`](ModbusFrame::parse) sets Illegal Data Address error
+    ///
+    /// Single register access is not checked. In [Enron mode](ModbusFrame::enron), reads are
+    /// checked by the number of 16-bit registers they access
+    pub fn require_even_alignment(mut self, reg: u16, count: u16) -> Self {
+        self.aligned_window = Some((reg, count));
+        self
+    }
+    /// Maximum accepted request PDU size (function code and data), default: 253 (the protocol
+    /// limit). Writes of multiple coils / registers (functions 15, 16 and 23) with larger PDUs
+    /// get Illegal Data Value error, useful for gateways protecting downstream devices with
+    /// smaller buffers
+    pub fn max_pdu(mut self, max_pdu: usize) -> Self {
+        self.max_pdu = max_pdu;
+        self
+    }
+    fn misaligned(&self, reg: u16, count: u16) -> bool {
+        match self.aligned_window {
+            Some((w_reg, w_count)) if count > 1 => {
+                let end = u32::from(reg) + u32::from(count);
+                let w_end = u32::from(w_reg) + u32::from(w_count);
+                // the access range intersects the window
+                u32::from(reg) < w_end && u32::from(w_reg) < end && (reg % 2 != 0 || count % 2 != 0)
+            }
+            _ => false,
+        }
+    }
+    /// Number of 16-bit registers to read with functions 3-4
+    fn read_words(&self) -> u16 {
+        if self.enron {
+            self.count << 1
+        } else {
+            self.count
+        }
+    }
+    fn writes_inputs(&self) -> bool {
+        match self.input_window {
+            Some((reg, count)) if self.func == MODBUS_SET_HOLDINGS_BULK => {
+                self.reg >= reg
+                    && u32::from(self.reg) + u32::from(self.count)
+                        <= u32::from(reg) + u32::from(count)
+            }
+            _ => false,
         }
     }
     /// Should be always called if response needs to be sent
     pub fn finalize_response(&mut self) -> Result<(), ErrorKind> {
+        if let Some(ref mut observer) = self.observer {
+            observer.0.on_response(
+                self.func,
+                if self.error > 0 {
+                    Some(self.error)
+                } else {
+                    None
+                },
+            );
+        }
+        if let Some(ref mut counters) = self.comm_counters {
+            if self.error == 0
+                && self.func != MODBUS_GET_COMM_EVENT_COUNTER
+                && self.func != MODBUS_GET_COMM_EVENT_LOG
+            {
+                counters.event_count = counters.event_count.wrapping_add(1);
+            }
+        }
         if self.error > 0 {
             match self.proto {
-                ModbusProto::TcpUdp => {
+                ModbusProto::Tcp | ModbusProto::Udp => {
                     self.response
                         // write 2b length 1b unit ID, 1b function code and 1b error
                         // 2b transaction ID and 2b protocol ID were already written by .parse()
                         .extend(&[0, 3, self.unit_id, self.func + 0x80, self.error])?;
                 }
-                ModbusProto::Rtu | ModbusProto::Ascii => {
+                ModbusProto::Rtu | ModbusProto::RtuOverTcp | ModbusProto::Ascii => {
                     self.response
                         // write 1b unit ID, 1b function code and 1b error
                         .extend(&[self.unit_id, self.func + 0x80, self.error])?;
@@ -124,7 +416,7 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
             }
         }
         match self.proto {
-            ModbusProto::Rtu => {
+            ModbusProto::Rtu | ModbusProto::RtuOverTcp => {
                 let len = self.response.len();
                 if len > u8::MAX as usize {
                     return Err(ErrorKind::OOB);
@@ -142,7 +434,7 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                 let lrc = calc_lrc(self.response.as_slice(), len as u8);
                 self.response.push(lrc)
             }
-            ModbusProto::TcpUdp => Ok(()),
+            ModbusProto::Tcp | ModbusProto::Udp => Ok(()),
         }
     }
     /// Process write functions
@@ -150,6 +442,20 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
         &mut self,
         ctx: &mut C,
     ) -> Result<(), ErrorKind> {
+        self.process_write_ctx(ctx)
+    }
+    /// The same as [`process_write`](ModbusFrame::process_write) but for a context trait object,
+    /// allows to choose the storage backend at runtime
+    ///
+    /// Registers are read with per-register virtual calls, which is slightly slower than the
+    /// generic version
+    pub fn process_write_dyn(
+        &mut self,
+        ctx: &mut dyn context::ModbusContext,
+    ) -> Result<(), ErrorKind> {
+        self.process_write_ctx(ctx)
+    }
+    fn process_write_ctx<C: ContextRead + ?Sized>(&mut self, ctx: &mut C) -> Result<(), ErrorKind> {
         match self.func {
             MODBUS_SET_COIL => {
                 // func 5
@@ -200,6 +506,11 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                         self.count,
                         &self.buf[self.frame_start + 7..self.frame_start + 7 + bytes as usize],
                     )
+                } else if self.writes_inputs() {
+                    ctx.set_inputs_from_u8(
+                        self.reg,
+                        &self.buf[self.frame_start + 7..self.frame_start + 7 + bytes as usize],
+                    )
                 } else {
                     ctx.set_holdings_from_u8(
                         self.reg,
@@ -216,6 +527,82 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                     Ok(())
                 }
             }
+            MODBUS_MASK_WRITE_REGISTER => {
+                // func 22
+                // mask write register
+                let and_mask = u16::from_be_bytes([
+                    self.buf[self.frame_start + 4],
+                    self.buf[self.frame_start + 5],
+                ]);
+                let or_mask = u16::from_be_bytes([
+                    self.buf[self.frame_start + 6],
+                    self.buf[self.frame_start + 7],
+                ]);
+                let current = match ctx.get_holding(self.reg) {
+                    Ok(v) => v,
+                    Err(_) => {
+                        self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+                        return Ok(());
+                    }
+                };
+                if ctx
+                    .set_holding(self.reg, (current & and_mask) | (or_mask & !and_mask))
+                    .is_err()
+                {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+                    return Ok(());
+                }
+                tcp_response_set_data_len!(self, 8);
+                // 8b unit, func, reg, and mask, or mask
+                self.response
+                    .extend(&self.buf[self.frame_start..self.frame_start + 8])
+            }
+            MODBUS_READ_WRITE_MULTIPLE_REGISTERS => {
+                // func 23
+                // write multiple registers, then read multiple registers
+                let write_reg = u16::from_be_bytes([
+                    self.buf[self.frame_start + 6],
+                    self.buf[self.frame_start + 7],
+                ]);
+                let bytes = self.buf[self.frame_start + 10];
+                // the read range is checked before writing, so a broken request does not modify
+                // the context
+                if ctx
+                    .get_holding(self.reg.saturating_add(self.count - 1))
+                    .is_err()
+                    || ctx
+                        .set_holdings_from_u8(
+                            write_reg,
+                            &self.buf
+                                [self.frame_start + 11..self.frame_start + 11 + bytes as usize],
+                        )
+                        .is_err()
+                {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+                    return Ok(());
+                }
+                let data_len = self.count << 1;
+                tcp_response_set_data_len!(self, data_len + 3);
+                // 2b unit and func
+                self.response
+                    .extend(&self.buf[self.frame_start..self.frame_start + 2])?;
+                #[allow(clippy::cast_possible_truncation)]
+                // 1b data len
+                self.response.push(data_len as u8)?;
+                if let Err(e) =
+                    ctx.read_as_u8(MODBUS_GET_HOLDINGS, self.reg, self.count, self.response)
+                {
+                    if e == ErrorKind::OOBContext {
+                        self.response.cut_end(5, 0);
+                        self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+                        Ok(())
+                    } else {
+                        Err(e)
+                    }
+                } else {
+                    Ok(())
+                }
+            }
             MODBUS_GET_HOLDINGS | MODBUS_GET_INPUTS | MODBUS_GET_COILS | MODBUS_GET_DISCRETES => {
                 Err(ErrorKind::ReadCallOnWriteFrame)
             }
@@ -229,7 +616,11 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
     /// [`ModbusContext`](context::ModbusContext)) don't forget to call
     /// [`process_external_write`](ModbusFrame::process_external_write), these two calls together
     /// replace the call to [`process_write`](ModbusFrame::process_write).
-    pub fn get_external_write(&mut self) -> Result<Write, ErrorKind> {
+    ///
+    /// The returned struct borrows the request buffer only, so the frame can be used again (e.g.
+    /// to call [`process_external_write`](ModbusFrame::process_external_write) after the write is
+    /// awaited) while the write data is still held.
+    pub fn get_external_write(&mut self) -> Result<Write<'a>, ErrorKind> {
         match self.func {
             MODBUS_SET_COIL => {
                 // func 5
@@ -345,6 +736,17 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
 
     /// Process read functions
     pub fn process_read<C: context::ModbusContext>(&mut self, ctx: &C) -> Result<(), ErrorKind> {
+        self.process_read_ctx(ctx)
+    }
+    /// The same as [`process_read`](ModbusFrame::process_read) but for a context trait object,
+    /// allows to choose the storage backend at runtime
+    ///
+    /// Registers are read with per-register virtual calls, which is slightly slower than the
+    /// generic version
+    pub fn process_read_dyn(&mut self, ctx: &dyn context::ModbusContext) -> Result<(), ErrorKind> {
+        self.process_read_ctx(ctx)
+    }
+    fn process_read_ctx<C: ContextRead + ?Sized>(&mut self, ctx: &C) -> Result<(), ErrorKind> {
         match self.func {
             MODBUS_GET_COILS | MODBUS_GET_DISCRETES => {
                 // funcs 1 - 2
@@ -362,13 +764,11 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                 }
                 #[allow(clippy::cast_possible_truncation)]
                 self.response.push(data_len as u8)?;
-                let result = if self.func == MODBUS_GET_COILS {
-                    ctx.get_coils_as_u8(self.reg, self.count, self.response)
-                } else {
-                    ctx.get_discretes_as_u8(self.reg, self.count, self.response)
-                };
+                let result = ctx.read_as_u8(self.func, self.reg, self.count, self.response);
                 if let Err(e) = result {
-                    if e == ErrorKind::OOBContext {
+                    if e == ErrorKind::OOBContext && self.lenient_oob {
+                        ctx.read_as_u8_by_reg(self.func, self.reg, self.count, self.response, true)
+                    } else if e == ErrorKind::OOBContext {
                         self.response.cut_end(5, 0);
                         self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
                         Ok(())
@@ -382,7 +782,8 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
             MODBUS_GET_HOLDINGS | MODBUS_GET_INPUTS => {
                 // funcs 3 - 4
                 // read holdings / inputs
-                let data_len = self.count << 1;
+                let count = self.read_words();
+                let data_len = count << 1;
                 tcp_response_set_data_len!(self, data_len + 3);
                 // 2b unit and func
                 self.response
@@ -393,13 +794,11 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                 #[allow(clippy::cast_possible_truncation)]
                 // 1b data len
                 self.response.push(data_len as u8)?;
-                let result = if self.func == MODBUS_GET_HOLDINGS {
-                    ctx.get_holdings_as_u8(self.reg, self.count, self.response)
-                } else {
-                    ctx.get_inputs_as_u8(self.reg, self.count, self.response)
-                };
+                let result = ctx.read_as_u8(self.func, self.reg, count, self.response);
                 if let Err(e) = result {
-                    if e == ErrorKind::OOBContext {
+                    if e == ErrorKind::OOBContext && self.lenient_oob {
+                        ctx.read_as_u8_by_reg(self.func, self.reg, count, self.response, true)
+                    } else if e == ErrorKind::OOBContext {
                         self.response.cut_end(5, 0);
                         self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
                         Ok(())
@@ -410,14 +809,69 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                     Ok(())
                 }
             }
+            MODBUS_GET_COMM_EVENT_COUNTER => {
+                // func 11
+                // get comm event counter
+                let (status, count) = match self.comm_counters {
+                    Some(ref counters) => (counters.status(), counters.event_count),
+                    None => {
+                        self.error = MODBUS_ERROR_ILLEGAL_FUNCTION;
+                        return Ok(());
+                    }
+                };
+                tcp_response_set_data_len!(self, 6);
+                // 2b unit and func
+                self.response
+                    .extend(&self.buf[self.frame_start..self.frame_start + 2])?;
+                self.response.extend(&status.to_be_bytes())?;
+                self.response.extend(&count.to_be_bytes())
+            }
+            MODBUS_DIAGNOSTICS => {
+                // func 8, sub-function 0
+                // return query data
+                tcp_response_set_data_len!(self, 6);
+                // 6b unit, func, sub-function, data
+                self.response
+                    .extend(&self.buf[self.frame_start..self.frame_start + 6])
+            }
             MODBUS_SET_COIL
             | MODBUS_SET_HOLDING
             | MODBUS_SET_COILS_BULK
-            | MODBUS_SET_HOLDINGS_BULK => Err(ErrorKind::WriteCallOnReadFrame),
+            | MODBUS_SET_HOLDINGS_BULK
+            | MODBUS_MASK_WRITE_REGISTER
+            | MODBUS_READ_WRITE_MULTIPLE_REGISTERS => Err(ErrorKind::WriteCallOnReadFrame),
             _ => Ok(()),
         }
     }
 
+    /// Describe the requested read without touching the response buffer.
+    ///
+    /// The returned struct does not borrow the frame, so the data can be fetched asynchronously,
+    /// then put into the response with [`get_external_read`](ModbusFrame::get_external_read) and
+    /// [`process_external_read`](ModbusFrame::process_external_read).
+    pub fn read_request(&self) -> Result<ReadRequest, ErrorKind> {
+        let kind = match self.func {
+            MODBUS_GET_COILS => ReadKind::Coils,
+            MODBUS_GET_DISCRETES => ReadKind::Discretes,
+            MODBUS_GET_INPUTS => ReadKind::Inputs,
+            MODBUS_GET_HOLDINGS => ReadKind::Holdings,
+            MODBUS_SET_COIL
+            | MODBUS_SET_HOLDING
+            | MODBUS_SET_COILS_BULK
+            | MODBUS_SET_HOLDINGS_BULK => return Err(ErrorKind::WriteCallOnReadFrame),
+            _ => return Err(ErrorKind::IllegalFunction),
+        };
+        let count = match kind {
+            ReadKind::Coils | ReadKind::Discretes => self.count,
+            ReadKind::Inputs | ReadKind::Holdings => self.read_words(),
+        };
+        Ok(ReadRequest {
+            kind,
+            address: self.reg,
+            count,
+        })
+    }
+
     /// Construct [`Read`] struct describing the requested read.
     ///
     /// If you use this to process the requested read yourself (so not calling
@@ -459,7 +913,8 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
             MODBUS_GET_HOLDINGS | MODBUS_GET_INPUTS => {
                 // funcs 3 - 4
                 // read holdings / inputs
-                let data_len = self.count << 1;
+                let count = self.read_words();
+                let data_len = count << 1;
                 tcp_response_set_data_len!(self, data_len + 3);
                 // 2b unit and func
                 self.response
@@ -478,7 +933,7 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
 
                 Ok(Read::Words(ReadWords {
                     address: self.reg,
-                    count: self.count,
+                    count,
                     buf: &mut self.response.as_mut_slice()[current_length..new_length],
                 }))
             }
@@ -519,16 +974,482 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
         }
     }
 
+    /// The same as [`process_read`](ModbusFrame::process_read) but for an asynchronous context,
+    /// supports functions 1-4 only
+    ///
+    /// Does nothing if the frame does not require processing or an error (e.g. Illegal Data
+    /// Value for a count above the function limit) is already set by
+    /// [`parse`](ModbusFrame::parse). With [`lenient_oob`](ModbusFrame::lenient_oob), if the
+    /// range is out of the context, registers are read one by one and out-of-context ones are
+    /// zero-filled.
+    ///
+    /// Errors:
+    ///
+    /// * **WriteCallOnReadFrame** the frame is a write one
+    /// * **IllegalFunction** the function is not supported (the error is not set in the frame)
+    #[cfg(feature = "async")]
+    pub async fn process_read_async<C: async_context::AsyncModbusContext + ?Sized>(
+        &mut self,
+        ctx: &C,
+    ) -> Result<(), ErrorKind> {
+        if self.error != 0 || !self.processing_required {
+            return Ok(());
+        }
+        let req = self.read_request()?;
+        let count = usize::from(req.count);
+        let mut bits = [false; 2000];
+        let mut words = [0u16; 125];
+        let mut result =
+            read_async(ctx, req.kind, req.address, &mut bits, &mut words, 0..count).await;
+        if result == Err(ErrorKind::OOBContext) && self.lenient_oob {
+            result = Ok(());
+            for i in 0..count {
+                #[allow(clippy::cast_possible_truncation)]
+                let Some(reg) = req.address.checked_add(i as u16) else {
+                    break;
+                };
+                match read_async(ctx, req.kind, reg, &mut bits, &mut words, i..i + 1).await {
+                    Ok(()) | Err(ErrorKind::OOBContext) => {}
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                }
+            }
+        }
+        if let Err(e) = result {
+            return self.process_external_read(Err(async_context_error(e)));
+        }
+        match self.get_external_read()? {
+            Read::Bits(read) => {
+                for (i, value) in bits[..count].iter().enumerate() {
+                    if *value {
+                        read.buf[i / 8] |= 1 << (i % 8);
+                    }
+                }
+            }
+            Read::Words(read) => {
+                for (chunk, value) in read.buf.chunks_mut(2).zip(&words[..count]) {
+                    chunk.copy_from_slice(&value.to_be_bytes());
+                }
+            }
+        }
+        self.process_external_read(Ok(()))
+    }
+
+    /// The same as [`process_write`](ModbusFrame::process_write) but for an asynchronous
+    /// context, supports functions 5, 6, 15 and 16 only (other functions get IllegalFunction
+    /// error)
+    ///
+    /// Errors:
+    ///
+    /// * **ReadCallOnWriteFrame** the frame is a read one
+    #[cfg(feature = "async")]
+    pub async fn process_write_async<C: async_context::AsyncModbusContext + ?Sized>(
+        &mut self,
+        ctx: &mut C,
+    ) -> Result<(), ErrorKind> {
+        let write = match self.get_external_write() {
+            Ok(write) => write,
+            // the error is set in the frame
+            Err(e) if e.is_modbus_error() => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let result = match write {
+            Write::Bits(write) => {
+                let count = usize::from(write.count);
+                let mut bits = [false; 1968];
+                for (i, value) in bits[..count].iter_mut().enumerate() {
+                    *value = write.data[i / 8] >> (i % 8) & 1 == 1;
+                }
+                ctx.set_coils_bulk(write.address, &bits[..count]).await
+            }
+            Write::Words(write) => {
+                let count = usize::from(write.count);
+                let mut words = [0u16; 123];
+                for (value, chunk) in words[..count].iter_mut().zip(write.data.chunks(2)) {
+                    *value = u16::from_be_bytes([chunk[0], chunk[1]]);
+                }
+                ctx.set_holdings_bulk(write.address, &words[..count]).await
+            }
+        };
+        self.process_external_write(result.map_err(async_context_error))
+    }
+
+    /// After [`parse`](ModbusFrame::parse): the function code if it is not supported by the crate
+    /// (see [`process_custom`](ModbusFrame::process_custom)), useful for logging
+    pub fn unsupported_function(&self) -> Option<u8> {
+        if self.custom {
+            Some(self.func)
+        } else {
+            None
+        }
+    }
+
+    /// After [`parse`](ModbusFrame::parse): the full length of the response a read/write function
+    /// will produce, including MBAP header (TCP/UDP), CRC (RTU) or LRC (ASCII, the response is
+    /// binary, before it is encoded), useful to pre-size buffers
+    ///
+    /// Returns `None` if an error is already set or the function is not a read/write one
+    pub fn expected_response_len(&self) -> Option<usize> {
+        if self.error != 0 {
+            return None;
+        }
+        // unit id, function and the data
+        let len = match self.func {
+            MODBUS_GET_COILS | MODBUS_GET_DISCRETES => 3 + (usize::from(self.count) + 7) / 8,
+            MODBUS_GET_HOLDINGS | MODBUS_GET_INPUTS => 3 + usize::from(self.read_words()) * 2,
+            MODBUS_READ_WRITE_MULTIPLE_REGISTERS => 3 + usize::from(self.count) * 2,
+            MODBUS_SET_COIL
+            | MODBUS_SET_HOLDING
+            | MODBUS_SET_COILS_BULK
+            | MODBUS_SET_HOLDINGS_BULK => 6,
+            MODBUS_MASK_WRITE_REGISTER => 8,
+            _ => return None,
+        };
+        Some(match self.proto {
+            ModbusProto::Tcp | ModbusProto::Udp => len + 6,
+            ModbusProto::Rtu | ModbusProto::RtuOverTcp => len + 2,
+            ModbusProto::Ascii => len + 1,
+        })
+    }
+
+    /// Process a custom function
+    ///
+    /// Functions, not supported by the crate (all except ones listed in [`consts`](crate::consts)
+    /// as MODBUS_GET_* / MODBUS_SET_* / MODBUS_MASK_WRITE_REGISTER /
+    /// MODBUS_READ_WRITE_MULTIPLE_REGISTERS) are considered custom, e.g. user-defined functions
+    /// 0x41-0x48 or 0x11 (report server ID). [`parse`](ModbusFrame::parse) marks such frames
+    /// with IllegalFunction error, unless the method is called after it.
+    ///
+    /// data_len - request data length (the function code is not included), for TCP/UDP it must
+    /// match the frame header, for RTU/ASCII it is used to verify the frame CRC/LRC
+    ///
+    /// The handler gets the function code, the request data and the response vector to write
+    /// the response data (after the function code) into. Unit ID, function code, TCP/UDP length
+    /// and CRC/LRC (with [`finalize_response`](ModbusFrame::finalize_response)) are written by
+    /// the crate. If the handler returns a Modbus error, it is sent back to the client.
+    ///
+    /// Does nothing if the frame function is not custom.
+    pub fn process_custom<F>(&mut self, data_len: u8, handler: F) -> Result<(), ErrorKind>
+    where
+        F: FnOnce(u8, &[u8], &mut V) -> Result<(), ErrorKind>,
+    {
+        if !self.custom {
+            return Ok(());
+        }
+        let data_start = self.frame_start + 2;
+        let data_end = data_start + data_len as usize;
+        let checksum_len = match self.proto {
+            ModbusProto::Tcp | ModbusProto::Udp => 0,
+            ModbusProto::Rtu | ModbusProto::RtuOverTcp => 2,
+            ModbusProto::Ascii => 1,
+        };
+        if data_end + checksum_len > self.buf.len() {
+            return Err(ErrorKind::OOB);
+        }
+        if self.proto.has_mbap_header() {
+            if u16::from_be_bytes([self.buf[4], self.buf[5]]) != u16::from(data_len) + 2 {
+                return Err(ErrorKind::FrameBroken);
+            }
+        } else if data_len > 251 {
+            return Err(ErrorKind::FrameCRCError);
+        } else {
+            self.check_frame_crc(data_len + 2)?;
+        }
+        if !self.response_required {
+            // broadcast
+            let result = handler(self.func, &self.buf[data_start..data_end], self.response);
+            self.response.clear();
+            return match result {
+                Err(e) if !e.is_modbus_error() => Err(e),
+                _ => Ok(()),
+            };
+        }
+        self.error = 0;
+        tcp_response_set_data_len!(self, 0);
+        self.response.extend(&[self.unit_id, self.func])?;
+        match handler(self.func, &self.buf[data_start..data_end], self.response) {
+            Ok(()) => {
+                if self.proto.has_mbap_header() {
+                    let len_buf = u16::try_from(self.response.len() - 6)?.to_be_bytes();
+                    self.response.replace(4, len_buf[0]);
+                    self.response.replace(5, len_buf[1]);
+                }
+                Ok(())
+            }
+            Err(e) if e.is_modbus_error() => {
+                self.set_modbus_error_if_unset(&e)?;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Process Diagnostics request (function 0x08)
+    ///
+    /// Sub-function 0x00 (return query data) is processed by
+    /// [`process_read`](ModbusFrame::process_read). Other sub-functions are marked by
+    /// [`parse`](ModbusFrame::parse) with IllegalFunction error, unless the method is called.
+    ///
+    /// The handler gets the sub-function and the request data word and returns the response
+    /// data word. If the handler returns a Modbus error (e.g. IllegalFunction for unsupported
+    /// sub-functions), it is sent back to the client. Counter sub-functions can be processed
+    /// with [`DiagnosticCounters::process`](diagnostics::DiagnosticCounters::process).
+    ///
+    /// Does nothing if the frame is not a Diagnostics request or has been already processed.
+    pub fn process_diagnostics<F>(&mut self, handler: F) -> Result<(), ErrorKind>
+    where
+        F: FnOnce(u16, u16) -> Result<u16, ErrorKind>,
+    {
+        if self.func != MODBUS_DIAGNOSTICS
+            || self.error != MODBUS_ERROR_ILLEGAL_FUNCTION
+            || !self.response_required
+        {
+            return Ok(());
+        }
+        let data = u16::from_be_bytes([
+            self.buf[self.frame_start + 4],
+            self.buf[self.frame_start + 5],
+        ]);
+        match handler(self.reg, data) {
+            Ok(value) => {
+                self.error = 0;
+                tcp_response_set_data_len!(self, 6);
+                // 4b unit, func, sub-function
+                self.response
+                    .extend(&self.buf[self.frame_start..self.frame_start + 4])?;
+                self.response.extend(&value.to_be_bytes())
+            }
+            Err(e) if e.is_modbus_error() => {
+                self.error = 0;
+                self.set_modbus_error_if_unset(&e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Process Read Exception Status request (function 0x07)
+    ///
+    /// Should be called after [`parse`](ModbusFrame::parse), which marks such frames with
+    /// IllegalFunction error, unless the method is called. The response contains the single
+    /// status byte (8 exception status outputs, device-specific).
+    ///
+    /// Does nothing if the frame is not a Read Exception Status request.
+    pub fn process_exception_status(&mut self, status: u8) -> Result<(), ErrorKind> {
+        if self.func != MODBUS_READ_EXCEPTION_STATUS
+            || self.error != MODBUS_ERROR_ILLEGAL_FUNCTION
+            || !self.response_required
+        {
+            return Ok(());
+        }
+        self.error = 0;
+        tcp_response_set_data_len!(self, 3);
+        // 2b unit and func
+        self.response
+            .extend(&self.buf[self.frame_start..self.frame_start + 2])?;
+        self.response.push(status)
+    }
+
+    /// Process Report Server ID request (function 0x11)
+    ///
+    /// Should be called after [`parse`](ModbusFrame::parse), which marks such frames with
+    /// IllegalFunction error, unless the method is called. The response contains the server id
+    /// bytes, followed by the run indicator status (0x00 - off, 0xFF - on).
+    ///
+    /// Does nothing if the frame is not a Report Server ID request.
+    ///
+    /// Errors:
+    ///
+    /// * **OOB** the server id is longer than 250 bytes
+    pub fn process_report_server_id(
+        &mut self,
+        server_id: &[u8],
+        running: bool,
+    ) -> Result<(), ErrorKind> {
+        if self.func != MODBUS_REPORT_SERVER_ID
+            || self.error != MODBUS_ERROR_ILLEGAL_FUNCTION
+            || !self.response_required
+        {
+            return Ok(());
+        }
+        if server_id.len() > 250 {
+            return Err(ErrorKind::OOB);
+        }
+        self.error = 0;
+        #[allow(clippy::cast_possible_truncation)]
+        let data_len = server_id.len() as u8 + 1;
+        tcp_response_set_data_len!(self, data_len + 3);
+        // 2b unit and func, 1b data len
+        self.response
+            .extend(&self.buf[self.frame_start..self.frame_start + 2])?;
+        self.response.push(data_len)?;
+        self.response.extend(server_id)?;
+        self.response.push(if running { 0xff } else { 0x00 })
+    }
+
+    /// Process Read Device Identification request (function 0x2B, MEI type 0x0E)
+    ///
+    /// Should be called after [`parse`](ModbusFrame::parse), which marks such frames with
+    /// IllegalFunction error, unless the method is called. Stream access read codes (basic,
+    /// regular, extended) return as many objects as fit in a single response, starting from the
+    /// requested object id (or from the first one if the id is not known), setting "more follows"
+    /// and the next object id if a continuation is required. Individual access returns
+    /// IllegalDataAddress error if the object is not registered.
+    ///
+    /// Does nothing if the frame is not a Read Device Identification request.
+    pub fn process_read_device_id(
+        &mut self,
+        device_id: &device_id::DeviceIdentification,
+    ) -> Result<(), ErrorKind> {
+        if self.func != MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT
+            || self.buf[self.frame_start + 2] != MODBUS_MEI_READ_DEVICE_ID
+            || self.error != MODBUS_ERROR_ILLEGAL_FUNCTION
+            || !self.response_required
+        {
+            return Ok(());
+        }
+        let read_code = self.buf[self.frame_start + 3];
+        #[allow(clippy::cast_possible_truncation)]
+        let object_id = self.reg as u8;
+        let max_id = match read_code {
+            MODBUS_DEVICE_ID_READ_BASIC => MODBUS_DEVICE_ID_MAJOR_MINOR_REVISION,
+            MODBUS_DEVICE_ID_READ_REGULAR => 0x7f,
+            _ => 0xff,
+        };
+        if read_code == MODBUS_DEVICE_ID_READ_INDIVIDUAL
+            && device_id.get_object(object_id).is_none()
+        {
+            self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+            return Ok(());
+        }
+        self.error = 0;
+        tcp_response_set_data_len!(self, 0);
+        // 2b unit and func, 1b MEI type, 1b read code, 1b conformity level
+        self.response
+            .extend(&self.buf[self.frame_start..self.frame_start + 4])?;
+        self.response.push(device_id.conformity_level())?;
+        let header_pos = self.response.len();
+        // 1b more follows, 1b next object id, 1b number of objects
+        self.response.extend(&[0, 0, 0])?;
+        let start_id = if read_code == MODBUS_DEVICE_ID_READ_INDIVIDUAL
+            || (object_id <= max_id && device_id.get_object(object_id).is_some())
+        {
+            object_id
+        } else {
+            0
+        };
+        // max PDU length - 7b header
+        let mut space = 246;
+        let mut count = 0;
+        for (id, value) in device_id.objects() {
+            if *id < start_id || *id > max_id {
+                continue;
+            }
+            if value.len() + 2 > space {
+                self.response.replace(header_pos, 0xff);
+                self.response.replace(header_pos + 1, *id);
+                break;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            self.response.extend(&[*id, value.len() as u8])?;
+            self.response.extend(value)?;
+            space -= value.len() + 2;
+            count += 1;
+            if read_code == MODBUS_DEVICE_ID_READ_INDIVIDUAL {
+                break;
+            }
+        }
+        self.response.replace(header_pos + 2, count);
+        if self.proto.has_mbap_header() {
+            let len_buf = u16::try_from(self.response.len() - 6)?.to_be_bytes();
+            self.response.replace(4, len_buf[0]);
+            self.response.replace(5, len_buf[1]);
+        }
+        Ok(())
+    }
+
+    fn check_frame_crc(&self, len: u8) -> Result<(), ErrorKind> {
+        match self.proto {
+            ModbusProto::Tcp | ModbusProto::Udp => {
+                // no checksum, the data (unit id and PDU) must fit into the MBAP length
+                if u16::from(len) <= u16::from_be_bytes([self.buf[4], self.buf[5]]) {
+                    Ok(())
+                } else {
+                    Err(ErrorKind::FrameBroken)
+                }
+            }
+            ModbusProto::Rtu | ModbusProto::RtuOverTcp => {
+                if self.buf.len() < usize::from(len) + 2 {
+                    return Err(ErrorKind::FrameBroken);
+                }
+                if calc_crc16(self.buf, len)
+                    == u16::from_le_bytes([self.buf[len as usize], self.buf[len as usize + 1]])
+                {
+                    Ok(())
+                } else {
+                    Err(ErrorKind::FrameCRCError)
+                }
+            }
+            ModbusProto::Ascii => {
+                if self.buf.len() <= usize::from(len) {
+                    return Err(ErrorKind::FrameBroken);
+                }
+                if calc_lrc(self.buf, len) == self.buf[len as usize] {
+                    Ok(())
+                } else {
+                    Err(ErrorKind::FrameLRCError)
+                }
+            }
+        }
+    }
+
     /// Parse frame buffer
-    #[allow(clippy::too_many_lines)]
+    ///
+    /// Read and write multiple requests with zero count (or count above the function limit) get
+    /// Illegal Data Value exception responses
     pub fn parse(&mut self) -> Result<(), ErrorKind> {
-        if self.proto == ModbusProto::TcpUdp {
-            //let tr_id = u16::from_be_bytes([self.buf[0], self.buf[1]]);
+        let result = self.parse_frame();
+        if result.is_ok() && self.func != 0 {
+            if let Some(ref mut observer) = self.observer {
+                observer.0.on_request(self.func, self.reg, self.count);
+            }
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn parse_frame(&mut self) -> Result<(), ErrorKind> {
+        if self.proto.has_mbap_header() {
+            if self.buf.len() < 8 {
+                return Err(ErrorKind::FrameBroken);
+            }
+            let tr_id = u16::from_be_bytes([self.buf[0], self.buf[1]]);
             let proto_id = u16::from_be_bytes([self.buf[2], self.buf[3]]);
             let length = u16::from_be_bytes([self.buf[4], self.buf[5]]);
-            if proto_id != 0 || !(6..=250).contains(&length) {
+            // unit id and the fixed part of the PDU, which is read before the data length is
+            // known
+            let min_length = match self.buf[7] {
+                MODBUS_READ_EXCEPTION_STATUS
+                | MODBUS_GET_COMM_EVENT_COUNTER
+                | MODBUS_REPORT_SERVER_ID => 2,
+                MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT => 5,
+                MODBUS_SET_COILS_BULK | MODBUS_SET_HOLDINGS_BULK => 7,
+                MODBUS_MASK_WRITE_REGISTER => 8,
+                MODBUS_READ_WRITE_MULTIPLE_REGISTERS => 11,
+                _ => 6,
+            };
+            if proto_id != 0 || !(min_length..=250).contains(&length) {
+                return Err(ErrorKind::FrameBroken);
+            }
+            let frame_len = length as usize + 6;
+            // UDP datagrams must contain exactly one frame
+            if self.buf.len() < frame_len
+                || (self.proto == ModbusProto::Udp && self.buf.len() != frame_len)
+            {
                 return Err(ErrorKind::FrameBroken);
             }
+            self.transaction_id = Some(tr_id);
             self.frame_start = 6;
         }
         if self.frame_start >= self.buf.len() {
@@ -536,27 +1457,21 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
         }
         let unit = self.buf[self.frame_start];
         let broadcast = unit == 0 || unit == 255; // some clients send broadcast to 0xff
-        if !broadcast && unit != self.unit_id {
-            return Ok(());
+        self.was_broadcast = broadcast;
+        if !broadcast {
+            if !self.accepts_unit(unit) {
+                return Ok(());
+            }
+            self.unit_id = unit;
+        }
+        if let Some(ref mut counters) = self.comm_counters {
+            counters.message_count = counters.message_count.wrapping_add(1);
         }
-        if !broadcast && self.proto == ModbusProto::TcpUdp {
+        if !broadcast && self.proto.has_mbap_header() {
             // copy 4 bytes: tr id and proto
             self.response.extend(&self.buf[0..4])?;
         }
         self.func = self.buf[self.frame_start + 1];
-        macro_rules! check_frame_crc {
-            ($len:expr) => {
-                self.proto == ModbusProto::TcpUdp
-                    || (self.proto == ModbusProto::Rtu
-                        && calc_crc16(self.buf, $len)
-                            == u16::from_le_bytes([
-                                self.buf[$len as usize],
-                                self.buf[$len as usize + 1],
-                            ]))
-                    || (self.proto == ModbusProto::Ascii
-                        && calc_lrc(self.buf, $len) == self.buf[$len as usize])
-            };
-        }
         match self.func {
             MODBUS_GET_COILS | MODBUS_GET_DISCRETES => {
                 // funcs 1 - 2
@@ -564,15 +1479,13 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                 if broadcast {
                     return Ok(());
                 }
-                if !check_frame_crc!(6) {
-                    return Err(ErrorKind::FrameCRCError);
-                }
+                self.check_frame_crc(6)?;
                 self.response_required = true;
                 self.count = u16::from_be_bytes([
                     self.buf[self.frame_start + 4],
                     self.buf[self.frame_start + 5],
                 ]);
-                if self.count > 2000 {
+                if self.count == 0 || self.count > 2000 {
                     self.error = MODBUS_ERROR_ILLEGAL_DATA_VALUE;
                     return Ok(());
                 }
@@ -589,31 +1502,31 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                 if broadcast {
                     return Ok(());
                 }
-                if !check_frame_crc!(6) {
-                    return Err(ErrorKind::FrameCRCError);
-                }
+                self.check_frame_crc(6)?;
                 self.response_required = true;
                 self.count = u16::from_be_bytes([
                     self.buf[self.frame_start + 4],
                     self.buf[self.frame_start + 5],
                 ]);
-                if self.count > 125 {
+                if self.count == 0 || self.count > if self.enron { 62 } else { 125 } {
                     self.error = MODBUS_ERROR_ILLEGAL_DATA_VALUE;
                     return Ok(());
                 }
-                self.processing_required = true;
                 self.reg = u16::from_be_bytes([
                     self.buf[self.frame_start + 2],
                     self.buf[self.frame_start + 3],
                 ]);
+                if self.misaligned(self.reg, self.read_words()) {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+                    return Ok(());
+                }
+                self.processing_required = true;
                 Ok(())
             }
             MODBUS_SET_COIL | MODBUS_SET_HOLDING => {
                 // func 5 / 6
                 // write single coil / register
-                if !check_frame_crc!(6) {
-                    return Err(ErrorKind::FrameCRCError);
-                }
+                self.check_frame_crc(6)?;
                 if !broadcast {
                     self.response_required = true;
                 }
@@ -630,9 +1543,9 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                 // funcs 15 & 16
                 // write multiple coils / registers
                 let bytes = self.buf[self.frame_start + 6];
-                if !check_frame_crc!(7 + bytes) {
-                    return Err(ErrorKind::FrameCRCError);
-                }
+                let len =
+                    u8::try_from(7 + usize::from(bytes)).map_err(|_| ErrorKind::FrameBroken)?;
+                self.check_frame_crc(len)?;
                 if !broadcast {
                     self.response_required = true;
                 }
@@ -645,28 +1558,183 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
                 } else {
                     123
                 };
-                if self.count > max_count {
+                if self.count == 0 || self.count > max_count {
                     self.error = MODBUS_ERROR_ILLEGAL_DATA_VALUE;
                     return Ok(());
                 }
-                if bytes > 246 {
+                // the byte count must match the count, otherwise a different number of
+                // coils / registers would be written
+                let expected_bytes = if self.func == MODBUS_SET_COILS_BULK {
+                    (usize::from(self.count) + 7) / 8
+                } else {
+                    usize::from(self.count) * 2
+                };
+                if usize::from(bytes) != expected_bytes {
                     self.error = MODBUS_ERROR_ILLEGAL_DATA_VALUE;
                     return Ok(());
                 }
+                // func, reg, count, byte count and data
+                if bytes > 246 || usize::from(bytes) + 6 > self.max_pdu {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_VALUE;
+                    return Ok(());
+                }
+                self.reg = u16::from_be_bytes([
+                    self.buf[self.frame_start + 2],
+                    self.buf[self.frame_start + 3],
+                ]);
+                if self.func == MODBUS_SET_HOLDINGS_BULK && self.misaligned(self.reg, self.count) {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+                    return Ok(());
+                }
+                self.processing_required = true;
+                self.readonly = false;
+                Ok(())
+            }
+            MODBUS_MASK_WRITE_REGISTER => {
+                // func 22
+                // mask write register
+                self.check_frame_crc(8)?;
+                if !broadcast {
+                    self.response_required = true;
+                }
+                self.count = 1;
                 self.processing_required = true;
                 self.readonly = false;
                 self.reg = u16::from_be_bytes([
                     self.buf[self.frame_start + 2],
                     self.buf[self.frame_start + 3],
                 ]);
+                Ok(())
+            }
+            MODBUS_READ_WRITE_MULTIPLE_REGISTERS => {
+                // func 23
+                // write multiple registers, then read multiple registers
+                if broadcast {
+                    return Ok(());
+                }
+                let bytes = self.buf[self.frame_start + 10];
+                let len =
+                    u8::try_from(11 + usize::from(bytes)).map_err(|_| ErrorKind::FrameBroken)?;
+                self.check_frame_crc(len)?;
+                self.response_required = true;
                 self.count = u16::from_be_bytes([
                     self.buf[self.frame_start + 4],
                     self.buf[self.frame_start + 5],
                 ]);
+                let write_count = u16::from_be_bytes([
+                    self.buf[self.frame_start + 8],
+                    self.buf[self.frame_start + 9],
+                ]);
+                if self.count == 0
+                    || self.count > 125
+                    || write_count == 0
+                    || write_count > 121
+                    || u16::from(bytes) != write_count << 1
+                    || usize::from(bytes) + 10 > self.max_pdu
+                {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_VALUE;
+                    return Ok(());
+                }
+                self.reg = u16::from_be_bytes([
+                    self.buf[self.frame_start + 2],
+                    self.buf[self.frame_start + 3],
+                ]);
+                let write_reg = u16::from_be_bytes([
+                    self.buf[self.frame_start + 6],
+                    self.buf[self.frame_start + 7],
+                ]);
+                if self.misaligned(self.reg, self.count) || self.misaligned(write_reg, write_count)
+                {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_ADDRESS;
+                    return Ok(());
+                }
+                self.processing_required = true;
+                self.readonly = false;
+                Ok(())
+            }
+            MODBUS_READ_EXCEPTION_STATUS => {
+                // func 7
+                // read exception status
+                if broadcast {
+                    return Ok(());
+                }
+                self.check_frame_crc(2)?;
+                self.response_required = true;
+                // unsupported until processed with process_exception_status
+                self.error = MODBUS_ERROR_ILLEGAL_FUNCTION;
+                Ok(())
+            }
+            MODBUS_GET_COMM_EVENT_COUNTER => {
+                // func 11
+                // get comm event counter
+                if broadcast {
+                    return Ok(());
+                }
+                self.check_frame_crc(2)?;
+                self.response_required = true;
+                if self.comm_counters.is_some() {
+                    self.processing_required = true;
+                } else {
+                    self.error = MODBUS_ERROR_ILLEGAL_FUNCTION;
+                }
+                Ok(())
+            }
+            MODBUS_DIAGNOSTICS => {
+                // func 8
+                // diagnostics
+                self.check_frame_crc(6)?;
+                if broadcast {
+                    return Ok(());
+                }
+                self.response_required = true;
+                self.reg = u16::from_be_bytes([
+                    self.buf[self.frame_start + 2],
+                    self.buf[self.frame_start + 3],
+                ]);
+                if self.reg == MODBUS_DIAG_RETURN_QUERY_DATA {
+                    self.processing_required = true;
+                } else {
+                    // unsupported until processed with process_diagnostics
+                    self.error = MODBUS_ERROR_ILLEGAL_FUNCTION;
+                }
+                Ok(())
+            }
+            MODBUS_REPORT_SERVER_ID => {
+                // func 17
+                // report server id
+                if broadcast {
+                    return Ok(());
+                }
+                self.check_frame_crc(2)?;
+                self.response_required = true;
+                // unsupported until processed with process_report_server_id
+                self.error = MODBUS_ERROR_ILLEGAL_FUNCTION;
+                Ok(())
+            }
+            MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT
+                if self.buf[self.frame_start + 2] == MODBUS_MEI_READ_DEVICE_ID =>
+            {
+                // func 43, MEI type 14
+                // read device identification, other MEI types are processed as custom
+                if broadcast {
+                    return Ok(());
+                }
+                self.check_frame_crc(5)?;
+                self.response_required = true;
+                if !(MODBUS_DEVICE_ID_READ_BASIC..=MODBUS_DEVICE_ID_READ_INDIVIDUAL)
+                    .contains(&self.buf[self.frame_start + 3])
+                {
+                    self.error = MODBUS_ERROR_ILLEGAL_DATA_VALUE;
+                    return Ok(());
+                }
+                self.reg = u16::from(self.buf[self.frame_start + 4]);
+                // unsupported until processed with process_read_device_id
+                self.error = MODBUS_ERROR_ILLEGAL_FUNCTION;
                 Ok(())
             }
             _ => {
-                // function unsupported
+                // function unsupported, can be processed with process_custom
+                self.custom = true;
                 if !broadcast {
                     self.response_required = true;
                     self.error = MODBUS_ERROR_ILLEGAL_FUNCTION;
@@ -680,18 +1748,110 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
     ///
     /// Returns None if no fields will be changed.
     pub fn changes(&self) -> Option<Changes> {
+        if self.writes_inputs() {
+            return None;
+        }
         let reg = self.reg;
         let count = self.count;
 
         Some(match self.func {
             MODBUS_SET_COIL => Changes::Coils { reg, count: 1 },
             MODBUS_SET_COILS_BULK => Changes::Coils { reg, count },
-            MODBUS_SET_HOLDING => Changes::Holdings { reg, count: 1 },
+            MODBUS_SET_HOLDING | MODBUS_MASK_WRITE_REGISTER => Changes::Holdings { reg, count: 1 },
             MODBUS_SET_HOLDINGS_BULK => Changes::Holdings { reg, count },
+            MODBUS_READ_WRITE_MULTIPLE_REGISTERS => Changes::Holdings {
+                reg: u16::from_be_bytes([
+                    self.buf[self.frame_start + 6],
+                    self.buf[self.frame_start + 7],
+                ]),
+                count: u16::from_be_bytes([
+                    self.buf[self.frame_start + 8],
+                    self.buf[self.frame_start + 9],
+                ]),
+            },
             _ => return None,
         })
     }
 
+    /// After [`parse`](ModbusFrame::parse): the register kind, the first register and the count
+    /// a write function is going to modify (for read/write multiple registers: the write range),
+    /// e.g. for a policy layer to veto writes to protected registers with
+    /// [`set_modbus_error_if_unset`](ModbusFrame::set_modbus_error_if_unset) before
+    /// [`process_write`](ModbusFrame::process_write) is called
+    ///
+    /// Returns `None` for read functions and frames which do not require processing
+    pub fn write_target(&self) -> Option<(RegKind, u16, u16)> {
+        if self.readonly || !self.processing_required || self.error != 0 {
+            return None;
+        }
+        let holding = if self.writes_inputs() {
+            RegKind::Input
+        } else {
+            RegKind::Holding
+        };
+        match self.func {
+            MODBUS_SET_COIL => Some((RegKind::Coil, self.reg, 1)),
+            MODBUS_SET_COILS_BULK => Some((RegKind::Coil, self.reg, self.count)),
+            MODBUS_SET_HOLDING | MODBUS_MASK_WRITE_REGISTER => Some((holding, self.reg, 1)),
+            MODBUS_SET_HOLDINGS_BULK => Some((holding, self.reg, self.count)),
+            MODBUS_READ_WRITE_MULTIPLE_REGISTERS => Some((
+                RegKind::Holding,
+                u16::from_be_bytes([
+                    self.buf[self.frame_start + 6],
+                    self.buf[self.frame_start + 7],
+                ]),
+                u16::from_be_bytes([
+                    self.buf[self.frame_start + 8],
+                    self.buf[self.frame_start + 9],
+                ]),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Decodes the write payload of a parsed frame into (address, new value) pairs
+    ///
+    /// Supported for functions 5, 6, 15, 16 and 23, for other functions (including mask write
+    /// register, as its result depends on the current value) and frames with errors, the iterator
+    /// is empty. The context is not required.
+    pub fn changed_values(&self) -> ChangedValues<'a> {
+        let fs = self.frame_start;
+        let buf = self.buf;
+        let (reg, count, data) = match self.func {
+            _ if self.error != 0 || !self.processing_required || self.writes_inputs() => {
+                (0, 0, ChangedData::Bits(&[]))
+            }
+            MODBUS_SET_COIL => (
+                self.reg,
+                1,
+                ChangedData::Bits(if buf[fs + 4] == 0xff { &[1] } else { &[0] }),
+            ),
+            MODBUS_SET_HOLDING => (self.reg, 1, ChangedData::Words(&buf[fs + 4..fs + 6])),
+            MODBUS_SET_COILS_BULK => (
+                self.reg,
+                self.count,
+                ChangedData::Bits(&buf[fs + 7..fs + 7 + buf[fs + 6] as usize]),
+            ),
+            MODBUS_SET_HOLDINGS_BULK => (
+                self.reg,
+                self.count,
+                ChangedData::Words(&buf[fs + 7..fs + 7 + buf[fs + 6] as usize]),
+            ),
+            MODBUS_READ_WRITE_MULTIPLE_REGISTERS => (
+                u16::from_be_bytes([buf[fs + 6], buf[fs + 7]]),
+                u16::from_be_bytes([buf[fs + 8], buf[fs + 9]]),
+                ChangedData::Words(&buf[fs + 11..fs + 11 + buf[fs + 10] as usize]),
+            ),
+            _ => (0, 0, ChangedData::Bits(&[])),
+        };
+        ChangedValues {
+            reg,
+            count,
+            pos: 0,
+            data,
+        }
+    }
+
     /// If the error field on the [`ModbusFrame`] isn't already set this function will set it and
     /// resize the response buffer to what's expected by [`ModbusFrame::finalize_response`]
     ///
@@ -701,11 +1861,7 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
     pub fn set_modbus_error_if_unset(&mut self, err: &ErrorKind) -> Result<(), ErrorKind> {
         if self.error == 0 && err.is_modbus_error() {
             // leave 0 bytes for RTU/ASCII, leave 4 bytes for TCP/UDP (Transaction ID and Protocol ID)
-            let len_leave_before_finalize = if self.proto == ModbusProto::TcpUdp {
-                4
-            } else {
-                0
-            };
+            let len_leave_before_finalize = if self.proto.has_mbap_header() { 4 } else { 0 };
 
             self.response.resize(len_leave_before_finalize, 0)?;
             self.error = err
@@ -716,15 +1872,233 @@ impl<'a, V: VectorTrait<u8>> ModbusFrame<'a, V> {
     }
 }
 
+/// Reads registers into bits (coils, discretes) or words (inputs, holdings) range
+#[cfg(feature = "async")]
+async fn read_async<C: async_context::AsyncModbusContext + ?Sized>(
+    ctx: &C,
+    kind: ReadKind,
+    reg: u16,
+    bits: &mut [bool],
+    words: &mut [u16],
+    range: core::ops::Range<usize>,
+) -> Result<(), ErrorKind> {
+    match kind {
+        ReadKind::Coils => ctx.get_coils_into(reg, &mut bits[range]).await,
+        ReadKind::Discretes => ctx.get_discretes_into(reg, &mut bits[range]).await,
+        ReadKind::Inputs => ctx.get_inputs_into(reg, &mut words[range]).await,
+        ReadKind::Holdings => ctx.get_holdings_into(reg, &mut words[range]).await,
+    }
+}
+
+/// Out-of-context registers are reported to the client as Illegal Data Address
+#[cfg(feature = "async")]
+fn async_context_error(e: ErrorKind) -> ErrorKind {
+    if e == ErrorKind::OOBContext {
+        ErrorKind::IllegalDataAddress
+    } else {
+        e
+    }
+}
+
+// must be updated together with ModbusFrame::parse_frame, which classifies functions as read
+// (readonly) or write, and with test_std_supported_functions_classified
+const SUPPORTED_FUNCTIONS: &[u8] = &[
+    MODBUS_GET_COILS,
+    MODBUS_GET_DISCRETES,
+    MODBUS_GET_HOLDINGS,
+    MODBUS_GET_INPUTS,
+    MODBUS_SET_COIL,
+    MODBUS_SET_HOLDING,
+    MODBUS_READ_EXCEPTION_STATUS,
+    MODBUS_DIAGNOSTICS,
+    MODBUS_GET_COMM_EVENT_COUNTER,
+    MODBUS_SET_COILS_BULK,
+    MODBUS_SET_HOLDINGS_BULK,
+    MODBUS_REPORT_SERVER_ID,
+    MODBUS_MASK_WRITE_REGISTER,
+    MODBUS_READ_WRITE_MULTIPLE_REGISTERS,
+    MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT,
+];
+
+/// Function codes, handled by [`ModbusFrame::parse`], in ascending order
+///
+/// Other functions are reported with [`ModbusFrame::unsupported_function`] and can be processed
+/// with [`ModbusFrame::process_custom`]. For function 0x2B, only Read Device Identification
+/// (MEI type 0x0E) is supported.
+pub fn supported_functions() -> &'static [u8] {
+    SUPPORTED_FUNCTIONS
+}
+
+/// Parse a frame, process it with the context and finalize the response in a single call
+///
+/// The response vector is cleared and contains the response to send (if response_required is
+/// set in the outcome). Frames for other unit ids are ignored (nothing is sent). Functions, which
+/// require additional processing (e.g. [`process_custom`](ModbusFrame::process_custom)), get
+/// IllegalFunction error, use [`ModbusFrame`] directly to process them.
+///
+/// ```
+/// use rmodbus::{server::{process_frame_full, storage::ModbusStorageSmall, Changes}, ModbusProto};
+///
+/// let mut ctx = ModbusStorageSmall::new();
+/// let mut response = Vec::new();
+/// // set holding 1 to 0x1234
+/// let request = [0, 1, 0, 0, 0, 6, 1, 6, 0, 1, 0x12, 0x34];
+/// let outcome =
+///     process_frame_full(1, &request, ModbusProto::Tcp, &mut ctx, &mut response).unwrap();
+/// assert!(outcome.response_required);
+/// assert_eq!(outcome.changes, Some(Changes::Holdings { reg: 1, count: 1 }));
+/// assert_eq!(response, request);
+/// ```
+pub fn process_frame_full<C: context::ModbusContext, V: VectorTrait<u8>>(
+    unit_id: u8,
+    buf: &[u8],
+    proto: ModbusProto,
+    ctx: &mut C,
+    response: &mut V,
+) -> Result<FrameOutcome, ErrorKind> {
+    let mut frame = ModbusFrame::new(unit_id, buf, proto, response);
+    frame.parse()?;
+    let mut changes = None;
+    if frame.processing_required {
+        if frame.readonly {
+            frame.process_read(ctx)?;
+        } else {
+            frame.process_write(ctx)?;
+            if frame.error == 0 {
+                changes = frame.changes();
+            }
+        }
+    }
+    if frame.response_required {
+        frame.finalize_response()?;
+    }
+    Ok(FrameOutcome {
+        response_required: frame.response_required,
+        changes,
+    })
+}
+
+/// Parse a frame without a response buffer, e.g. to route or filter frames before processing
+///
+/// The frame is parsed the same way as with [`ModbusFrame::parse`] for a server with the unit id.
+/// Frames for other unit ids are not parsed (func is zero), the unit id the frame is addressed
+/// to is returned in any case.
+///
+/// ```
+/// use rmodbus::{server::inspect, ModbusProto};
+///
+/// let request = [0, 1, 0, 0, 0, 6, 1, 3, 0, 10, 0, 2];
+/// let info = inspect(1, &request, ModbusProto::Tcp).unwrap();
+/// assert_eq!((info.func, info.reg, info.count), (3, 10, 2));
+/// assert!(info.readonly);
+/// ```
+pub fn inspect(unit_id: u8, buf: &[u8], proto: ModbusProto) -> Result<FrameInfo, ErrorKind> {
+    // parse copies MBAP transaction and protocol ids only
+    let mut header = [0u8; 4];
+    let mut response = SliceWriter::new(&mut header);
+    let mut frame = ModbusFrame::new(unit_id, buf, proto, &mut response);
+    frame.parse()?;
+    Ok(FrameInfo {
+        unit_id: buf[frame.frame_start],
+        func: frame.func,
+        reg: frame.reg,
+        count: frame.count,
+        readonly: frame.readonly,
+        is_broadcast: frame.was_broadcast,
+    })
+}
+
+/// See [`process_frame_full`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameOutcome {
+    /// the response must be sent
+    pub response_required: bool,
+    /// registers changed by a write function (None for reads and failed writes)
+    pub changes: Option<Changes>,
+}
+
+/// See [`inspect`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameInfo {
+    /// the unit id the frame is addressed to
+    pub unit_id: u8,
+    pub func: u8,
+    /// see [`ModbusFrame`] fields
+    pub reg: u16,
+    /// see [`ModbusFrame`] fields
+    pub count: u16,
+    pub readonly: bool,
+    /// unit id 0 or 255
+    pub is_broadcast: bool,
+}
+
 /// See [`ModbusFrame::changes`]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Changes {
     Coils { reg: u16, count: u16 },
     Holdings { reg: u16, count: u16 },
 }
 
+/// See [`ModbusFrame::write_target`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RegKind {
+    Coil,
+    Holding,
+    /// write multiple registers within the [input window](ModbusFrame::allow_input_writes)
+    Input,
+}
+
+/// See [`ModbusFrame::changed_values`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RegValue {
+    Coil(bool),
+    Holding(u16),
+}
+
+#[derive(Debug, Clone)]
+enum ChangedData<'a> {
+    Bits(&'a [u8]),
+    Words(&'a [u8]),
+}
+
+/// See [`ModbusFrame::changed_values`]
+#[derive(Debug, Clone)]
+pub struct ChangedValues<'a> {
+    reg: u16,
+    count: u16,
+    pos: u16,
+    data: ChangedData<'a>,
+}
+
+impl Iterator for ChangedValues<'_> {
+    type Item = (u16, RegValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.count {
+            return None;
+        }
+        let pos = self.pos as usize;
+        let value = match self.data {
+            ChangedData::Bits(data) => RegValue::Coil(data.get(pos / 8)? >> (pos % 8) & 1 == 1),
+            ChangedData::Words(data) => RegValue::Holding(u16::from_be_bytes([
+                *data.get(pos * 2)?,
+                *data.get(pos * 2 + 1)?,
+            ])),
+        };
+        let item = (self.reg.wrapping_add(self.pos), value);
+        self.pos += 1;
+        Some(item)
+    }
+}
+
 /// See [`get_external_write`](ModbusFrame::get_external_write)
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct WriteBits<'a> {
     pub address: u16,
     pub count: u16,
@@ -733,6 +2107,7 @@ pub struct WriteBits<'a> {
 
 /// See [`get_external_write`](ModbusFrame::get_external_write)
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct WriteWords<'a> {
     pub address: u16,
     pub count: u16,
@@ -741,6 +2116,7 @@ pub struct WriteWords<'a> {
 
 /// See [`get_external_write`](ModbusFrame::get_external_write)
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Write<'a> {
     Bits(WriteBits<'a>),
     Words(WriteWords<'a>),
@@ -748,6 +2124,7 @@ pub enum Write<'a> {
 
 /// See [`get_external_read`](ModbusFrame::get_external_read)
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadBits<'a> {
     pub address: u16,
     pub count: u16,
@@ -756,14 +2133,35 @@ pub struct ReadBits<'a> {
 
 /// See [`get_external_read`](ModbusFrame::get_external_read)
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadWords<'a> {
     pub address: u16,
     pub count: u16,
     pub buf: &'a mut [u8],
 }
 
+/// See [`read_request`](ModbusFrame::read_request)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadKind {
+    Coils,
+    Discretes,
+    Inputs,
+    Holdings,
+}
+
+/// See [`read_request`](ModbusFrame::read_request)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadRequest {
+    pub kind: ReadKind,
+    pub address: u16,
+    pub count: u16,
+}
+
 /// See [`get_external_read`](ModbusFrame::get_external_read)
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Read<'a> {
     Bits(ReadBits<'a>),
     Words(ReadWords<'a>),
@@ -0,0 +1,352 @@
+use super::{
+    super::{ErrorKind, VectorTrait},
+    context::ModbusContext,
+};
+
+/// Context wrapper, which maps logical register addresses to the inner context indexes
+///
+/// A register address of a bank is translated to `reg - base` before it is passed to the inner
+/// context, addresses below the bank base are out of the context. E.g. with
+/// [`holdings_base(40001)`](OffsetStorage::holdings_base), `set_holding(40001, ..)` sets the
+/// inner holding 0. All bases are 0 by default.
+///
+/// ```rust
+/// use rmodbus::{
+///     server::{context::ModbusContext, offset::OffsetStorage, storage::ModbusStorageSmall},
+///     ErrorKind,
+/// };
+///
+/// let mut ctx = OffsetStorage::new(ModbusStorageSmall::new()).holdings_base(40001);
+/// ctx.set_holding(40001, 123).unwrap();
+/// assert_eq!(ctx.inner().holdings[0], 123);
+/// assert_eq!(ctx.get_holding(40000), Err(ErrorKind::OOBContext));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OffsetStorage<C> {
+    inner: C,
+    coils_base: u16,
+    discretes_base: u16,
+    inputs_base: u16,
+    holdings_base: u16,
+}
+
+#[inline]
+fn translate(reg: u16, base: u16) -> Result<u16, ErrorKind> {
+    reg.checked_sub(base).ok_or(ErrorKind::OOBContext)
+}
+
+impl<C: ModbusContext> OffsetStorage<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            coils_base: 0,
+            discretes_base: 0,
+            inputs_base: 0,
+            holdings_base: 0,
+        }
+    }
+    /// Logical address of the inner coil 0
+    pub fn coils_base(mut self, base: u16) -> Self {
+        self.coils_base = base;
+        self
+    }
+    /// Logical address of the inner discrete 0
+    pub fn discretes_base(mut self, base: u16) -> Self {
+        self.discretes_base = base;
+        self
+    }
+    /// Logical address of the inner input 0
+    pub fn inputs_base(mut self, base: u16) -> Self {
+        self.inputs_base = base;
+        self
+    }
+    /// Logical address of the inner holding 0
+    pub fn holdings_base(mut self, base: u16) -> Self {
+        self.holdings_base = base;
+        self
+    }
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C: ModbusContext> ModbusContext for OffsetStorage<C> {
+    fn get_inputs_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_inputs_as_u8(translate(reg, self.inputs_base)?, count, result)
+    }
+
+    fn get_holdings_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_holdings_as_u8(translate(reg, self.holdings_base)?, count, result)
+    }
+
+    fn set_inputs_from_u8(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_inputs_from_u8(translate(reg, self.inputs_base)?, values)
+    }
+
+    fn set_holdings_from_u8(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_holdings_from_u8(translate(reg, self.holdings_base)?, values)
+    }
+
+    fn get_coils_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_coils_as_u8(translate(reg, self.coils_base)?, count, result)
+    }
+
+    fn get_coils_as_u8_bytes<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_coils_as_u8_bytes(translate(reg, self.coils_base)?, count, result)
+    }
+
+    fn get_discretes_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_discretes_as_u8(translate(reg, self.discretes_base)?, count, result)
+    }
+
+    fn get_discretes_as_u8_bytes<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_discretes_as_u8_bytes(translate(reg, self.discretes_base)?, count, result)
+    }
+
+    fn set_coils_from_u8(&mut self, reg: u16, count: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_coils_from_u8(translate(reg, self.coils_base)?, count, values)
+    }
+
+    fn set_discretes_from_u8(
+        &mut self,
+        reg: u16,
+        count: u16,
+        values: &[u8],
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .set_discretes_from_u8(translate(reg, self.discretes_base)?, count, values)
+    }
+
+    fn set_coils_from_u8_bytes(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_coils_from_u8_bytes(translate(reg, self.coils_base)?, values)
+    }
+
+    fn set_discretes_from_u8_bytes(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_discretes_from_u8_bytes(translate(reg, self.discretes_base)?, values)
+    }
+
+    fn get_coils_bulk<V: VectorTrait<bool>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_coils_bulk(translate(reg, self.coils_base)?, count, result)
+    }
+
+    fn get_discretes_bulk<V: VectorTrait<bool>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_discretes_bulk(translate(reg, self.discretes_base)?, count, result)
+    }
+
+    fn get_inputs_bulk<V: VectorTrait<u16>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_inputs_bulk(translate(reg, self.inputs_base)?, count, result)
+    }
+
+    fn get_holdings_bulk<V: VectorTrait<u16>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        self.inner
+            .get_holdings_bulk(translate(reg, self.holdings_base)?, count, result)
+    }
+
+    fn get_coils_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        self.inner
+            .get_coils_into(translate(reg, self.coils_base)?, out)
+    }
+
+    fn get_discretes_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        self.inner
+            .get_discretes_into(translate(reg, self.discretes_base)?, out)
+    }
+
+    fn get_inputs_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        self.inner
+            .get_inputs_into(translate(reg, self.inputs_base)?, out)
+    }
+
+    fn get_holdings_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        self.inner
+            .get_holdings_into(translate(reg, self.holdings_base)?, out)
+    }
+
+    fn set_coils_bulk(&mut self, reg: u16, values: &[bool]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_coils_bulk(translate(reg, self.coils_base)?, values)
+    }
+
+    fn set_discretes_bulk(&mut self, reg: u16, values: &[bool]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_discretes_bulk(translate(reg, self.discretes_base)?, values)
+    }
+
+    fn set_inputs_bulk(&mut self, reg: u16, values: &[u16]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_inputs_bulk(translate(reg, self.inputs_base)?, values)
+    }
+
+    fn set_holdings_bulk(&mut self, reg: u16, values: &[u16]) -> Result<(), ErrorKind> {
+        self.inner
+            .set_holdings_bulk(translate(reg, self.holdings_base)?, values)
+    }
+
+    fn get_coil(&self, reg: u16) -> Result<bool, ErrorKind> {
+        self.inner.get_coil(translate(reg, self.coils_base)?)
+    }
+
+    fn get_discrete(&self, reg: u16) -> Result<bool, ErrorKind> {
+        self.inner
+            .get_discrete(translate(reg, self.discretes_base)?)
+    }
+
+    fn get_input(&self, reg: u16) -> Result<u16, ErrorKind> {
+        self.inner.get_input(translate(reg, self.inputs_base)?)
+    }
+
+    fn get_holding(&self, reg: u16) -> Result<u16, ErrorKind> {
+        self.inner.get_holding(translate(reg, self.holdings_base)?)
+    }
+
+    fn set_coil(&mut self, reg: u16, value: bool) -> Result<(), ErrorKind> {
+        self.inner.set_coil(translate(reg, self.coils_base)?, value)
+    }
+
+    fn set_discrete(&mut self, reg: u16, value: bool) -> Result<(), ErrorKind> {
+        self.inner
+            .set_discrete(translate(reg, self.discretes_base)?, value)
+    }
+
+    fn set_input(&mut self, reg: u16, value: u16) -> Result<(), ErrorKind> {
+        self.inner
+            .set_input(translate(reg, self.inputs_base)?, value)
+    }
+
+    fn set_holding(&mut self, reg: u16, value: u16) -> Result<(), ErrorKind> {
+        self.inner
+            .set_holding(translate(reg, self.holdings_base)?, value)
+    }
+
+    fn get_inputs_as_u32(&self, reg: u16) -> Result<u32, ErrorKind> {
+        self.inner
+            .get_inputs_as_u32(translate(reg, self.inputs_base)?)
+    }
+
+    fn get_holdings_as_u32(&self, reg: u16) -> Result<u32, ErrorKind> {
+        self.inner
+            .get_holdings_as_u32(translate(reg, self.holdings_base)?)
+    }
+
+    fn set_inputs_from_u32(&mut self, reg: u16, value: u32) -> Result<(), ErrorKind> {
+        self.inner
+            .set_inputs_from_u32(translate(reg, self.inputs_base)?, value)
+    }
+
+    fn set_holdings_from_u32(&mut self, reg: u16, value: u32) -> Result<(), ErrorKind> {
+        self.inner
+            .set_holdings_from_u32(translate(reg, self.holdings_base)?, value)
+    }
+
+    fn get_inputs_as_u64(&self, reg: u16) -> Result<u64, ErrorKind> {
+        self.inner
+            .get_inputs_as_u64(translate(reg, self.inputs_base)?)
+    }
+
+    fn get_holdings_as_u64(&self, reg: u16) -> Result<u64, ErrorKind> {
+        self.inner
+            .get_holdings_as_u64(translate(reg, self.holdings_base)?)
+    }
+
+    fn set_inputs_from_u64(&mut self, reg: u16, value: u64) -> Result<(), ErrorKind> {
+        self.inner
+            .set_inputs_from_u64(translate(reg, self.inputs_base)?, value)
+    }
+
+    fn set_holdings_from_u64(&mut self, reg: u16, value: u64) -> Result<(), ErrorKind> {
+        self.inner
+            .set_holdings_from_u64(translate(reg, self.holdings_base)?, value)
+    }
+
+    fn get_inputs_as_f32(&self, reg: u16) -> Result<f32, ErrorKind> {
+        self.inner
+            .get_inputs_as_f32(translate(reg, self.inputs_base)?)
+    }
+
+    fn get_holdings_as_f32(&self, reg: u16) -> Result<f32, ErrorKind> {
+        self.inner
+            .get_holdings_as_f32(translate(reg, self.holdings_base)?)
+    }
+
+    fn set_inputs_from_f32(&mut self, reg: u16, value: f32) -> Result<(), ErrorKind> {
+        self.inner
+            .set_inputs_from_f32(translate(reg, self.inputs_base)?, value)
+    }
+
+    fn set_holdings_from_f32(&mut self, reg: u16, value: f32) -> Result<(), ErrorKind> {
+        self.inner
+            .set_holdings_from_f32(translate(reg, self.holdings_base)?, value)
+    }
+}
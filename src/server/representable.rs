@@ -1,8 +1,15 @@
+use super::context::WordOrder;
+
 /// Implemented for structs that can be represented using u16 registers.
 /// It is highly recommended that implementors of this type ensure that
 /// [`RegisterRepresentable::to_registers_sequential`] and
 /// [`RegisterRepresentable::from_registers_sequential`] are exact
 /// inverses of each other.
+///
+/// Types which contain several multi-register fields should override
+/// [`RegisterRepresentable::to_registers_with_order`] and
+/// [`RegisterRepresentable::from_registers_with_order`] to apply the word order per field (see
+/// [`WordOrder::u32_to_registers`] and similar helpers).
 #[allow(clippy::module_name_repetitions)]
 pub trait RegisterRepresentable<const N: usize> {
     /// Convert this type into a sequence of `u16`s which can be loaded
@@ -11,6 +18,34 @@ pub trait RegisterRepresentable<const N: usize> {
     /// Extract this type from a sequence of `u16`s taken from sequential
     /// modbus registers. (From lower to higher addresses)
     fn from_registers_sequential(value: &[u16; N]) -> Self;
+    /// Convert this type into a sequence of `u16`s with the given word order
+    ///
+    /// The default implementation treats the type as a single value: big-endian order is
+    /// identical to [`RegisterRepresentable::to_registers_sequential`], for little-endian one
+    /// the registers are reversed.
+    fn to_registers_with_order(&self, order: WordOrder) -> [u16; N] {
+        let mut regs = self.to_registers_sequential();
+        if order == WordOrder::LittleEndian {
+            regs.reverse();
+        }
+        regs
+    }
+    /// Extract this type from a sequence of `u16`s with the given word order
+    ///
+    /// See [`RegisterRepresentable::to_registers_with_order`]
+    fn from_registers_with_order(value: &[u16; N], order: WordOrder) -> Self
+    where
+        Self: Sized,
+    {
+        match order {
+            WordOrder::BigEndian => Self::from_registers_sequential(value),
+            WordOrder::LittleEndian => {
+                let mut regs = *value;
+                regs.reverse();
+                Self::from_registers_sequential(&regs)
+            }
+        }
+    }
 }
 
 /// The other side of [`RegisterRepresentable`], similar to how the
@@ -74,6 +109,7 @@ pub mod representations {
     //! assert_eq!(to_be_stored, (read.0 << 16) | (read.0 >> 16));
     //! ```
     use super::RegisterRepresentable;
+    use crate::{ErrorKind, VectorTrait};
     #[cfg(feature = "with_bincode")]
     use bincode::{Decode, Encode};
     #[cfg(feature = "with_serde")]
@@ -162,10 +198,58 @@ pub mod representations {
         }
     }
 
+    /// Decode `count` records, each taking `N` registers, from a register table
+    ///
+    /// Returns [`ErrorKind::OOB`] if there are less than `count * N` registers
+    #[cfg(feature = "std")]
+    pub fn read_many<const N: usize, T: RegisterRepresentable<N>>(
+        regs: &[u16],
+        count: usize,
+    ) -> Result<Vec<T>, ErrorKind> {
+        let regs = regs
+            .get(..count.checked_mul(N).ok_or(ErrorKind::OOB)?)
+            .ok_or(ErrorKind::OOB)?;
+        Ok(regs
+            .chunks_exact(N)
+            .map(|chunk| {
+                let mut buf = [0; N];
+                buf.copy_from_slice(chunk);
+                T::from_registers_sequential(&buf)
+            })
+            .collect())
+    }
+
+    /// The same as [`read_many`], but decodes `out.len()` records into a slice
+    pub fn read_many_into<const N: usize, T: RegisterRepresentable<N>>(
+        regs: &[u16],
+        out: &mut [T],
+    ) -> Result<(), ErrorKind> {
+        let regs = regs.get(..out.len() * N).ok_or(ErrorKind::OOB)?;
+        for (chunk, value) in regs.chunks_exact(N).zip(out.iter_mut()) {
+            let mut buf = [0; N];
+            buf.copy_from_slice(chunk);
+            *value = T::from_registers_sequential(&buf);
+        }
+        Ok(())
+    }
+
+    /// Encode records into a register table, `N` registers per record
+    ///
+    /// Note: the vector is always appended
+    pub fn write_many<const N: usize, T: RegisterRepresentable<N>, V: VectorTrait<u16>>(
+        values: &[T],
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        for value in values {
+            result.extend(&value.to_registers_sequential())?;
+        }
+        Ok(())
+    }
+
     /// Tests specifically for the 4 representations provided
     #[cfg(test)]
     mod tests {
-        use super::super::RegisterBuffer;
+        use super::super::{RegisterBuffer, WordOrder};
         #[allow(clippy::wildcard_imports)]
         use super::*;
         #[test]
@@ -210,5 +294,119 @@ pub mod representations {
             assert_eq!(little_endian[2], 0x2222u16);
             assert_eq!(little_endian[3], 0x1111u16);
         }
+        #[test]
+        fn test_with_order() {
+            #[derive(Debug, PartialEq)]
+            struct Meter {
+                energy: u32,
+                status: u16,
+            }
+            impl RegisterRepresentable<3> for Meter {
+                fn to_registers_sequential(&self) -> [u16; 3] {
+                    self.to_registers_with_order(WordOrder::BigEndian)
+                }
+                fn from_registers_sequential(value: &[u16; 3]) -> Self {
+                    Self::from_registers_with_order(value, WordOrder::BigEndian)
+                }
+                fn to_registers_with_order(&self, order: WordOrder) -> [u16; 3] {
+                    let e = order.u32_to_registers(self.energy);
+                    [e[0], e[1], self.status]
+                }
+                fn from_registers_with_order(value: &[u16; 3], order: WordOrder) -> Self {
+                    Self {
+                        energy: order.u32_from_registers(&[value[0], value[1]]),
+                        status: value[2],
+                    }
+                }
+            }
+            let meter = Meter {
+                energy: 0x1111_2222,
+                status: 7,
+            };
+            let regs = meter.to_registers_sequential();
+            assert_eq!(regs, [0x1111, 0x2222, 7]);
+            assert_eq!(Meter::from_registers_sequential(&regs), meter);
+            let regs = meter.to_registers_with_order(WordOrder::LittleEndian);
+            assert_eq!(regs, [0x2222, 0x1111, 7]);
+            assert_eq!(
+                Meter::from_registers_with_order(&regs, WordOrder::LittleEndian),
+                meter
+            );
+            // the same registers decoded with the other order
+            assert_eq!(
+                Meter::from_registers_with_order(&regs, WordOrder::BigEndian).energy,
+                0x2222_1111
+            );
+            // the default implementation (single value)
+            let value = U32BigEndian(0x1111_2222);
+            let regs = value.to_registers_with_order(WordOrder::LittleEndian);
+            assert_eq!(regs, [0x2222, 0x1111]);
+            assert_eq!(
+                U32BigEndian::from_registers_with_order(&regs, WordOrder::LittleEndian),
+                value
+            );
+            assert_eq!(
+                value.to_registers_with_order(WordOrder::BigEndian),
+                value.to_registers_sequential()
+            );
+        }
+        #[test]
+        #[cfg(feature = "std")]
+        fn test_read_write_many() {
+            #[derive(Debug, Default, PartialEq)]
+            struct Sensor {
+                id: u16,
+                value: u32,
+            }
+            impl RegisterRepresentable<3> for Sensor {
+                fn to_registers_sequential(&self) -> [u16; 3] {
+                    let v = U32BigEndian(self.value).to_registers_sequential();
+                    [self.id, v[0], v[1]]
+                }
+                fn from_registers_sequential(value: &[u16; 3]) -> Self {
+                    Self {
+                        id: value[0],
+                        value: U32BigEndian::from_registers_sequential(&[value[1], value[2]]).0,
+                    }
+                }
+            }
+            let regs = [1, 0, 10, 2, 1, 0, 3, 0xffff, 0xffff];
+            let sensors: Vec<Sensor> = read_many(&regs, 3).unwrap();
+            assert_eq!(
+                sensors,
+                [
+                    Sensor { id: 1, value: 10 },
+                    Sensor {
+                        id: 2,
+                        value: 0x1_0000
+                    },
+                    Sensor {
+                        id: 3,
+                        value: u32::MAX
+                    }
+                ]
+            );
+            let mut out = [Sensor::default(), Sensor::default()];
+            read_many_into(&regs, &mut out).unwrap();
+            assert_eq!(out, sensors[..2]);
+            assert_eq!(
+                read_many::<3, Sensor>(&regs, 4).unwrap_err(),
+                ErrorKind::OOB
+            );
+            assert_eq!(
+                read_many::<3, Sensor>(&regs, usize::MAX).unwrap_err(),
+                ErrorKind::OOB
+            );
+            let mut out = [
+                Sensor::default(),
+                Sensor::default(),
+                Sensor::default(),
+                Sensor::default(),
+            ];
+            assert_eq!(read_many_into(&regs, &mut out), Err(ErrorKind::OOB));
+            let mut written = Vec::new();
+            write_many(&sensors, &mut written).unwrap();
+            assert_eq!(written, regs);
+        }
     }
 }
@@ -1,6 +1,7 @@
 use super::{
     super::{ErrorKind, VectorTrait},
     context::ModbusContext,
+    Changes,
 };
 #[cfg(feature = "with_bincode")]
 use bincode::{Decode, Encode};
@@ -20,7 +21,7 @@ pub type ModbusStorageFull =
 
 /// Contains standard Modbus register contexts
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "with_serde", derive(Deserialize, Serialize))]
 #[cfg_attr(feature = "with_bincode", derive(Decode, Encode))]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -118,15 +119,13 @@ macro_rules! set_bools_from_u8 {
         let reg_to = $reg as usize + $count as usize;
         if reg_to > $ctx_size {
             Err(ErrorKind::OOBContext)
+        } else if $count as usize > $values.len() * 8 {
+            Err(ErrorKind::OOB)
         } else {
             let mut creg = $reg as usize;
             let mut cbyte = 0;
             let mut cnt = 0;
-            let len = $values.len();
             while creg < reg_to && cnt < $count {
-                if cbyte >= len {
-                    return Err(ErrorKind::OOB);
-                }
                 let mut b: u8 = $values[cbyte];
                 for _ in 0..8 {
                     $reg_context[creg] = b & 1 == 1;
@@ -156,6 +155,18 @@ macro_rules! get_bulk {
     }};
 }
 
+macro_rules! get_into {
+    ($reg_context:expr, $reg:expr, $out:expr, $ctx_size: expr) => {{
+        let reg_to = $reg as usize + $out.len();
+        if reg_to > $ctx_size {
+            Err(ErrorKind::OOBContext)
+        } else {
+            $out.copy_from_slice(&$reg_context[$reg as usize..reg_to]);
+            Ok(())
+        }
+    }};
+}
+
 macro_rules! set_bulk {
     ($reg_context:expr, $reg:expr, $values:expr, $ctx_size: expr) => {
         if $reg as usize + $values.len() > $ctx_size {
@@ -283,6 +294,26 @@ impl<const C: usize, const D: usize, const I: usize, const H: usize> ModbusStora
         }
     }
 
+    /// Define a custom-sized context with all registers of each type set to the given value
+    ///
+    /// As the function is const, a pre-populated context can be placed in a static:
+    ///
+    /// ```
+    /// use rmodbus::server::storage::ModbusStorage;
+    ///
+    /// static DEFAULTS: ModbusStorage<16, 16, 16, 16> =
+    ///     ModbusStorage::with_defaults(false, false, 0, 0xffff);
+    /// ```
+    #[inline]
+    pub const fn with_defaults(coil: bool, discrete: bool, input: u16, holding: u16) -> Self {
+        Self {
+            coils: [coil; C],
+            discretes: [discrete; D],
+            inputs: [input; I],
+            holdings: [holding; H],
+        }
+    }
+
     pub fn clear_all(&mut self) {
         self.clear_coils();
         self.clear_discretes();
@@ -313,6 +344,139 @@ impl<const C: usize, const D: usize, const I: usize, const H: usize> ModbusStora
             self.holdings[i] = 0;
         }
     }
+
+    /// Clear `count` coils starting from `reg`
+    pub fn clear_coils_range(&mut self, reg: u16, count: u16) -> Result<(), ErrorKind> {
+        clear_range(&mut self.coils, reg, count, false)
+    }
+
+    /// Clear `count` discretes starting from `reg`
+    pub fn clear_discretes_range(&mut self, reg: u16, count: u16) -> Result<(), ErrorKind> {
+        clear_range(&mut self.discretes, reg, count, false)
+    }
+
+    /// Clear `count` inputs starting from `reg`
+    pub fn clear_inputs_range(&mut self, reg: u16, count: u16) -> Result<(), ErrorKind> {
+        clear_range(&mut self.inputs, reg, count, 0)
+    }
+
+    /// Clear `count` holdings starting from `reg`
+    pub fn clear_holdings_range(&mut self, reg: u16, count: u16) -> Result<(), ErrorKind> {
+        clear_range(&mut self.holdings, reg, count, 0)
+    }
+
+    /// Compare the storage with a snapshot (e.g. a clone taken before writes), yielding ranges of
+    /// contiguous coils and holdings which differ
+    ///
+    /// Discretes and inputs are not compared, as they are not written by Modbus clients
+    pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = Changes> + 'a {
+        DiffRanges::new(&self.coils, &other.coils)
+            .map(|(reg, count)| Changes::Coils { reg, count })
+            .chain(
+                DiffRanges::new(&self.holdings, &other.holdings)
+                    .map(|(reg, count)| Changes::Holdings { reg, count }),
+            )
+    }
+
+    /// Size of the storage dump in bytes, see [`dump_into`](ModbusStorage::dump_into)
+    pub const fn dump_size() -> usize {
+        C + D + (I + H) * 2
+    }
+
+    /// Dump the storage, e.g. to persist it on disk
+    ///
+    /// The order is: coils, discretes (a byte per value, 0 or 1), inputs, holdings (big-endian)
+    pub fn dump_into<V: VectorTrait<u8>>(&self, out: &mut V) -> Result<(), ErrorKind> {
+        out.clear();
+        for bits in [&self.coils[..], &self.discretes[..]] {
+            for b in bits {
+                out.push(u8::from(*b))?;
+            }
+        }
+        for regs in [&self.inputs[..], &self.holdings[..]] {
+            for r in regs {
+                out.extend(&r.to_be_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Restore the storage from a dump, created with [`dump_into`](ModbusStorage::dump_into)
+    ///
+    /// Errors:
+    ///
+    /// * **OOB** the data length does not match [`dump_size`](ModbusStorage::dump_size), the
+    ///   storage is not modified
+    pub fn restore_from(&mut self, data: &[u8]) -> Result<(), ErrorKind> {
+        if data.len() != Self::dump_size() {
+            return Err(ErrorKind::OOB);
+        }
+        let (coils, data) = data.split_at(C);
+        let (discretes, data) = data.split_at(D);
+        let (inputs, holdings) = data.split_at(I * 2);
+        for (v, b) in self.coils.iter_mut().zip(coils) {
+            *v = *b != 0;
+        }
+        for (v, b) in self.discretes.iter_mut().zip(discretes) {
+            *v = *b != 0;
+        }
+        for (v, b) in self.inputs.iter_mut().zip(inputs.chunks_exact(2)) {
+            *v = u16::from_be_bytes([b[0], b[1]]);
+        }
+        for (v, b) in self.holdings.iter_mut().zip(holdings.chunks_exact(2)) {
+            *v = u16::from_be_bytes([b[0], b[1]]);
+        }
+        Ok(())
+    }
+}
+
+/// Ranges of contiguous differing values of two banks, as (reg, count)
+struct DiffRanges<'a, T> {
+    a: &'a [T],
+    b: &'a [T],
+    pos: usize,
+}
+
+impl<'a, T> DiffRanges<'a, T> {
+    fn new(a: &'a [T], b: &'a [T]) -> Self {
+        // registers above u16::MAX are not addressable
+        let len = a.len().min(usize::from(u16::MAX) + 1);
+        Self {
+            a: &a[..len],
+            b: &b[..len],
+            pos: 0,
+        }
+    }
+}
+
+impl<T: PartialEq> Iterator for DiffRanges<'_, T> {
+    type Item = (u16, u16);
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.a.len() && self.a[self.pos] == self.b[self.pos] {
+            self.pos += 1;
+        }
+        if self.pos == self.a.len() {
+            return None;
+        }
+        let start = self.pos;
+        while self.pos < self.a.len()
+            && self.a[self.pos] != self.b[self.pos]
+            && self.pos - start < usize::from(u16::MAX)
+        {
+            self.pos += 1;
+        }
+        Some((start as u16, (self.pos - start) as u16))
+    }
+}
+
+fn clear_range<T: Copy>(bank: &mut [T], reg: u16, count: u16, value: T) -> Result<(), ErrorKind> {
+    let reg_to = reg as usize + count as usize;
+    bank.get_mut(reg as usize..reg_to)
+        .ok_or(ErrorKind::OOBContext)?
+        .fill(value);
+    Ok(())
 }
 
 #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
@@ -438,6 +602,22 @@ impl<const C: usize, const D: usize, const I: usize, const H: usize> ModbusConte
         get_bulk!(self.holdings, reg, count, result, H)
     }
 
+    fn get_coils_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        get_into!(self.coils, reg, out, C)
+    }
+
+    fn get_discretes_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        get_into!(self.discretes, reg, out, D)
+    }
+
+    fn get_inputs_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        get_into!(self.inputs, reg, out, I)
+    }
+
+    fn get_holdings_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        get_into!(self.holdings, reg, out, H)
+    }
+
     fn set_coils_bulk(&mut self, reg: u16, values: &[bool]) -> Result<(), ErrorKind> {
         set_bulk!(self.coils, reg, values, C)
     }
@@ -536,3 +716,455 @@ impl<const C: usize, const D: usize, const I: usize, const H: usize> ModbusConte
         self.set_holdings_from_u32(reg, value.bits())
     }
 }
+
+/// Small packed context (1000) registers per type
+pub type ModbusStoragePackedSmall = ModbusStoragePacked<
+    { SMALL_STORAGE_SIZE / 8 },
+    { SMALL_STORAGE_SIZE / 8 },
+    SMALL_STORAGE_SIZE,
+    SMALL_STORAGE_SIZE,
+>;
+/// Full packed context (10000) registers per type
+pub type ModbusStoragePackedFull = ModbusStoragePacked<
+    { FULL_STORAGE_SIZE / 8 },
+    { FULL_STORAGE_SIZE / 8 },
+    FULL_STORAGE_SIZE,
+    FULL_STORAGE_SIZE,
+>;
+
+/// The same as [`ModbusStorage`], but coils and discretes are packed into bits (LSB first), which
+/// takes 8 times less RAM for them
+///
+/// The generic constants order is: coil bytes, discrete bytes, inputs, holdings, so the number of
+/// coils / discretes is the number of bytes * 8
+///
+/// ```
+/// use rmodbus::server::{context::ModbusContext, storage::ModbusStoragePacked};
+///
+/// // 128 coils, 16 discretes, 0 inputs and 100 holdings
+/// let mut context = ModbusStoragePacked::<16, 2, 0, 100>::new();
+/// context.set_coil(127, true).unwrap();
+/// assert_eq!(context.coils[15], 0x80);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "with_serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "with_bincode", derive(Decode, Encode))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ModbusStoragePacked<const CB: usize, const DB: usize, const I: usize, const H: usize> {
+    #[cfg_attr(feature = "with_serde", serde(with = "serde_arrays"))]
+    pub coils: [u8; CB],
+    #[cfg_attr(feature = "with_serde", serde(with = "serde_arrays"))]
+    pub discretes: [u8; DB],
+    #[cfg_attr(feature = "with_serde", serde(with = "serde_arrays"))]
+    pub inputs: [u16; I],
+    #[cfg_attr(feature = "with_serde", serde(with = "serde_arrays"))]
+    pub holdings: [u16; H],
+}
+
+impl<const CB: usize, const DB: usize, const I: usize, const H: usize> Default
+    for ModbusStoragePacked<CB, DB, I, H>
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CB: usize, const DB: usize, const I: usize, const H: usize>
+    ModbusStoragePacked<CB, DB, I, H>
+{
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            coils: [0; CB],
+            discretes: [0; DB],
+            inputs: [0; I],
+            holdings: [0; H],
+        }
+    }
+
+    pub fn clear_all(&mut self) {
+        self.clear_coils();
+        self.clear_discretes();
+        self.clear_inputs();
+        self.clear_holdings();
+    }
+
+    pub fn clear_coils(&mut self) {
+        self.coils = [0; CB];
+    }
+
+    pub fn clear_discretes(&mut self) {
+        self.discretes = [0; DB];
+    }
+
+    pub fn clear_inputs(&mut self) {
+        self.inputs = [0; I];
+    }
+
+    pub fn clear_holdings(&mut self) {
+        self.holdings = [0; H];
+    }
+}
+
+#[inline]
+fn bit_get(bits: &[u8], n: usize) -> bool {
+    bits[n >> 3] >> (n & 7) & 1 == 1
+}
+
+#[inline]
+fn bit_set(bits: &mut [u8], n: usize, value: bool) {
+    if value {
+        bits[n >> 3] |= 1 << (n & 7);
+    } else {
+        bits[n >> 3] &= !(1 << (n & 7));
+    }
+}
+
+fn bits_check(bits: &[u8], reg: u16, count: usize) -> Result<usize, ErrorKind> {
+    let reg_to = reg as usize + count;
+    if reg_to > bits.len() * 8 {
+        Err(ErrorKind::OOBContext)
+    } else {
+        Ok(reg_to)
+    }
+}
+
+fn get_bits_as_u8<V: VectorTrait<u8>>(
+    bits: &[u8],
+    reg: u16,
+    count: u16,
+    result: &mut V,
+) -> Result<(), ErrorKind> {
+    let reg_to = bits_check(bits, reg, count.into())?;
+    let mut creg = reg as usize;
+    while creg < reg_to {
+        let mut cbyte = 0;
+        for i in 0..8 {
+            if bit_get(bits, creg) {
+                cbyte |= 1 << i;
+            }
+            creg += 1;
+            if creg >= reg_to {
+                break;
+            }
+        }
+        result.push(cbyte)?;
+    }
+    Ok(())
+}
+
+fn get_bits_as_u8_bytes<V: VectorTrait<u8>>(
+    bits: &[u8],
+    reg: u16,
+    count: u16,
+    result: &mut V,
+) -> Result<(), ErrorKind> {
+    let reg_to = bits_check(bits, reg, count.into())?;
+    for creg in reg as usize..reg_to {
+        result.push(u8::from(bit_get(bits, creg)))?;
+    }
+    Ok(())
+}
+
+fn get_bits_bulk<V: VectorTrait<bool>>(
+    bits: &[u8],
+    reg: u16,
+    count: u16,
+    result: &mut V,
+) -> Result<(), ErrorKind> {
+    let reg_to = bits_check(bits, reg, count.into())?;
+    for creg in reg as usize..reg_to {
+        result.push(bit_get(bits, creg))?;
+    }
+    Ok(())
+}
+
+fn get_bits_into(bits: &[u8], reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+    bits_check(bits, reg, out.len())?;
+    for (i, v) in out.iter_mut().enumerate() {
+        *v = bit_get(bits, reg as usize + i);
+    }
+    Ok(())
+}
+
+fn set_bits_from_u8(bits: &mut [u8], reg: u16, count: u16, values: &[u8]) -> Result<(), ErrorKind> {
+    let reg_to = bits_check(bits, reg, count.into())?;
+    if usize::from(count) > values.len() * 8 {
+        return Err(ErrorKind::OOB);
+    }
+    let mut creg = reg as usize;
+    let mut cbyte = 0;
+    while creg < reg_to {
+        let mut b = values[cbyte];
+        for _ in 0..8 {
+            bit_set(bits, creg, b & 1 == 1);
+            b >>= 1;
+            creg += 1;
+            if creg == reg_to {
+                break;
+            }
+        }
+        cbyte += 1;
+    }
+    Ok(())
+}
+
+fn set_bits_from_u8_bytes(bits: &mut [u8], reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+    bits_check(bits, reg, values.len())?;
+    for (i, value) in values.iter().enumerate() {
+        bit_set(bits, reg as usize + i, *value > 0);
+    }
+    Ok(())
+}
+
+fn set_bits_bulk(bits: &mut [u8], reg: u16, values: &[bool]) -> Result<(), ErrorKind> {
+    bits_check(bits, reg, values.len())?;
+    for (i, value) in values.iter().enumerate() {
+        bit_set(bits, reg as usize + i, *value);
+    }
+    Ok(())
+}
+
+impl<const CB: usize, const DB: usize, const I: usize, const H: usize> ModbusContext
+    for ModbusStoragePacked<CB, DB, I, H>
+{
+    fn get_inputs_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_regs_as_u8!(self.inputs, reg, count, result, I)
+    }
+
+    fn get_holdings_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_regs_as_u8!(self.holdings, reg, count, result, H)
+    }
+
+    fn set_inputs_from_u8(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        set_regs_from_u8!(self.inputs, reg, values, I)
+    }
+
+    fn set_holdings_from_u8(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        set_regs_from_u8!(self.holdings, reg, values, H)
+    }
+
+    fn get_coils_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bits_as_u8(&self.coils, reg, count, result)
+    }
+
+    fn get_coils_as_u8_bytes<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bits_as_u8_bytes(&self.coils, reg, count, result)
+    }
+
+    fn get_discretes_as_u8<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bits_as_u8(&self.discretes, reg, count, result)
+    }
+
+    fn get_discretes_as_u8_bytes<V: VectorTrait<u8>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bits_as_u8_bytes(&self.discretes, reg, count, result)
+    }
+
+    fn set_coils_from_u8(&mut self, reg: u16, count: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        set_bits_from_u8(&mut self.coils, reg, count, values)
+    }
+
+    fn set_coils_from_u8_bytes(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        set_bits_from_u8_bytes(&mut self.coils, reg, values)
+    }
+
+    fn set_discretes_from_u8_bytes(&mut self, reg: u16, values: &[u8]) -> Result<(), ErrorKind> {
+        set_bits_from_u8_bytes(&mut self.discretes, reg, values)
+    }
+
+    fn set_discretes_from_u8(
+        &mut self,
+        reg: u16,
+        count: u16,
+        values: &[u8],
+    ) -> Result<(), ErrorKind> {
+        set_bits_from_u8(&mut self.discretes, reg, count, values)
+    }
+
+    fn get_coils_bulk<V: VectorTrait<bool>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bits_bulk(&self.coils, reg, count, result)
+    }
+
+    fn get_discretes_bulk<V: VectorTrait<bool>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bits_bulk(&self.discretes, reg, count, result)
+    }
+
+    fn get_inputs_bulk<V: VectorTrait<u16>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bulk!(self.inputs, reg, count, result, I)
+    }
+
+    fn get_holdings_bulk<V: VectorTrait<u16>>(
+        &self,
+        reg: u16,
+        count: u16,
+        result: &mut V,
+    ) -> Result<(), ErrorKind> {
+        get_bulk!(self.holdings, reg, count, result, H)
+    }
+
+    fn get_coils_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        get_bits_into(&self.coils, reg, out)
+    }
+
+    fn get_discretes_into(&self, reg: u16, out: &mut [bool]) -> Result<(), ErrorKind> {
+        get_bits_into(&self.discretes, reg, out)
+    }
+
+    fn get_inputs_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        get_into!(self.inputs, reg, out, I)
+    }
+
+    fn get_holdings_into(&self, reg: u16, out: &mut [u16]) -> Result<(), ErrorKind> {
+        get_into!(self.holdings, reg, out, H)
+    }
+
+    fn set_coils_bulk(&mut self, reg: u16, values: &[bool]) -> Result<(), ErrorKind> {
+        set_bits_bulk(&mut self.coils, reg, values)
+    }
+
+    fn set_discretes_bulk(&mut self, reg: u16, values: &[bool]) -> Result<(), ErrorKind> {
+        set_bits_bulk(&mut self.discretes, reg, values)
+    }
+
+    fn set_inputs_bulk(&mut self, reg: u16, values: &[u16]) -> Result<(), ErrorKind> {
+        set_bulk!(self.inputs, reg, values, I)
+    }
+
+    fn set_holdings_bulk(&mut self, reg: u16, values: &[u16]) -> Result<(), ErrorKind> {
+        set_bulk!(self.holdings, reg, values, H)
+    }
+
+    fn get_coil(&self, reg: u16) -> Result<bool, ErrorKind> {
+        bits_check(&self.coils, reg, 1)?;
+        Ok(bit_get(&self.coils, reg.into()))
+    }
+
+    fn get_discrete(&self, reg: u16) -> Result<bool, ErrorKind> {
+        bits_check(&self.discretes, reg, 1)?;
+        Ok(bit_get(&self.discretes, reg.into()))
+    }
+
+    fn get_input(&self, reg: u16) -> Result<u16, ErrorKind> {
+        get!(self.inputs, reg, I)
+    }
+
+    fn get_holding(&self, reg: u16) -> Result<u16, ErrorKind> {
+        get!(self.holdings, reg, H)
+    }
+
+    fn set_coil(&mut self, reg: u16, value: bool) -> Result<(), ErrorKind> {
+        bits_check(&self.coils, reg, 1)?;
+        bit_set(&mut self.coils, reg.into(), value);
+        Ok(())
+    }
+
+    fn set_discrete(&mut self, reg: u16, value: bool) -> Result<(), ErrorKind> {
+        bits_check(&self.discretes, reg, 1)?;
+        bit_set(&mut self.discretes, reg.into(), value);
+        Ok(())
+    }
+
+    fn set_input(&mut self, reg: u16, value: u16) -> Result<(), ErrorKind> {
+        set!(self.inputs, reg, value, I)
+    }
+
+    fn set_holding(&mut self, reg: u16, value: u16) -> Result<(), ErrorKind> {
+        set!(self.holdings, reg, value, H)
+    }
+
+    fn get_inputs_as_u32(&self, reg: u16) -> Result<u32, ErrorKind> {
+        get_u32!(self.inputs, reg, I)
+    }
+
+    fn get_holdings_as_u32(&self, reg: u16) -> Result<u32, ErrorKind> {
+        get_u32!(self.holdings, reg, H)
+    }
+
+    fn set_inputs_from_u32(&mut self, reg: u16, value: u32) -> Result<(), ErrorKind> {
+        set_u32!(self.inputs, reg, value, I)
+    }
+
+    fn set_holdings_from_u32(&mut self, reg: u16, value: u32) -> Result<(), ErrorKind> {
+        set_u32!(self.holdings, reg, value, H)
+    }
+
+    fn get_inputs_as_u64(&self, reg: u16) -> Result<u64, ErrorKind> {
+        get_u64!(self.inputs, reg, I)
+    }
+
+    fn get_holdings_as_u64(&self, reg: u16) -> Result<u64, ErrorKind> {
+        get_u64!(self.holdings, reg, H)
+    }
+
+    fn set_inputs_from_u64(&mut self, reg: u16, value: u64) -> Result<(), ErrorKind> {
+        set_u64!(self.inputs, reg, value, I)
+    }
+
+    fn set_holdings_from_u64(&mut self, reg: u16, value: u64) -> Result<(), ErrorKind> {
+        set_u64!(self.holdings, reg, value, H)
+    }
+
+    fn get_inputs_as_f32(&self, reg: u16) -> Result<f32, ErrorKind> {
+        Ok(Ieee754::from_bits(self.get_inputs_as_u32(reg)?))
+    }
+
+    fn get_holdings_as_f32(&self, reg: u16) -> Result<f32, ErrorKind> {
+        Ok(Ieee754::from_bits(self.get_holdings_as_u32(reg)?))
+    }
+
+    #[inline]
+    fn set_inputs_from_f32(&mut self, reg: u16, value: f32) -> Result<(), ErrorKind> {
+        self.set_inputs_from_u32(reg, value.bits())
+    }
+
+    #[inline]
+    fn set_holdings_from_f32(&mut self, reg: u16, value: f32) -> Result<(), ErrorKind> {
+        self.set_holdings_from_u32(reg, value.bits())
+    }
+}
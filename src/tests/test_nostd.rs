@@ -191,7 +191,7 @@ fn test_nostd_frame() {
     let request = [1, 1, 0, 5, 0, 5];
     let response = [0x77, 0x55, 0, 0, 0, 4, 1, 1, 1, 0x15];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -205,7 +205,7 @@ fn test_nostd_frame() {
     let mut result_mem = alloc_stack!([u8; 10]);
     for i in 0..10 {
         let mut result = FixedVec::new(&mut result_mem[..i]);
-        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
         match frame.parse() {
             Ok(()) => {
                 if i > 3 {
@@ -235,7 +235,7 @@ fn test_nostd_client() {
     let coils = [
         true, true, true, false, true, true, false, true, true, false, true,
     ];
-    let protos = [ModbusProto::TcpUdp, ModbusProto::Rtu, ModbusProto::Ascii];
+    let protos = [ModbusProto::Tcp, ModbusProto::Rtu, ModbusProto::Ascii];
 
     for proto in &protos {
         // set coils bulk
@@ -315,3 +315,123 @@ fn test_nostd_client() {
         assert_eq!(result.as_slice(), coils);
     }
 }
+
+#[test]
+fn test_nostd_client_parse_response_typed_ref() {
+    let mut ctx = CTX.write();
+    ctx.set_holdings_bulk(10, &[0x1234, 0x5678]).unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request_mem = alloc_stack!([u8; 256]);
+    let mut request = FixedVec::new(&mut request_mem);
+    mreq.generate_get_holdings(10, 2, &mut request).unwrap();
+    let mut response_mem = alloc_stack!([u8; 256]);
+    let mut response = FixedVec::new(&mut response_mem);
+    let mut frame = ModbusFrame::new(1, request.as_slice(), ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        mreq.parse_response_typed_ref(response.as_slice()).unwrap(),
+        ResponseDataRef::Holdings(&[0x12, 0x34, 0x56, 0x78])
+    );
+}
+
+#[test]
+fn test_nostd_slice_writer() {
+    let mut ctx = CTX.write();
+    ctx.set_holdings_bulk(20, &[1, 2, 3, 4]).unwrap();
+    let framebuf = gen_tcp_frame(&[1, 3, 0, 20, 0, 4]);
+    // too small
+    let mut buf = [0u8; 10];
+    let mut response = SliceWriter::new(&mut buf);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.process_read(&*ctx).err().unwrap(), ErrorKind::OOB);
+    // adequately sized
+    let mut buf = [0u8; 17];
+    let mut response = SliceWriter::new(&mut buf);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        response.as_slice(),
+        [0x77, 0x55, 0, 0, 0, 11, 1, 3, 8, 0, 1, 0, 2, 0, 3, 0, 4]
+    );
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    mreq.tr_id = 0x7755;
+    let mut request_buf = [0u8; 12];
+    let mut request = SliceWriter::new(&mut request_buf);
+    mreq.generate_get_holdings(20, 4, &mut request).unwrap();
+    let mut result_buf = [0u16; 4];
+    let mut result = SliceWriter::new(&mut result_buf);
+    mreq.parse_u16(response.as_slice(), &mut result).unwrap();
+    assert_eq!(result.as_slice(), [1, 2, 3, 4]);
+    assert_eq!(result.push(5).err().unwrap(), ErrorKind::OOB);
+    result.cut_end(3, 0);
+    result.resize(3, 7).unwrap();
+    assert_eq!(result.as_slice(), [1, 7, 7]);
+    assert_eq!(result.resize(5, 0).err().unwrap(), ErrorKind::OOB);
+}
+
+#[test]
+fn test_nostd_response_buf() {
+    let mut ctx = CTX.write();
+    ctx.set_holdings_bulk(40, &[0x1234, 0x5678]).unwrap();
+    let framebuf = gen_tcp_frame(&[1, 3, 0, 40, 0, 2]);
+    let mut response = ResponseBuf::new();
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    let sendable = response;
+    assert_eq!(
+        sendable.as_sendable(),
+        [0x77, 0x55, 0, 0, 0, 7, 1, 3, 4, 0x12, 0x34, 0x56, 0x78]
+    );
+    // the buffer is cleared by the next frame
+    let framebuf = gen_tcp_frame(&[1, 3, 0, 40, 0, 1]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        response.as_sendable(),
+        [0x77, 0x55, 0, 0, 0, 5, 1, 3, 2, 0x12, 0x34]
+    );
+    assert_eq!(sendable.as_sendable().len(), 13);
+}
+
+#[test]
+fn test_nostd_parse_bool_into() {
+    let mut ctx = CTX.write();
+    let values = [
+        true, false, true, true, false, false, true, true, true, false, true, false, true,
+    ];
+    ctx.set_coils_bulk(300, &values).unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request_buf = [0u8; 12];
+    let mut request = SliceWriter::new(&mut request_buf);
+    mreq.generate_get_coils(300, 13, &mut request).unwrap();
+    let mut buf = [0u8; 11];
+    let mut response = SliceWriter::new(&mut buf);
+    let mut frame = ModbusFrame::new(1, request.as_slice(), ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    let mut result = [false; 16];
+    assert_eq!(
+        mreq.parse_bool_into(response.as_slice(), &mut result)
+            .unwrap(),
+        13
+    );
+    assert_eq!(result[..13], values);
+    assert_eq!(result[13..], [false; 3]);
+    let mut result = [false; 4];
+    assert_eq!(
+        mreq.parse_bool_into(response.as_slice(), &mut result)
+            .unwrap(),
+        4
+    );
+    assert_eq!(result, values[..4]);
+}
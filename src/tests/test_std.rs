@@ -1,7 +1,12 @@
 #[allow(clippy::wildcard_imports)]
 use crate::client::*;
-use crate::server::context::ModbusContext;
-use crate::server::storage::{ModbusStorageFull, FULL_STORAGE_SIZE as STORAGE_SIZE};
+use crate::server::context::{FloatByteOrder, ModbusContext, WordOrder};
+use crate::server::device_id::DeviceIdentification;
+use crate::server::diagnostics::{CommEventCounters, DiagnosticCounters};
+use crate::server::storage::{
+    ModbusStorageFull, ModbusStoragePackedSmall, ModbusStorageSmall,
+    FULL_STORAGE_SIZE as STORAGE_SIZE,
+};
 #[allow(clippy::wildcard_imports)]
 use crate::server::*;
 #[allow(clippy::wildcard_imports)]
@@ -216,6 +221,14 @@ fn test_std_read_inputs_as_bytes_oob() {
     }
     ctx.set_inputs_from_u64(u16::try_from(STORAGE_SIZE - 4).unwrap(), 0x9999)
         .unwrap();
+    if ctx
+        .set_inputs_from_f64(u16::try_from(STORAGE_SIZE - 3).unwrap(), 1.0)
+        .is_ok()
+    {
+        panic!("{}", "oob failed MAX f64")
+    }
+    ctx.set_inputs_from_f64(u16::try_from(STORAGE_SIZE - 4).unwrap(), 1.0)
+        .unwrap();
     if ctx
         .set_inputs_from_representable(
             u16::try_from(STORAGE_SIZE - 1).unwrap(),
@@ -310,6 +323,11 @@ fn test_std_get_set_inputs() {
     );
     ctx.set_inputs_from_f32(200, 1234.567).unwrap();
     assert_eq!(ctx.get_inputs_as_f32(200).unwrap(), 1234.567f32);
+    ctx.set_inputs_from_f64(210, 1_234_567.891_011).unwrap();
+    assert_eq!(ctx.get_inputs_as_f64(210).unwrap(), 1_234_567.891_011f64);
+    let nan = f64::from_bits(0x7ff8_0000_dead_beef);
+    ctx.set_inputs_from_f64(210, nan).unwrap();
+    assert_eq!(ctx.get_inputs_as_f64(210).unwrap().to_bits(), nan.to_bits());
 
     ctx.set_inputs_from_representable(300, &representations::U32LittleEndian(1_234_567))
         .unwrap();
@@ -401,6 +419,14 @@ fn test_std_read_holdings_as_bytes_oob() {
     }
     ctx.set_holdings_from_u64(u16::try_from(STORAGE_SIZE - 4).unwrap(), 0x9999)
         .unwrap();
+    if ctx
+        .set_holdings_from_f64(u16::try_from(STORAGE_SIZE - 3).unwrap(), 1.0)
+        .is_ok()
+    {
+        panic!("{}", "oob failed MAX f64")
+    }
+    ctx.set_holdings_from_f64(u16::try_from(STORAGE_SIZE - 4).unwrap(), 1.0)
+        .unwrap();
     if ctx
         .set_holdings_from_representable(
             u16::try_from(STORAGE_SIZE - 1).unwrap(),
@@ -495,6 +521,14 @@ fn test_std_get_set_holdings() {
     );
     ctx.set_holdings_from_f32(200, 1234.567).unwrap();
     assert_eq!(ctx.get_holdings_as_f32(200).unwrap(), 1234.567f32);
+    ctx.set_holdings_from_f64(210, 1_234_567.891_011).unwrap();
+    assert_eq!(ctx.get_holdings_as_f64(210).unwrap(), 1_234_567.891_011f64);
+    let nan = f64::from_bits(0x7ff8_0000_dead_beef);
+    ctx.set_holdings_from_f64(210, nan).unwrap();
+    assert_eq!(
+        ctx.get_holdings_as_f64(210).unwrap().to_bits(),
+        nan.to_bits()
+    );
 
     ctx.set_holdings_from_representable(300, &representations::U32LittleEndian(1_234_567))
         .unwrap();
@@ -529,6 +563,94 @@ fn test_std_get_set_holdings() {
     );
 }
 
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_std_get_set_word_order() {
+    let mut ctx = CTX.write().unwrap();
+    let mut result = Vec::new();
+
+    ctx.clear_holdings();
+    ctx.clear_inputs();
+
+    ctx.set_holdings_from_u32_with_order(10, 0x1234_5678, WordOrder::BigEndian)
+        .unwrap();
+    ctx.get_holdings_bulk(10, 2, &mut result).unwrap();
+    assert_eq!(result, [0x1234, 0x5678]);
+    assert_eq!(
+        ctx.get_holdings_as_u32_with_order(10, WordOrder::BigEndian)
+            .unwrap(),
+        0x1234_5678
+    );
+    assert_eq!(ctx.get_holdings_as_u32(10).unwrap(), 0x1234_5678);
+    result.clear();
+    ctx.set_holdings_from_u32_with_order(10, 0x1234_5678, WordOrder::LittleEndian)
+        .unwrap();
+    ctx.get_holdings_bulk(10, 2, &mut result).unwrap();
+    assert_eq!(result, [0x5678, 0x1234]);
+    assert_eq!(
+        ctx.get_holdings_as_u32_with_order(10, WordOrder::LittleEndian)
+            .unwrap(),
+        0x1234_5678
+    );
+    result.clear();
+    ctx.set_inputs_from_u32_with_order(10, 0x1234_5678, WordOrder::LittleEndian)
+        .unwrap();
+    ctx.get_inputs_bulk(10, 2, &mut result).unwrap();
+    assert_eq!(result, [0x5678, 0x1234]);
+    assert_eq!(
+        ctx.get_inputs_as_u32_with_order(10, WordOrder::LittleEndian)
+            .unwrap(),
+        0x1234_5678
+    );
+
+    result.clear();
+    ctx.set_holdings_from_u64_with_order(20, 0x1122_3344_5566_7788, WordOrder::LittleEndian)
+        .unwrap();
+    ctx.get_holdings_bulk(20, 4, &mut result).unwrap();
+    assert_eq!(result, [0x7788, 0x5566, 0x3344, 0x1122]);
+    assert_eq!(
+        ctx.get_holdings_as_u64_with_order(20, WordOrder::LittleEndian)
+            .unwrap(),
+        0x1122_3344_5566_7788
+    );
+    result.clear();
+    ctx.set_inputs_from_u64_with_order(20, 0x1122_3344_5566_7788, WordOrder::BigEndian)
+        .unwrap();
+    ctx.get_inputs_bulk(20, 4, &mut result).unwrap();
+    assert_eq!(result, [0x1122, 0x3344, 0x5566, 0x7788]);
+    assert_eq!(
+        ctx.get_inputs_as_u64_with_order(20, WordOrder::BigEndian)
+            .unwrap(),
+        0x1122_3344_5566_7788
+    );
+
+    ctx.set_holdings_from_f32_with_order(30, 1234.567, WordOrder::LittleEndian)
+        .unwrap();
+    assert_eq!(
+        ctx.get_holdings_as_u32_with_order(30, WordOrder::LittleEndian)
+            .unwrap(),
+        1234.567f32.to_bits()
+    );
+    assert_eq!(
+        ctx.get_holdings_as_f32_with_order(30, WordOrder::LittleEndian)
+            .unwrap(),
+        1234.567
+    );
+    ctx.set_inputs_from_f32_with_order(30, 1234.567, WordOrder::LittleEndian)
+        .unwrap();
+    assert_eq!(
+        ctx.get_inputs_as_f32_with_order(30, WordOrder::LittleEndian)
+            .unwrap(),
+        1234.567
+    );
+    assert!(ctx
+        .get_holdings_as_u32_with_order(
+            u16::try_from(STORAGE_SIZE - 1).unwrap(),
+            WordOrder::LittleEndian
+        )
+        .is_err());
+}
+
 #[test]
 fn test_std_get_bools_as_u8() {
     let mut data = Vec::new();
@@ -569,6 +691,82 @@ fn test_std_get_bools_as_u8() {
     assert_eq!(*result.get(2).unwrap(), 0b10_1000);
 }
 
+#[test]
+fn test_std_get_set_holdings_u32_f32_bulk() {
+    let mut ctx = ModbusStorageSmall::default();
+    let values = [1.5f32, -2.25, 1e10];
+    ctx.set_holdings_from_f32_bulk(10, &values).unwrap();
+    assert_eq!(ctx.get_holdings_as_f32(12).unwrap(), -2.25);
+    let mut result = Vec::new();
+    ctx.get_holdings_as_f32_bulk(10, 3, &mut result).unwrap();
+    assert_eq!(result, values);
+    let values = [0x1122_3344, 0x5566_7788, 0x99aa_bbcc];
+    ctx.set_holdings_from_u32_bulk(994, &values).unwrap();
+    assert_eq!(ctx.get_holding(999).unwrap(), 0xbbcc);
+    let mut result = Vec::new();
+    ctx.get_holdings_as_u32_bulk(994, 3, &mut result).unwrap();
+    assert_eq!(result, values);
+    // out of range, nothing is written
+    assert_eq!(
+        ctx.set_holdings_from_u32_bulk(996, &[1, 2, 3])
+            .err()
+            .unwrap(),
+        ErrorKind::OOBContext
+    );
+    assert_eq!(ctx.get_holding(997).unwrap(), 0x7788);
+    assert_eq!(
+        ctx.set_holdings_from_f32_bulk(u16::MAX, &[1.0])
+            .err()
+            .unwrap(),
+        ErrorKind::OOBContext
+    );
+    let mut result = Vec::new();
+    assert_eq!(
+        ctx.get_holdings_as_u32_bulk(996, 3, &mut result)
+            .err()
+            .unwrap(),
+        ErrorKind::OOBContext
+    );
+    assert!(result.is_empty());
+    ctx.set_holdings_from_u32_bulk(0, &[]).unwrap();
+}
+
+#[test]
+fn test_std_get_into() {
+    let mut ctx = ModbusStorageSmall::default();
+    ctx.set_holdings_bulk(10, &[1, 2, 3]).unwrap();
+    ctx.set_inputs_bulk(997, &[4, 5, 6]).unwrap();
+    ctx.set_coils_bulk(5, &[true, false, true]).unwrap();
+    ctx.set_discretes_bulk(998, &[true, true]).unwrap();
+    let mut regs = [0u16; 3];
+    ctx.get_holdings_into(10, &mut regs).unwrap();
+    assert_eq!(regs, [1, 2, 3]);
+    ctx.get_inputs_into(997, &mut regs).unwrap();
+    assert_eq!(regs, [4, 5, 6]);
+    let mut bools = [false; 3];
+    ctx.get_coils_into(5, &mut bools).unwrap();
+    assert_eq!(bools, [true, false, true]);
+    ctx.get_discretes_into(998, &mut bools[..2]).unwrap();
+    assert_eq!(bools, [true, true, true]);
+    // zero-length
+    ctx.get_holdings_into(10, &mut []).unwrap();
+    // out of range
+    let mut regs = [0u16; 3];
+    assert_eq!(
+        ctx.get_holdings_into(1000, &mut regs[..1]).err().unwrap(),
+        ErrorKind::OOBContext
+    );
+    assert_eq!(
+        ctx.get_inputs_into(998, &mut regs).err().unwrap(),
+        ErrorKind::OOBContext
+    );
+    assert_eq!(regs, [0, 0, 0]);
+    assert_eq!(
+        ctx.get_coils_into(u16::MAX, &mut bools).err().unwrap(),
+        ErrorKind::OOBContext
+    );
+}
+
 #[test]
 fn test_std_get_set_regs_as_u8() {
     let mut data = Vec::new();
@@ -589,6 +787,19 @@ fn test_std_get_set_regs_as_u8() {
     ctx.get_holdings_bulk(0, u16::try_from(data.len()).unwrap(), &mut result)
         .unwrap();
     assert_eq!(result, data);
+    let mut result = Vec::new();
+    ctx.get_holdings_as_u8_bytes(1, 3, &mut result).unwrap();
+    assert_eq!(result, [0, 45, 0x11, 0xcf, 0, 31]);
+    ctx.set_inputs_bulk(0, &[0x1234]).unwrap();
+    let mut result = Vec::new();
+    ctx.get_inputs_as_u8_bytes(0, 1, &mut result).unwrap();
+    assert_eq!(result, [0x12, 0x34]);
+    assert_eq!(
+        ctx.get_holdings_as_u8_bytes(u16::try_from(STORAGE_SIZE - 2).unwrap(), 3, &mut result)
+            .err()
+            .unwrap(),
+        ErrorKind::OOBContext
+    );
 }
 
 #[test]
@@ -637,7 +848,7 @@ fn gen_tcp_frame(data: &[u8]) -> ModbusFrameBuf {
         frame[i + 6] = *v;
     }
     assert_eq!(
-        guess_request_frame_len(&frame, ModbusProto::TcpUdp).unwrap(),
+        guess_request_frame_len(&frame, ModbusProto::Tcp).unwrap(),
         u8::try_from(data.len() + 6).unwrap()
     );
     frame
@@ -690,7 +901,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     let request = [1, 1, 0, 5, 0, 5];
     let response = [0x77, 0x55, 0, 0, 0, 4, 1, 1, 1, 0x15];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -724,10 +935,10 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
         panic!()
     }
     // check illegal_function
-    let request = [1, 7, 0x27, 0xe, 0, 0xf];
-    let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x87, 1];
+    let request = [1, 9, 0x27, 0xe, 0, 0xf];
+    let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x89, 1];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(!frame.processing_required);
@@ -747,7 +958,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     let request = [1, 1, 0x27, 0xe, 0, 0xf];
     let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x81, 2];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -770,7 +981,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     let request = [1, 1, 0, 5, 0, 5];
     let mut framebuf = gen_tcp_frame(&request);
     framebuf[5] = 2;
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     match frame.parse() {
         Ok(()) => panic!(),
         Err(e) => match e {
@@ -780,7 +991,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     }
     let mut framebuf = gen_tcp_frame(&request);
     framebuf[5] = 251;
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     match frame.parse() {
         Ok(()) => panic!(),
         Err(e) => match e {
@@ -790,7 +1001,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     }
     let mut framebuf = gen_tcp_frame(&request);
     framebuf[3] = 22;
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     match frame.parse() {
         Ok(()) => panic!(),
         Err(e) => match e {
@@ -803,7 +1014,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     ctx.set_discrete(12, true).unwrap();
     ctx.set_discrete(16, true).unwrap();
     let framebuf = gen_tcp_frame(&[1, 2, 0, 5, 0, 0x10]);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -825,7 +1036,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
         0x77, 0x55, 0, 0, 0, 0x19, 1, 3, 0x16, 0, 0, 0, 0, 0x26, 0xf9, 0, 0, 0x25, 0x47, 0, 0, 0,
         0, 0x25, 0x32, 0, 0, 0, 0, 0, 0,
     ];
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -849,7 +1060,7 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     ctx.set_input(281, 15923).unwrap();
     ctx.set_input(284, 54321).unwrap();
     let framebuf = gen_tcp_frame(&[1, 4, 1, 0x18, 0, 6]);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -863,6 +1074,146 @@ fn test_std_frame_fc01_fc02_fc03_fc04_unknown_function() {
     );
 }
 
+#[test]
+fn test_std_frame_udp() {
+    let mut result = Vec::new();
+    let request = [0x77, 0x55, 0, 0, 0, 6, 1, 3, 0, 0, 0, 1];
+    // exact datagram
+    for proto in [ModbusProto::Tcp, ModbusProto::Udp] {
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut result);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+    }
+    // shorter than the declared length
+    for proto in [ModbusProto::Tcp, ModbusProto::Udp] {
+        let mut frame = ModbusFrame::new(1, &request[..11], proto, &mut result);
+        assert_eq!(frame.parse().err().unwrap(), ErrorKind::FrameBroken);
+        let mut frame = ModbusFrame::new(1, &request[..5], proto, &mut result);
+        assert_eq!(frame.parse().err().unwrap(), ErrorKind::FrameBroken);
+    }
+    // longer than the declared length: fine for TCP (the buffer may contain garbage), broken
+    // for UDP
+    let framebuf = gen_tcp_frame(&request[6..]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Udp, &mut result);
+    assert_eq!(frame.parse().err().unwrap(), ErrorKind::FrameBroken);
+    // client
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Udp);
+    mreq.tr_id = 0x7755;
+    let mut req = Vec::new();
+    mreq.generate_get_holdings(0, 1, &mut req).unwrap();
+    assert_eq!(req, request);
+    let response = [0x77, 0x55, 0, 0, 0, 5, 1, 3, 2, 0, 1];
+    mreq.parse_ok(&response).unwrap();
+    assert_eq!(
+        mreq.parse_ok(&response[..10]).err().unwrap(),
+        ErrorKind::FrameBroken
+    );
+    let mut longer = response.to_vec();
+    longer.push(0);
+    assert_eq!(
+        mreq.parse_ok(&longer).err().unwrap(),
+        ErrorKind::FrameBroken
+    );
+    // deprecated alias
+    #[allow(deprecated)]
+    let proto = ModbusProto::TcpUdp;
+    assert_eq!(proto, ModbusProto::Tcp);
+}
+
+#[test]
+fn test_std_frame_input_writes() {
+    let mut ctx = ModbusStorageSmall::default();
+    let mut result = Vec::new();
+    let framebuf = gen_tcp_frame(&[1, 16, 0, 10, 0, 2, 4, 0x12, 0x34, 0x56, 0x78]);
+    // off by default
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_write(&mut ctx).unwrap();
+    assert_eq!(ctx.get_holding(10).unwrap(), 0x1234);
+    assert_eq!(ctx.get_input(10).unwrap(), 0);
+    ctx.clear_holdings();
+    // the window does not contain the whole range
+    let mut frame =
+        ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result).allow_input_writes(0, 11);
+    frame.parse().unwrap();
+    frame.process_write(&mut ctx).unwrap();
+    assert_eq!(ctx.get_holding(11).unwrap(), 0x5678);
+    assert_eq!(ctx.get_input(11).unwrap(), 0);
+    ctx.clear_holdings();
+    let mut frame =
+        ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result).allow_input_writes(0, 12);
+    frame.parse().unwrap();
+    assert!(frame.changes().is_none());
+    assert_eq!(frame.changed_values().count(), 0);
+    frame.process_write(&mut ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(result[7..12], [16, 0, 10, 0, 2]);
+    let mut inputs = Vec::new();
+    ctx.get_inputs_bulk(10, 2, &mut inputs).unwrap();
+    assert_eq!(inputs, [0x1234, 0x5678]);
+    assert_eq!(ctx.get_holding(10).unwrap(), 0);
+    assert_eq!(ctx.get_holding(11).unwrap(), 0);
+}
+
+#[test]
+fn test_std_frame_lenient_oob() {
+    let mut ctx = ModbusStorageSmall::default();
+    ctx.set_holdings_bulk(995, &[1, 2, 3, 4, 5]).unwrap();
+    ctx.set_coils_bulk(998, &[true, true]).unwrap();
+    let mut result = Vec::new();
+    let framebuf = gen_tcp_frame(&[1, 3, 0x03, 0xe3, 0, 10]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result).lenient_oob(true);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        result,
+        [
+            0x77, 0x55, 0, 0, 0, 23, 1, 3, 20, 0, 1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0
+        ]
+    );
+    // trait objects
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result).lenient_oob(true);
+    frame.parse().unwrap();
+    frame.process_read_dyn(&ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result[9..19], [0, 1, 0, 2, 0, 3, 0, 4, 0, 5]);
+    assert_eq!(result[19..], [0; 10]);
+    // coils
+    let framebuf = gen_rtu_frame(&[1, 1, 0x03, 0xe6, 0, 4]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result).lenient_oob(true);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result[..4], [1, 1, 1, 0b0011]);
+    // strict by default
+    let framebuf = gen_tcp_frame(&[1, 3, 0x03, 0xe3, 0, 10]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    assert_eq!(frame.error, consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS);
+}
+
+#[test]
+fn test_std_frame_transaction_id() {
+    let mut result = Vec::new();
+    let request = [1, 3, 0, 0, 0, 1];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    assert_eq!(frame.transaction_id, None);
+    frame.parse().unwrap();
+    assert_eq!(frame.transaction_id, Some(0x7755));
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    assert_eq!(frame.transaction_id, None);
+}
+
 #[test]
 #[allow(clippy::too_many_lines)]
 fn test_std_frame_fc05_fc06() {
@@ -873,7 +1224,7 @@ fn test_std_frame_fc05_fc06() {
     let request = [1, 5, 0, 0xb, 0xff, 0];
     let response = [0x77, 0x55, 0, 0, 0, 6, 1, 5, 0, 0xb, 0xff, 0];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -898,7 +1249,7 @@ fn test_std_frame_fc05_fc06() {
     // write coil broadcast tcp
     let request = [0, 5, 0, 0x5, 0xff, 0];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(!frame.response_required);
     assert!(frame.processing_required);
@@ -922,7 +1273,7 @@ fn test_std_frame_fc05_fc06() {
     let request = [1, 5, 0, 0xb, 0xff, 1];
     let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x85, 3];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -936,7 +1287,7 @@ fn test_std_frame_fc05_fc06() {
     let request = [1, 5, 0x99, 0x99, 0xff, 0];
     let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x85, 2];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -960,7 +1311,7 @@ fn test_std_frame_fc05_fc06() {
     let request = [1, 6, 0, 0xc, 0x33, 0x55];
     let response = [0x77, 0x55, 0, 0, 0, 6, 1, 6, 0, 0xc, 0x33, 0x55];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -986,7 +1337,7 @@ fn test_std_frame_fc05_fc06() {
     let request = [1, 6, 0xff, 0xc, 0x33, 0x55];
     let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x86, 2];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -1018,7 +1369,7 @@ fn test_std_frame_fc15() {
     let request = [1, 0xf, 1, 0x31, 0, 5, 1, 0x25]; // 6 bits in data but 5 coils
     let response = [0x77, 0x55, 0, 0, 0, 6, 1, 0xf, 1, 0x31, 0, 5];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -1049,7 +1400,7 @@ fn test_std_frame_fc15() {
     let request = [1, 0xf, 0x99, 0xe8, 0, 5, 1, 0x25]; // 6 bits in data but 5 coils
     let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x8f, 2];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -1083,7 +1434,7 @@ fn test_std_frame_fc16() {
     ];
     let response = [0x77, 0x55, 0, 0, 0, 6, 1, 0x10, 1, 0x2c, 0, 4];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert_eq!(frame.func, 0x10);
     assert_eq!(frame.error, 0);
@@ -1115,7 +1466,7 @@ fn test_std_frame_fc16() {
     ];
     let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x90, 2];
     let framebuf = gen_tcp_frame(&request);
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::TcpUdp, &mut result);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
@@ -1139,281 +1490,819 @@ fn test_std_frame_fc16() {
 }
 
 #[test]
-fn test_modbus_ascii() {
-    let ctx = ModbusStorageFull::new();
+fn test_std_frame_fc22() {
+    let mut ctx = CTX.write().unwrap();
+    ctx.clear_all();
     let mut result = Vec::new();
-    let mut ascii_result = Vec::new();
-    let request = [
-        0x3a, 0x30, 0x31, 0x30, 0x33, 0x30, 0x30, 0x30, 0x32, 0x30, 0x30, 0x30, 0x31, 0x46, 0x39,
-        0xd, 0xa,
-    ];
-    let response = [
-        0x3a, 0x30, 0x31, 0x30, 0x33, 0x30, 0x32, 0x30, 0x30, 0x30, 0x30, 0x46, 0x41, 0xd, 0xa,
-    ];
-    let mut framebuf: ModbusFrameBuf = [0; 256];
-    parse_ascii_frame(&request, request.len(), &mut framebuf, 0).unwrap();
-    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Ascii, &mut result);
+    // mask write register
+    ctx.set_holding(4, 0x12).unwrap();
+    let request = [1, 0x16, 0, 4, 0, 0xf2, 0, 0x25];
+    let response = [0x77, 0x55, 0, 0, 0, 8, 1, 0x16, 0, 4, 0, 0xf2, 0, 0x25];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert_eq!(frame.func, 0x16);
+    assert_eq!(frame.error, 0);
+    assert!(frame.response_required);
+    assert!(frame.processing_required);
+    assert!(!frame.readonly);
+    assert_eq!(
+        frame.changes(),
+        Some(Changes::Holdings { reg: 4, count: 1 })
+    );
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    assert_eq!(ctx.get_holding(4).unwrap(), 0x17);
+    ctx.set_holding(4, 0x12).unwrap();
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
     frame.parse().unwrap();
     assert!(frame.response_required);
     assert!(frame.processing_required);
     assert_eq!(frame.error, 0);
-    assert!(frame.readonly);
-    frame.process_read(&ctx).unwrap();
+    assert!(!frame.readonly);
+    frame.process_write(&mut *ctx).unwrap();
     assert_eq!(frame.error, 0);
     frame.finalize_response().unwrap();
-    generate_ascii_frame(result.as_slice(), &mut ascii_result).unwrap();
-    assert_eq!(ascii_result.as_slice(), response);
-}
-
-#[test]
-#[allow(clippy::too_many_lines)]
-fn test_std_client() {
-    let mut ctx = CTX.write().unwrap();
-    ctx.clear_discretes();
-    let coils = [
-        true, true, true, false, true, true, false, true, true, false, true,
-    ];
-    let holdings = [2345u16, 4723, 193, 3845, 8321, 1244, 8723, 2231, 48572];
-    let holdstr = "The Quick Brown Fox Jumps Over The Lazy Dog";
-    let protos = [ModbusProto::TcpUdp, ModbusProto::Rtu, ModbusProto::Ascii];
-
-    for proto in &protos {
-        // set coils bulk
-        ctx.clear_coils();
-        let mut mreq = ModbusRequest::new(2, *proto);
+    check_rtu_response(&result, &response);
+    assert_eq!(ctx.get_holding(4).unwrap(), 0x17);
+    // mask write register context oob
+    let request = [1, 0x16, 0x99, 0xe8, 0, 0xf2, 0, 0x25];
+    let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x96, 2];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    assert!(frame.processing_required);
+    assert_eq!(frame.error, 0);
+    assert!(!frame.readonly);
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(frame.error, 2);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    // client
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        ctx.set_holding(10, 0x12).unwrap();
+        let mut mreq = ModbusRequest::new(1, proto);
         let mut request = Vec::new();
-        mreq.generate_set_coils_bulk(100, &coils, &mut request)
+        mreq.generate_mask_write_register(10, 0xf2, 0x25, &mut request)
             .unwrap();
+        assert_eq!(
+            guess_request_frame_len(&request, proto).unwrap(),
+            u8::try_from(request.len()).unwrap()
+        );
         let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
-        } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
-        }
-        let mut frame = ModbusFrame::new(2, &framebuf, *proto, &mut response);
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
         frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(!frame.readonly);
         frame.process_write(&mut *ctx).unwrap();
-        assert_eq!(frame.error, 0);
         frame.finalize_response().unwrap();
+        assert_eq!(
+            guess_response_frame_len(&response, proto).unwrap(),
+            u8::try_from(response.len()).unwrap()
+        );
         mreq.parse_ok(&response).unwrap();
-        for i in 100..100 + coils.len() {
-            assert_eq!(
-                ctx.get_coil(u16::try_from(i).unwrap()).unwrap(),
-                coils[i - 100]
-            );
-        }
+        assert_eq!(
+            mreq.parse_slice(&response).unwrap(),
+            [0, 10, 0, 0xf2, 0, 0x25]
+        );
+        assert_eq!(ctx.get_holding(10).unwrap(), 0x17);
+    }
+}
 
-        // reading coils
-        let mut mreq = ModbusRequest::new(3, *proto);
-        let mut request = Vec::new();
-        mreq.generate_get_coils(100, u16::try_from(coils.len()).unwrap(), &mut request)
-            .unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
-        } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
-        }
-        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
-        frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(frame.readonly);
-        frame.process_read(&*ctx).unwrap();
-        assert_eq!(frame.error, 0);
-        frame.finalize_response().unwrap();
-        let mut result = Vec::new();
-        mreq.parse_bool(&response, &mut result).unwrap();
-        assert_eq!(result, coils);
-
-        // reading discretes
-        ctx.clear_coils();
-        ctx.clear_discretes();
-        for c in 200..200 + coils.len() {
-            ctx.set_discrete(u16::try_from(c).unwrap(), coils[c - 200])
-                .unwrap();
-        }
-        let mut mreq = ModbusRequest::new(3, *proto);
-        let mut request = Vec::new();
-        mreq.generate_get_discretes(200, u16::try_from(coils.len()).unwrap(), &mut request)
-            .unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
-        } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
-        }
-        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
-        frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(frame.readonly);
-        frame.process_read(&*ctx).unwrap();
-        assert_eq!(frame.error, 0);
-        frame.finalize_response().unwrap();
-        let mut result = Vec::new();
-        mreq.parse_bool(&response, &mut result).unwrap();
-        assert_eq!(result, coils);
-
-        // set single coil
-        ctx.clear_coils();
-        ctx.clear_discretes();
-        let mut mreq = ModbusRequest::new(4, *proto);
-        let mut request = Vec::new();
-        mreq.generate_set_coil(100, true, &mut request).unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
-        } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
-        }
-        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+#[test]
+#[allow(clippy::too_many_lines)]
+fn test_std_frame_fc23() {
+    let mut ctx = CTX.write().unwrap();
+    ctx.clear_all();
+    let mut result = Vec::new();
+    // read/write multiple registers
+    ctx.set_holding(2, 0x1234).unwrap();
+    let request = [1, 0x17, 0, 1, 0, 3, 0, 3, 0, 2, 4, 0x11, 0x22, 0x33, 0x44];
+    let response = [
+        0x77, 0x55, 0, 0, 0, 9, 1, 0x17, 6, 0, 0, 0x12, 0x34, 0x11, 0x22,
+    ];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert_eq!(frame.func, 0x17);
+    assert_eq!(frame.error, 0);
+    assert_eq!(frame.reg, 1);
+    assert_eq!(frame.count, 3);
+    assert!(frame.response_required);
+    assert!(frame.processing_required);
+    assert!(!frame.readonly);
+    assert_eq!(
+        frame.changes(),
+        Some(Changes::Holdings { reg: 3, count: 2 })
+    );
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    assert_eq!(ctx.get_holding(3).unwrap(), 0x1122);
+    assert_eq!(ctx.get_holding(4).unwrap(), 0x3344);
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    assert!(frame.processing_required);
+    assert_eq!(frame.error, 0);
+    assert!(!frame.readonly);
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    check_rtu_response(&result, &response);
+    // read range context oob, nothing is written
+    ctx.clear_holdings();
+    let request = [
+        1, 0x17, 0x27, 0x0e, 0, 3, 0, 3, 0, 2, 4, 0x11, 0x22, 0x33, 0x44,
+    ];
+    let response = [0x77, 0x55, 0, 0, 0, 3, 1, 0x97, 2];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.processing_required);
+    assert_eq!(frame.error, 0);
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(frame.error, 2);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    assert_eq!(ctx.get_holding(3).unwrap(), 0);
+    // write range context oob
+    let request = [
+        1, 0x17, 0, 1, 0, 3, 0x27, 0x0f, 0, 2, 4, 0x11, 0x22, 0x33, 0x44,
+    ];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(frame.error, 2);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    // illegal counts
+    for (read_count, write_count, bytes) in [(126, 1, 2), (0, 1, 2), (1, 122, 4), (1, 2, 2)] {
+        let mut request = vec![1, 0x17, 0, 1, 0, read_count, 0, 3, 0, write_count, bytes];
+        request.resize(11 + bytes as usize, 0);
+        let framebuf = gen_tcp_frame(&request);
+        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
         frame.parse().unwrap();
         assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(!frame.readonly);
-        frame.process_write(&mut *ctx).unwrap();
-        assert_eq!(frame.error, 0);
-        frame.finalize_response().unwrap();
-        mreq.parse_ok(&response).unwrap();
-        assert!(ctx.get_coil(100).unwrap());
-
-        // set coils oob
-        let mut mreq = ModbusRequest::new(4, *proto);
+        assert!(!frame.processing_required);
+        assert_eq!(frame.error, 3);
+    }
+    // client
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        ctx.clear_holdings();
+        ctx.set_holding(100, 7).unwrap();
+        let mut mreq = ModbusRequest::new(1, proto);
         let mut request = Vec::new();
-        mreq.generate_set_coil(10001, true, &mut request).unwrap();
+        mreq.generate_read_write_multiple_registers(100, 3, 101, &[8, 9], &mut request)
+            .unwrap();
+        assert_eq!(
+            guess_request_frame_len(&request, proto).unwrap(),
+            u8::try_from(request.len()).unwrap()
+        );
         let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
-        } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
-        }
-        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
         frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(!frame.readonly);
         frame.process_write(&mut *ctx).unwrap();
-        assert_eq!(frame.error, 2);
         frame.finalize_response().unwrap();
         assert_eq!(
-            mreq.parse_ok(&response).err().unwrap(),
-            ErrorKind::IllegalDataAddress
+            guess_response_frame_len(&response, proto).unwrap(),
+            u8::try_from(response.len()).unwrap()
         );
+        let mut result = Vec::new();
+        mreq.parse_u16(&response, &mut result).unwrap();
+        assert_eq!(result, [7, 8, 9]);
+    }
+}
 
-        // set holdings bulk
-        ctx.clear_holdings();
-        let mut mreq = ModbusRequest::new(2, *proto);
-        let mut request = Vec::new();
-        mreq.generate_set_holdings_bulk(100, &holdings, &mut request)
-            .unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+#[test]
+fn test_std_frame_multi_unit() {
+    let mut ctx = CTX.write().unwrap();
+    let mut result = Vec::new();
+    let unit_ids = [3, 5, 7];
+    ctx.set_holding(100, 0x1234).unwrap();
+    // addressed to an id in the set
+    let request = [5, 3, 0, 100, 0, 1];
+    let response = [0x77, 0x55, 0, 0, 0, 5, 5, 3, 2, 0x12, 0x34];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new_multi(&unit_ids, &framebuf, ModbusProto::Tcp, &mut result);
+    assert!(frame.accepts_unit(7));
+    assert!(!frame.accepts_unit(1));
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    assert_eq!(frame.unit_id, 5);
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new_multi(&unit_ids, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result[0], 5);
+    // exceptions echo the matched id too
+    let request = [7, 3, 0, 100, 0, 200];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new_multi(&unit_ids, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 7, 0x83, 3]);
+    // outside the set
+    let request = [4, 3, 0, 100, 0, 1];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new_multi(&unit_ids, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(!frame.response_required);
+    assert!(!frame.processing_required);
+    assert!(result.is_empty());
+    // broadcast
+    let request = [0, 6, 0, 100, 0x55, 0x66];
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new_multi(&unit_ids, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    assert!(!frame.response_required);
+    assert!(frame.processing_required);
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(ctx.get_holding(100).unwrap(), 0x5566);
+}
+
+#[test]
+fn test_std_frame_dyn_context() {
+    let mut storage = ModbusStorageSmall::default();
+    for i in 0..20 {
+        storage.set_coil(i, i % 3 == 0).unwrap();
+        storage.set_discrete(i, i % 2 == 0).unwrap();
+        storage.set_holding(i, i * 0x101).unwrap();
+        storage.set_input(i, i * 0x202).unwrap();
+    }
+    let requests: [&[u8]; 7] = [
+        &[1, 1, 0, 2, 0, 11],
+        &[1, 2, 0, 1, 0, 8],
+        &[1, 3, 0, 5, 0, 3],
+        &[1, 4, 0, 0, 0, 2],
+        // out of the context
+        &[1, 3, 0x03, 0xe7, 0, 2],
+        &[1, 6, 0, 7, 0x12, 0x34],
+        &[1, 0x17, 0, 6, 0, 2, 0, 8, 0, 1, 2, 0x55, 0x66],
+    ];
+    for request in requests {
+        let framebuf = gen_tcp_frame(request);
+        let mut expected = Vec::new();
+        let mut ctx = ModbusStorageSmall::default();
+        ctx.set_coils_bulk(0, &storage.coils[..20]).unwrap();
+        ctx.set_discretes_bulk(0, &storage.discretes[..20]).unwrap();
+        ctx.set_holdings_bulk(0, &storage.holdings[..20]).unwrap();
+        ctx.set_inputs_bulk(0, &storage.inputs[..20]).unwrap();
+        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut expected);
+        frame.parse().unwrap();
+        if frame.readonly {
+            frame.process_read(&ctx).unwrap();
         } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
+            frame.process_write(&mut ctx).unwrap();
         }
-        let mut frame = ModbusFrame::new(2, &framebuf, *proto, &mut response);
-        frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(!frame.readonly);
-        frame.process_write(&mut *ctx).unwrap();
-        assert_eq!(frame.error, 0);
         frame.finalize_response().unwrap();
-        mreq.parse_ok(&response).unwrap();
-        for i in 100..100 + holdings.len() {
-            assert_eq!(
-                ctx.get_holding(u16::try_from(i).unwrap()).unwrap(),
-                holdings[i - 100]
-            );
-        }
-
-        // reading holdings
-        let mut mreq = ModbusRequest::new(3, *proto);
-        let mut request = Vec::new();
-        mreq.generate_get_holdings(100, u16::try_from(holdings.len()).unwrap(), &mut request)
-            .unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        let mut result = Vec::new();
+        let dyn_ctx: &mut dyn ModbusContext = &mut storage;
+        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+        frame.parse().unwrap();
+        if frame.readonly {
+            frame.process_read_dyn(dyn_ctx).unwrap();
         } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
+            frame.process_write_dyn(dyn_ctx).unwrap();
         }
-        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
-        frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(frame.readonly);
-        frame.process_read(&*ctx).unwrap();
-        assert_eq!(frame.error, 0);
         frame.finalize_response().unwrap();
-        let mut result = Vec::new();
-        mreq.parse_u16(&response, &mut result).unwrap();
-        assert_eq!(result, holdings);
+        assert_eq!(result, expected);
+    }
+    assert_eq!(storage.get_holding(7).unwrap(), 0x1234);
+    assert_eq!(storage.get_holding(8).unwrap(), 0x5566);
+}
 
-        // set holdings string
-        ctx.clear_holdings();
-        let mut mreq = ModbusRequest::new(2, *proto);
-        let mut request = Vec::new();
-        mreq.generate_set_holdings_string(100, holdstr, &mut request)
-            .unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
+#[test]
+fn test_std_frame_changed_values() {
+    type Case<'a> = (&'a [u8], &'a [(u16, RegValue)]);
+    let cases: [Case; 7] = [
+        (&[1, 5, 0, 3, 0xff, 0], &[(3, RegValue::Coil(true))]),
+        (&[1, 5, 0, 3, 0, 0], &[(3, RegValue::Coil(false))]),
+        (&[1, 6, 0, 4, 0x12, 0x34], &[(4, RegValue::Holding(0x1234))]),
+        (
+            &[1, 15, 0, 10, 0, 10, 2, 0b1000_0101, 0b10],
+            &[
+                (10, RegValue::Coil(true)),
+                (11, RegValue::Coil(false)),
+                (12, RegValue::Coil(true)),
+                (13, RegValue::Coil(false)),
+                (14, RegValue::Coil(false)),
+                (15, RegValue::Coil(false)),
+                (16, RegValue::Coil(false)),
+                (17, RegValue::Coil(true)),
+                (18, RegValue::Coil(false)),
+                (19, RegValue::Coil(true)),
+            ],
+        ),
+        (
+            &[1, 16, 0, 20, 0, 2, 4, 0x11, 0x22, 0x33, 0x44],
+            &[
+                (20, RegValue::Holding(0x1122)),
+                (21, RegValue::Holding(0x3344)),
+            ],
+        ),
+        (
+            &[1, 0x17, 0, 1, 0, 1, 0, 30, 0, 1, 2, 0x55, 0x66],
+            &[(30, RegValue::Holding(0x5566))],
+        ),
+        (&[1, 3, 0, 1, 0, 10], &[]),
+    ];
+    let mut result = Vec::new();
+    for (request, expected) in cases {
+        for (framebuf, proto) in [
+            (gen_tcp_frame(request), ModbusProto::Tcp),
+            (gen_rtu_frame(request), ModbusProto::Rtu),
+        ] {
+            let mut frame = ModbusFrame::new(1, &framebuf, proto, &mut result);
+            frame.parse().unwrap();
+            assert_eq!(frame.changed_values().collect::<Vec<_>>(), expected);
+        }
+    }
+    // invalid coil value
+    let framebuf = gen_tcp_frame(&[1, 5, 0, 3, 0x12, 0]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    let mut ctx = CTX.write().unwrap();
+    frame.process_write(&mut *ctx).unwrap();
+    assert_eq!(frame.changed_values().count(), 0);
+}
+
+#[test]
+fn test_std_frame_custom() {
+    fn handler(func: u8, data: &[u8], response: &mut Vec<u8>) -> Result<(), ErrorKind> {
+        assert_eq!(func, 0x41);
+        if data[0] == 0 {
+            return Err(ErrorKind::IllegalDataValue);
+        }
+        response.push(u8::try_from(data.len()).unwrap());
+        for v in data.iter().rev() {
+            response.push(*v);
+        }
+        Ok(())
+    }
+    let mut result = Vec::new();
+    let request = [1, 0x41, 1, 2, 3, 4];
+    let response = [0x77, 0x55, 0, 0, 0, 7, 1, 0x41, 4, 4, 3, 2, 1];
+    // no handler
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    assert!(!frame.processing_required);
+    assert_eq!(frame.error, 1);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0xc1, 1]);
+    // TCP
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert_eq!(
+        frame.process_custom(3, handler).err().unwrap(),
+        ErrorKind::FrameBroken
+    );
+    frame.process_custom(4, handler).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    // RTU
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    assert_eq!(
+        frame.process_custom(3, handler).err().unwrap(),
+        ErrorKind::FrameCRCError
+    );
+    frame.process_custom(4, handler).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    check_rtu_response(&result, &response);
+    // handler error
+    let framebuf = gen_tcp_frame(&[1, 0x41, 0, 2, 3, 4]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_custom(4, handler).unwrap();
+    assert_eq!(frame.error, 3);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0xc1, 3]);
+    // not a custom function
+    let framebuf = gen_tcp_frame(&[1, 6, 0, 1, 0, 2]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_custom(4, |_, _, _| panic!()).unwrap();
+    // broadcast
+    let framebuf = gen_tcp_frame(&[0, 0x41, 1, 2, 3, 4]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(!frame.response_required);
+    frame.process_custom(4, handler).unwrap();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn test_std_frame_diagnostics() {
+    let ctx = CTX.read().unwrap();
+    let mut counters = DiagnosticCounters::default();
+    let mut result = Vec::new();
+    // return query data
+    let request = [1, 8, 0, 0, 0xa5, 0x37];
+    let response = [0x77, 0x55, 0, 0, 0, 6, 1, 8, 0, 0, 0xa5, 0x37];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    let parse_result = frame.parse();
+    parse_result.unwrap();
+    assert!(frame.response_required);
+    assert!(frame.processing_required);
+    assert!(frame.readonly);
+    frame.process_read(&*ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    counters.count(&frame, &parse_result);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    let mut framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    let parse_result = frame.parse();
+    frame.process_read(&*ctx).unwrap();
+    counters.count(&frame, &parse_result);
+    frame.finalize_response().unwrap();
+    check_rtu_response(&result, &response);
+    // CRC error
+    framebuf[6] ^= 0xff;
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    let parse_result = frame.parse();
+    assert_eq!(parse_result, Err(ErrorKind::FrameCRCError));
+    counters.count(&frame, &parse_result);
+    // broadcast
+    let framebuf = gen_tcp_frame(&[0, 8, 0, 0, 0xa5, 0x37]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    let parse_result = frame.parse();
+    assert!(!frame.response_required);
+    counters.count(&frame, &parse_result);
+    // unhandled sub-function
+    let framebuf = gen_tcp_frame(&[1, 8, 0, 0x0b, 0, 0]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    let parse_result = frame.parse();
+    assert!(!frame.processing_required);
+    frame
+        .process_diagnostics(|_, _| Err(ErrorKind::IllegalFunction))
+        .unwrap();
+    counters.count(&frame, &parse_result);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0x88, 1]);
+    assert_eq!(
+        counters,
+        DiagnosticCounters {
+            bus_messages: 5,
+            bus_communication_errors: 1,
+            bus_exception_errors: 1,
+            server_messages: 4,
+            server_no_responses: 1,
+        }
+    );
+    // client, counters
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        for (sub_function, data, reply) in [
+            (consts::MODBUS_DIAG_RETURN_QUERY_DATA, 0x1234, 0x1234),
+            (consts::MODBUS_DIAG_RETURN_BUS_MESSAGE_COUNT, 0, 5),
+            (
+                consts::MODBUS_DIAG_RETURN_BUS_COMMUNICATION_ERROR_COUNT,
+                0,
+                1,
+            ),
+            (consts::MODBUS_DIAG_CLEAR_COUNTERS, 0, 0),
+            (consts::MODBUS_DIAG_RETURN_SERVER_MESSAGE_COUNT, 0, 0),
+        ] {
+            let mut mreq = ModbusRequest::new(1, proto);
+            let mut request = Vec::new();
+            mreq.generate_diagnostics(sub_function, data, &mut request)
+                .unwrap();
+            let mut response = Vec::new();
+            let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+            frame.parse().unwrap();
+            if frame.processing_required {
+                frame.process_read(&*ctx).unwrap();
+            }
+            frame
+                .process_diagnostics(|sub_function, data| counters.process(sub_function, data))
+                .unwrap();
+            frame.finalize_response().unwrap();
+            assert_eq!(
+                guess_response_frame_len(&response, proto).unwrap(),
+                u8::try_from(response.len()).unwrap()
+            );
+            assert_eq!(
+                mreq.parse_diagnostics(&response).unwrap(),
+                (sub_function, reply)
+            );
+        }
+        counters.bus_messages = 5;
+        counters.bus_communication_errors = 1;
+    }
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_diagnostics(0x42, 0, &mut request).unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame
+        .process_diagnostics(|sub_function, data| counters.process(sub_function, data))
+        .unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        mreq.parse_diagnostics(&response).err().unwrap(),
+        ErrorKind::IllegalFunction
+    );
+}
+
+#[test]
+fn test_std_frame_comm_event_counter() {
+    let mut ctx = CTX.write().unwrap();
+    let mut counters = CommEventCounters::default();
+    // not supported without counters
+    let mut result = Vec::new();
+    let framebuf = gen_tcp_frame(&[1, 0x0b]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(!frame.processing_required);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0x8b, 1]);
+    for (request, event_count, message_count) in [
+        // read holdings
+        (vec![1, 3, 0, 0, 0, 1], 1, 1),
+        // write holding
+        (vec![1, 6, 0, 1, 0, 2], 2, 2),
+        // exception
+        (vec![1, 3, 0xff, 0xff, 0, 1], 2, 3),
+        // another unit
+        (vec![2, 3, 0, 0, 0, 1], 2, 3),
+        // broadcast write
+        (vec![0, 6, 0, 1, 0, 2], 2, 4),
+        // comm event counter poll
+        (vec![1, 0x0b], 2, 5),
+    ] {
+        let framebuf = gen_rtu_frame(&request);
+        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result)
+            .with_comm_counters(&mut counters);
+        frame.parse().unwrap();
+        if frame.processing_required {
+            if frame.readonly {
+                frame.process_read(&*ctx).unwrap();
+            } else {
+                frame.process_write(&mut *ctx).unwrap();
+            }
+        }
+        if frame.response_required {
+            frame.finalize_response().unwrap();
+        }
+        assert_eq!(counters.event_count, event_count);
+        assert_eq!(counters.message_count, message_count);
+    }
+    // client
+    counters.busy = true;
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        let mut request = Vec::new();
+        mreq.generate_get_comm_event_counter(&mut request).unwrap();
+        assert_eq!(
+            guess_request_frame_len(&request, proto).unwrap(),
+            u8::try_from(request.len()).unwrap()
+        );
+        let mut response = Vec::new();
+        let mut frame =
+            ModbusFrame::new(1, &request, proto, &mut response).with_comm_counters(&mut counters);
+        frame.parse().unwrap();
+        frame.process_read(&*ctx).unwrap();
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            guess_response_frame_len(&response, proto).unwrap(),
+            u8::try_from(response.len()).unwrap()
+        );
+        assert_eq!(
+            mreq.parse_comm_event_counter(&response).unwrap(),
+            (0xffff, 2)
+        );
+    }
+    assert_eq!(counters.message_count, 7);
+}
+
+#[test]
+fn test_std_frame_report_server_id() {
+    let mut result = Vec::new();
+    let request = [1, 0x11];
+    let response = [0x77, 0x55, 0, 0, 0, 7, 1, 0x11, 4, b'G', b'W', b'1', 0xff];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    frame.process_report_server_id(b"GW1", true).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    frame.process_report_server_id(b"GW1", true).unwrap();
+    frame.finalize_response().unwrap();
+    check_rtu_response(&result, &response);
+    // not processed
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0x91, 1]);
+    // client
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        for running in [false, true] {
+            let mut mreq = ModbusRequest::new(1, proto);
+            let mut request = Vec::new();
+            mreq.generate_report_server_id(&mut request).unwrap();
+            assert_eq!(
+                guess_request_frame_len(&request, proto).unwrap(),
+                u8::try_from(request.len()).unwrap()
+            );
+            let mut response = Vec::new();
+            let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+            frame.parse().unwrap();
+            frame.process_report_server_id(b"gateway", running).unwrap();
+            frame.finalize_response().unwrap();
+            assert_eq!(
+                guess_response_frame_len(&response, proto).unwrap(),
+                u8::try_from(response.len()).unwrap()
+            );
+            let (server_id, run_indicator) = mreq.parse_server_id(&response).unwrap();
+            assert_eq!(server_id, b"gateway");
+            assert_eq!(run_indicator, running);
+        }
+    }
+}
+
+#[test]
+fn test_std_frame_device_id() {
+    let mut device_id = DeviceIdentification::new(b"VN", b"PC", b"V1").unwrap();
+    device_id
+        .set_object(consts::MODBUS_DEVICE_ID_PRODUCT_NAME, b"PN")
+        .unwrap();
+    let mut result = Vec::new();
+    // basic stream
+    let request = [1, 0x2b, 0x0e, 1, 0];
+    let response = [
+        0x77, 0x55, 0, 0, 0, 20, 1, 0x2b, 0x0e, 1, 0x82, 0, 0, 3, 0, 2, b'V', b'N', 1, 2, b'P',
+        b'C', 2, 2, b'V', b'1',
+    ];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    frame.process_read_device_id(&device_id).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    frame.process_read_device_id(&device_id).unwrap();
+    frame.finalize_response().unwrap();
+    check_rtu_response(&result, &response);
+    // not processed
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0xab, 1]);
+    // regular stream, unknown object id restarts from the beginning
+    let framebuf = gen_tcp_frame(&[1, 0x2b, 0x0e, 2, 0x42]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_read_device_id(&device_id).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result[13], 4);
+    assert_eq!(&result[result.len() - 4..], [4, 2, b'P', b'N']);
+    // individual access
+    let framebuf = gen_tcp_frame(&[1, 0x2b, 0x0e, 4, 1]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_read_device_id(&device_id).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        result.as_slice(),
+        [0x77, 0x55, 0, 0, 0, 12, 1, 0x2b, 0x0e, 4, 0x82, 0, 0, 1, 1, 2, b'P', b'C']
+    );
+    let framebuf = gen_tcp_frame(&[1, 0x2b, 0x0e, 4, 3]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_read_device_id(&device_id).unwrap();
+    assert_eq!(frame.error, 2);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0xab, 2]);
+    // invalid read code
+    let framebuf = gen_tcp_frame(&[1, 0x2b, 0x0e, 5, 0]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.process_read_device_id(&device_id).unwrap();
+    assert_eq!(frame.error, 3);
+    // client, with continuation
+    let long = [b'x'; 100];
+    let mut device_id = DeviceIdentification::new(&long, &long, &long).unwrap();
+    device_id.set_object(0x80, b"EXT").unwrap();
+    assert_eq!(device_id.conformity_level(), 0x83);
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        let mut objects = Vec::new();
+        let mut object_id = 0;
+        loop {
+            let mut request = Vec::new();
+            mreq.generate_read_device_id(3, object_id, &mut request)
+                .unwrap();
+            assert_eq!(
+                guess_request_frame_len(&request, proto).unwrap(),
+                u8::try_from(request.len()).unwrap()
+            );
+            let mut response = Vec::new();
+            let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+            frame.parse().unwrap();
+            frame.process_read_device_id(&device_id).unwrap();
+            frame.finalize_response().unwrap();
+            let mut result = Vec::new();
+            let info = mreq.parse_device_id(&response, &mut result).unwrap();
+            assert_eq!(info.conformity_level, 0x83);
+            for (id, value) in result {
+                objects.push((id, value.to_vec()));
+            }
+            if !info.more_follows {
+                break;
+            }
+            assert_eq!(info.next_object_id, 2);
+            object_id = info.next_object_id;
+        }
+        assert_eq!(
+            objects,
+            [
+                (0, long.to_vec()),
+                (1, long.to_vec()),
+                (2, long.to_vec()),
+                (0x80, b"EXT".to_vec())
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_modbus_ascii() {
+    let ctx = ModbusStorageFull::new();
+    let mut result = Vec::new();
+    let mut ascii_result = Vec::new();
+    let request = [
+        0x3a, 0x30, 0x31, 0x30, 0x33, 0x30, 0x30, 0x30, 0x32, 0x30, 0x30, 0x30, 0x31, 0x46, 0x39,
+        0xd, 0xa,
+    ];
+    let response = [
+        0x3a, 0x30, 0x31, 0x30, 0x33, 0x30, 0x32, 0x30, 0x30, 0x30, 0x30, 0x46, 0x41, 0xd, 0xa,
+    ];
+    let mut framebuf: ModbusFrameBuf = [0; 256];
+    parse_ascii_frame(&request, request.len(), &mut framebuf, 0).unwrap();
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Ascii, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    assert!(frame.processing_required);
+    assert_eq!(frame.error, 0);
+    assert!(frame.readonly);
+    frame.process_read(&ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    generate_ascii_frame(result.as_slice(), &mut ascii_result).unwrap();
+    assert_eq!(ascii_result.as_slice(), response);
+}
+
+#[test]
+#[allow(clippy::too_many_lines)]
+fn test_std_client() {
+    let mut ctx = CTX.write().unwrap();
+    ctx.clear_discretes();
+    let coils = [
+        true, true, true, false, true, true, false, true, true, false, true,
+    ];
+    let holdings = [2345u16, 4723, 193, 3845, 8321, 1244, 8723, 2231, 48572];
+    let holdstr = "The Quick Brown Fox Jumps Over The Lazy Dog";
+    let protos = [ModbusProto::Tcp, ModbusProto::Rtu, ModbusProto::Ascii];
+
+    for proto in &protos {
+        // set coils bulk
+        ctx.clear_coils();
+        let mut mreq = ModbusRequest::new(2, *proto);
+        let mut request = Vec::new();
+        mreq.generate_set_coils_bulk(100, &coils, &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
             generate_ascii_frame(&request, &mut ascii_frame).unwrap();
             for c in &mut framebuf {
                 *c = 0;
@@ -1432,11 +2321,17 @@ fn test_std_client() {
         assert_eq!(frame.error, 0);
         frame.finalize_response().unwrap();
         mreq.parse_ok(&response).unwrap();
+        for i in 100..100 + coils.len() {
+            assert_eq!(
+                ctx.get_coil(u16::try_from(i).unwrap()).unwrap(),
+                coils[i - 100]
+            );
+        }
 
-        // reading holdings string
+        // reading coils
         let mut mreq = ModbusRequest::new(3, *proto);
         let mut request = Vec::new();
-        mreq.generate_get_holdings(100, u16::try_from(holdstr.len()).unwrap(), &mut request)
+        mreq.generate_get_coils(100, u16::try_from(coils.len()).unwrap(), &mut request)
             .unwrap();
         let mut response = Vec::new();
         let mut framebuf: ModbusFrameBuf = [0; 256];
@@ -1453,111 +2348,3656 @@ fn test_std_client() {
         let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
         frame.parse().unwrap();
         assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(frame.readonly);
-        frame.process_read(&*ctx).unwrap();
-        assert_eq!(frame.error, 0);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(frame.readonly);
+        frame.process_read(&*ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        let mut result = Vec::new();
+        mreq.parse_bool(&response, &mut result).unwrap();
+        assert_eq!(result, coils);
+
+        // reading discretes
+        ctx.clear_coils();
+        ctx.clear_discretes();
+        for c in 200..200 + coils.len() {
+            ctx.set_discrete(u16::try_from(c).unwrap(), coils[c - 200])
+                .unwrap();
+        }
+        let mut mreq = ModbusRequest::new(3, *proto);
+        let mut request = Vec::new();
+        mreq.generate_get_discretes(200, u16::try_from(coils.len()).unwrap(), &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(frame.readonly);
+        frame.process_read(&*ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        let mut result = Vec::new();
+        mreq.parse_bool(&response, &mut result).unwrap();
+        assert_eq!(result, coils);
+
+        // set single coil
+        ctx.clear_coils();
+        ctx.clear_discretes();
+        let mut mreq = ModbusRequest::new(4, *proto);
+        let mut request = Vec::new();
+        mreq.generate_set_coil(100, true, &mut request).unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(!frame.readonly);
+        frame.process_write(&mut *ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        mreq.parse_ok(&response).unwrap();
+        assert!(ctx.get_coil(100).unwrap());
+
+        // set coils oob
+        let mut mreq = ModbusRequest::new(4, *proto);
+        let mut request = Vec::new();
+        mreq.generate_set_coil(10001, true, &mut request).unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(!frame.readonly);
+        frame.process_write(&mut *ctx).unwrap();
+        assert_eq!(frame.error, 2);
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            mreq.parse_ok(&response).err().unwrap(),
+            ErrorKind::IllegalDataAddress
+        );
+
+        // set holdings bulk
+        ctx.clear_holdings();
+        let mut mreq = ModbusRequest::new(2, *proto);
+        let mut request = Vec::new();
+        mreq.generate_set_holdings_bulk(100, &holdings, &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(2, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(!frame.readonly);
+        frame.process_write(&mut *ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        mreq.parse_ok(&response).unwrap();
+        for i in 100..100 + holdings.len() {
+            assert_eq!(
+                ctx.get_holding(u16::try_from(i).unwrap()).unwrap(),
+                holdings[i - 100]
+            );
+        }
+
+        // reading holdings
+        let mut mreq = ModbusRequest::new(3, *proto);
+        let mut request = Vec::new();
+        mreq.generate_get_holdings(100, u16::try_from(holdings.len()).unwrap(), &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(frame.readonly);
+        frame.process_read(&*ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        let mut result = Vec::new();
+        mreq.parse_u16(&response, &mut result).unwrap();
+        assert_eq!(result, holdings);
+
+        // set holdings string
+        ctx.clear_holdings();
+        let mut mreq = ModbusRequest::new(2, *proto);
+        let mut request = Vec::new();
+        mreq.generate_set_holdings_string(100, holdstr, &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(2, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(!frame.readonly);
+        frame.process_write(&mut *ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        mreq.parse_ok(&response).unwrap();
+
+        // reading holdings string
+        let mut mreq = ModbusRequest::new(3, *proto);
+        let mut request = Vec::new();
+        mreq.generate_get_holdings(100, u16::try_from(holdstr.len()).unwrap(), &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(frame.readonly);
+        frame.process_read(&*ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        let mut result = String::new();
+        mreq.parse_string(&response, &mut result).unwrap();
+        assert_eq!(result, holdstr);
+
+        // reading inputs
+        ctx.clear_holdings();
+        ctx.clear_inputs();
+        for c in 200..200 + holdings.len() {
+            ctx.set_input(u16::try_from(c).unwrap(), holdings[c - 200])
+                .unwrap();
+        }
+        let mut mreq = ModbusRequest::new(3, *proto);
+        let mut request = Vec::new();
+        mreq.generate_get_inputs(200, u16::try_from(holdings.len()).unwrap(), &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(frame.readonly);
+        frame.process_read(&*ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        let mut result = Vec::new();
+        mreq.parse_u16(&response, &mut result).unwrap();
+        assert_eq!(result, holdings);
+
+        // set single holding
+        ctx.clear_holdings();
+        ctx.clear_inputs();
+        let mut mreq = ModbusRequest::new(4, *proto);
+        let mut request = Vec::new();
+        mreq.generate_set_holding(100, 7777, &mut request).unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(!frame.readonly);
+        frame.process_write(&mut *ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        mreq.parse_ok(&response).unwrap();
+        assert!(ctx.get_coil(100).unwrap());
+
+        // set holding oob
+        let mut mreq = ModbusRequest::new(4, *proto);
+        let mut request = Vec::new();
+        mreq.generate_set_holding(10001, 0x7777, &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        if *proto == ModbusProto::Rtu {
+            let mut ascii_frame = Vec::new();
+            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+            for c in &mut framebuf {
+                *c = 0;
+            }
+            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        } else {
+            framebuf[..request.len()].copy_from_slice(&request[..]);
+        }
+        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(frame.processing_required);
+        assert_eq!(frame.error, 0);
+        assert!(!frame.readonly);
+        frame.process_write(&mut *ctx).unwrap();
+        assert_eq!(frame.error, 2);
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            mreq.parse_ok(&response).err().unwrap(),
+            ErrorKind::IllegalDataAddress
+        );
+    }
+}
+
+#[test]
+fn test_std_client_parse_f64() {
+    let values = [
+        1_234_567.891_011f64,
+        -0.5,
+        f64::from_bits(0x7ff8_0000_dead_beef),
+    ];
+    for proto in &[ModbusProto::Tcp, ModbusProto::Rtu] {
+        let mut ctx = CTX.write().unwrap();
+        ctx.clear_holdings();
+        for (i, v) in values.iter().enumerate() {
+            ctx.set_holdings_from_f64(u16::try_from(300 + i * 4).unwrap(), *v)
+                .unwrap();
+        }
+        let mut mreq = ModbusRequest::new(1, *proto);
+        let mut request = Vec::new();
+        mreq.generate_get_holdings(300, 12, &mut request).unwrap();
+        let mut framebuf: ModbusFrameBuf = [0; 256];
+        framebuf[..request.len()].copy_from_slice(&request[..]);
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &framebuf, *proto, &mut response);
+        frame.parse().unwrap();
+        frame.process_read(&*ctx).unwrap();
+        assert_eq!(frame.error, 0);
+        frame.finalize_response().unwrap();
+        let mut result = Vec::new();
+        mreq.parse_f64(&response, &mut result).unwrap();
+        assert_eq!(result.len(), values.len());
+        for (r, v) in result.iter().zip(values.iter()) {
+            assert_eq!(r.to_bits(), v.to_bits());
+        }
+    }
+}
+
+#[test]
+fn test_std_client_parse_u64_i64() {
+    // holdings 0x0102 0x0304 0x0506 0x0708 and 0xFFFF 0xFFFF 0xFFFF 0xFFFE, trailing odd byte
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 8, &mut request).unwrap();
+    let mut data = vec![
+        0x01, 0x03, 0x10, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xFF, 0xFF, 0xFF, 0xFF,
+        0xFF, 0xFF, 0xFF, 0xFE,
+    ];
+    let crc = State::<MODBUS>::calculate(&data).to_le_bytes();
+    let mut response = data.clone();
+    response.extend_from_slice(&crc);
+    let mut result: Vec<u64> = Vec::new();
+    mreq.parse_u64(&response, &mut result).unwrap();
+    assert_eq!(result, vec![0x0102_0304_0506_0708, 0xFFFF_FFFF_FFFF_FFFE]);
+    let mut result: Vec<i64> = Vec::new();
+    mreq.parse_i64(&response, &mut result).unwrap();
+    assert_eq!(result, vec![0x0102_0304_0506_0708, -2]);
+    let mut result: Vec<i32> = Vec::new();
+    mreq.parse_i32(&response, &mut result).unwrap();
+    assert_eq!(result, vec![0x0102_0304, 0x0506_0708, -1, -2]);
+    // the byte count must match the requested count
+    mreq.count = 4;
+    let mut result: Vec<u64> = Vec::new();
+    assert_eq!(
+        mreq.parse_u64(&response, &mut result).err().unwrap(),
+        ErrorKind::FrameBroken
+    );
+    // a partial value at the end is ignored
+    mreq.count = 7;
+    data[2] = 0x0E;
+    data.truncate(17);
+    let crc = State::<MODBUS>::calculate(&data).to_le_bytes();
+    let mut response = data.clone();
+    response.extend_from_slice(&crc);
+    let mut result: Vec<u64> = Vec::new();
+    mreq.parse_u64(&response, &mut result).unwrap();
+    assert_eq!(result, vec![0x0102_0304_0506_0708]);
+}
+
+#[test]
+fn test_std_client_string_swap() {
+    let mut ctx = CTX.write().unwrap();
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        for holdstr in ["The Q", "The Qu"] {
+            ctx.clear_holdings();
+            let mut mreq = ModbusRequest::new(1, proto);
+            let mut request = Vec::new();
+            mreq.generate_set_holdings_string_with_swap(100, holdstr, true, &mut request)
+                .unwrap();
+            let mut response = Vec::new();
+            let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+            frame.parse().unwrap();
+            frame.process_write(&mut *ctx).unwrap();
+            frame.finalize_response().unwrap();
+            mreq.parse_ok(&response).unwrap();
+            assert_eq!(ctx.get_holding(100).unwrap(), u16::from_be_bytes(*b"hT"));
+            assert_eq!(ctx.get_holding(101).unwrap(), u16::from_be_bytes(*b" e"));
+            // the trailing pad byte
+            if holdstr.len() % 2 == 1 {
+                assert_eq!(ctx.get_holding(102).unwrap(), u16::from_be_bytes([0, b'Q']));
+            }
+            let mut request = Vec::new();
+            mreq.generate_get_holdings(100, 3, &mut request).unwrap();
+            let mut response = Vec::new();
+            let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+            frame.parse().unwrap();
+            frame.process_read(&*ctx).unwrap();
+            frame.finalize_response().unwrap();
+            let mut result = String::new();
+            mreq.parse_string_with_swap(&response, true, &mut result)
+                .unwrap();
+            assert_eq!(result, holdstr);
+            mreq.parse_string(&response, &mut result).unwrap();
+            assert!(result.starts_with("hT e"));
+        }
+    }
+}
+
+#[test]
+fn test_std_client_auto_tr_id() {
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 0xfffe);
+    mreq.auto_increment_tr_id(true);
+    let mut tr_ids = Vec::new();
+    for _ in 0..3 {
+        let mut request = Vec::new();
+        mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+        tr_ids.push(u16::from_be_bytes([request[0], request[1]]));
+        assert_eq!(tr_ids[tr_ids.len() - 1], mreq.tr_id);
+    }
+    assert_eq!(tr_ids, [0xffff, 0, 1]);
+    // RTU
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    mreq.auto_increment_tr_id(true);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    assert_eq!(mreq.tr_id, 1);
+}
+
+#[test]
+fn test_std_client_parse_response_typed() {
+    let mut ctx = CTX.write().unwrap();
+    ctx.clear_all();
+    ctx.set_coils_bulk(10, &[true, false, true]).unwrap();
+    ctx.set_holdings_bulk(10, &[1, 2, 3]).unwrap();
+    ctx.set_inputs_bulk(10, &[4, 5]).unwrap();
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        for (func, expected) in [
+            (1, ResponseData::Coils(vec![true, false, true])),
+            (2, ResponseData::Discretes(vec![false, false, false])),
+            (3, ResponseData::Holdings(vec![1, 2, 3])),
+            (4, ResponseData::Inputs(vec![4, 5])),
+            (6, ResponseData::WriteOk),
+        ] {
+            let mut request = Vec::new();
+            match func {
+                1 => mreq.generate_get_coils(10, 3, &mut request),
+                2 => mreq.generate_get_discretes(10, 3, &mut request),
+                3 => mreq.generate_get_holdings(10, 3, &mut request),
+                4 => mreq.generate_get_inputs(10, 2, &mut request),
+                _ => mreq.generate_set_holding(10, 1, &mut request),
+            }
+            .unwrap();
+            let mut response = Vec::new();
+            let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+            frame.parse().unwrap();
+            if frame.readonly {
+                frame.process_read(&*ctx).unwrap();
+            } else {
+                frame.process_write(&mut *ctx).unwrap();
+            }
+            frame.finalize_response().unwrap();
+            assert_eq!(mreq.parse_response_typed(&response).unwrap(), expected);
+        }
+        let mut request = Vec::new();
+        mreq.generate_report_server_id(&mut request).unwrap();
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        frame.process_report_server_id(b"ID", true).unwrap();
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            mreq.parse_response_typed(&response).unwrap(),
+            ResponseData::ServerId(b"ID".to_vec(), true)
+        );
+        assert_eq!(
+            mreq.parse_response_typed_ref(&response).unwrap(),
+            ResponseDataRef::ServerId(b"ID", true)
+        );
+        // Modbus error
+        let mut request = Vec::new();
+        mreq.generate_get_holdings(0xffff, 1, &mut request).unwrap();
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        frame.process_read(&*ctx).unwrap();
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            mreq.parse_response_typed(&response).err().unwrap(),
+            ErrorKind::IllegalDataAddress
+        );
+    }
+}
+
+#[test]
+fn test_std_client_count_limits() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_get_coils(0, 2000, &mut request).unwrap();
+    assert_eq!(
+        mreq.generate_get_coils(0, 2001, &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    mreq.generate_get_discretes(0, 2000, &mut request).unwrap();
+    assert_eq!(
+        mreq.generate_get_discretes(0, 2001, &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    mreq.generate_get_holdings(0, 125, &mut request).unwrap();
+    assert_eq!(
+        mreq.generate_get_holdings(0, 126, &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    mreq.generate_get_inputs(0, 125, &mut request).unwrap();
+    assert_eq!(
+        mreq.generate_get_inputs(0, 126, &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    mreq.generate_set_coils_bulk(0, &[true; 1968], &mut request)
+        .unwrap();
+    assert_eq!(
+        mreq.generate_set_coils_bulk(0, &[true; 1969], &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    mreq.generate_set_holdings_bulk(0, &[1; 123], &mut request)
+        .unwrap();
+    assert_eq!(
+        mreq.generate_set_holdings_bulk(0, &[1; 124], &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    mreq.generate_set_holdings_string(0, &"x".repeat(246), &mut request)
+        .unwrap();
+    assert_eq!(
+        mreq.generate_set_holdings_string(0, &"x".repeat(247), &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+}
+
+#[test]
+fn test_std_client_set_i16() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_set_holding_i16(2, -1, &mut request).unwrap();
+    assert_eq!(request[..6], [1, 6, 0, 2, 0xff, 0xff]);
+    mreq.generate_set_holdings_bulk_i16(2, &[-1, -2, 3], &mut request)
+        .unwrap();
+    assert_eq!(
+        request[..13],
+        [1, 16, 0, 2, 0, 3, 6, 0xff, 0xff, 0xff, 0xfe, 0, 3]
+    );
+    assert_eq!(
+        mreq.generate_set_holdings_bulk_i16(0, &[-1; 124], &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+}
+
+#[test]
+fn test_std_client_error_response() {
+    let errors = [
+        (
+            consts::MODBUS_ERROR_ILLEGAL_FUNCTION,
+            ErrorKind::IllegalFunction,
+        ),
+        (
+            consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS,
+            ErrorKind::IllegalDataAddress,
+        ),
+        (
+            consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE,
+            ErrorKind::IllegalDataValue,
+        ),
+        (
+            consts::MODBUS_ERROR_SERVER_DEVICE_FAILURE,
+            ErrorKind::SlaveDeviceFailure,
+        ),
+        (consts::MODBUS_ERROR_ACKNOWLEDGE, ErrorKind::Acknowledge),
+        (
+            consts::MODBUS_ERROR_SERVER_DEVICE_BUSY,
+            ErrorKind::SlaveDeviceBusy,
+        ),
+        (
+            consts::MODBUS_ERROR_GATEWAY_PATH_UNAVAILABLE,
+            ErrorKind::GatewayPathUnavailable,
+        ),
+        (
+            consts::MODBUS_ERROR_GATEWAY_TARGET_FAILED,
+            ErrorKind::GatewayTargetFailed,
+        ),
+    ];
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu, ModbusProto::Ascii] {
+        let mut mreq = ModbusRequest::new(2, proto);
+        let mut request = Vec::new();
+        let mut response = Vec::new();
+        mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+        for (code, err) in errors {
+            mreq.generate_error_response(code, &mut response).unwrap();
+            let mut frame = Vec::new();
+            if proto == ModbusProto::Ascii {
+                generate_ascii_frame(&response, &mut frame).unwrap();
+            } else {
+                frame.extend_from_slice(&response);
+            }
+            assert_eq!(
+                guess_response_frame_len(&frame, proto).unwrap(),
+                u8::try_from(frame.len()).unwrap()
+            );
+            assert_eq!(mreq.parse_ok(&response).err().unwrap(), err);
+        }
+    }
+    let mut mreq = ModbusRequest::new(2, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    let mut response = Vec::new();
+    mreq.tr_id = 0x1234;
+    mreq.generate_set_holding(0, 1, &mut request).unwrap();
+    mreq.generate_error_response(consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS, &mut response)
+        .unwrap();
+    assert_eq!(response, [0x12, 0x34, 0, 0, 0, 3, 2, 0x86, 2]);
+}
+
+#[test]
+fn test_std_rtu_timeouts() {
+    use std::time::Duration;
+    assert_eq!(rtu_char_time(9600), Duration::from_nanos(1_145_833));
+    assert_eq!(rtu_frame_timeout(9600), Duration::from_nanos(4_010_416));
+    assert_eq!(rtu_char_timeout(9600), Duration::from_nanos(1_718_750));
+    assert_eq!(rtu_frame_timeout(19200), Duration::from_nanos(2_005_208));
+    assert_eq!(rtu_frame_timeout(115_200), Duration::from_micros(1750));
+    assert_eq!(rtu_char_timeout(115_200), Duration::from_micros(750));
+}
+
+#[test]
+fn test_std_client_set_coils_bulk_count() {
+    let mut ctx = CTX.write().unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    let values = [true, false, true, true, false, true];
+    ctx.set_coils_bulk(200, &[false; 8]).unwrap();
+    mreq.generate_set_coils_bulk_count(200, &values, 5, &mut request)
+        .unwrap();
+    assert_eq!(request[..8], [1, 15, 0, 200, 0, 5, 1, 0b10_1101]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    frame.process_write(&mut *ctx).unwrap();
+    frame.finalize_response().unwrap();
+    mreq.parse_ok(&response).unwrap();
+    let mut coils = Vec::new();
+    ctx.get_coils_bulk(200, 6, &mut coils).unwrap();
+    assert_eq!(coils, [true, false, true, true, false, false]);
+    assert_eq!(
+        mreq.generate_set_coils_bulk_count(200, &values, 7, &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+}
+
+#[test]
+fn test_std_client_get_holdings_chunked() {
+    let mut ctx = CTX.write().unwrap();
+    for i in 0..300 {
+        ctx.set_holding(1000 + i, i * 3).unwrap();
+    }
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 10);
+    mreq.auto_increment_tr_id(true);
+    let mut requests = Vec::new();
+    mreq.generate_get_holdings_chunked(1000, 300, &mut requests)
+        .unwrap();
+    assert_eq!(requests.len(), 3);
+    for (request, (tr_id, reg, count)) in
+        requests
+            .iter()
+            .zip([(11u16, 1000u16, 125u16), (12, 1125, 125), (13, 1250, 50)])
+    {
+        assert_eq!(request[..2], tr_id.to_be_bytes());
+        assert_eq!(request[8..10], reg.to_be_bytes());
+        assert_eq!(request[10..12], count.to_be_bytes());
+    }
+    let mut responses = Vec::new();
+    for request in &requests {
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, request, ModbusProto::Tcp, &mut response);
+        frame.parse().unwrap();
+        frame.process_read(&*ctx).unwrap();
+        frame.finalize_response().unwrap();
+        responses.push(response);
+    }
+    let mut result = Vec::new();
+    mreq.parse_u16_chunked(&responses, &mut result).unwrap();
+    assert_eq!(result, (0..300).map(|i| i * 3).collect::<Vec<u16>>());
+    assert_eq!(
+        mreq.parse_u16_chunked(&responses[..2], &mut result)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    responses.swap(0, 1);
+    assert!(mreq.parse_u16_chunked(&responses, &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_std_client_set_coils_bulk_from_bytes() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    let mut expected = Vec::new();
+    mreq.generate_set_coils_bulk(100, &[true, false, true, false, false], &mut expected)
+        .unwrap();
+    mreq.generate_set_coils_bulk_from_bytes(100, 5, &[0x05], &mut request)
+        .unwrap();
+    assert_eq!(request, expected);
+    // the payload is forwarded verbatim, including bits above count
+    mreq.generate_set_coils_bulk_count(100, &[1u8, 0, 1, 0, 0, 1], 5, &mut expected)
+        .unwrap();
+    mreq.generate_set_coils_bulk_from_bytes(100, 5, &[0x25], &mut request)
+        .unwrap();
+    assert_eq!(request, expected);
+    assert_eq!(mreq.count, 5);
+    for (count, packed) in [(5, &[][..]), (8, &[0xff, 0][..]), (9, &[0xff][..])] {
+        assert_eq!(
+            mreq.generate_set_coils_bulk_from_bytes(100, count, packed, &mut request)
+                .err()
+                .unwrap(),
+            ErrorKind::OOB
+        );
+    }
+}
+
+#[test]
+fn test_std_frame_zero_count_read() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    for func in [consts::MODBUS_GET_COILS, consts::MODBUS_GET_HOLDINGS] {
+        if func == consts::MODBUS_GET_COILS {
+            mreq.generate_get_coils(0, 0, &mut request).unwrap();
+        } else {
+            mreq.generate_get_holdings(0, 0, &mut request).unwrap();
+        }
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(!frame.processing_required);
+        assert_eq!(frame.error, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE);
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            response[..3],
+            [1, func + 0x80, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE]
+        );
+        assert_eq!(
+            mreq.parse_ok(&response).err().unwrap(),
+            ErrorKind::IllegalDataValue
+        );
+    }
+}
+
+#[test]
+fn test_std_response_bytes_remaining() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 2, &mut request).unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&*CTX.read().unwrap()).unwrap();
+    frame.finalize_response().unwrap();
+    let total = response.len();
+    assert_eq!(total, 13);
+    for read in 6..=total {
+        assert_eq!(
+            response_bytes_remaining(&response, read, ModbusProto::Tcp).unwrap(),
+            total - read
+        );
+    }
+    assert_eq!(
+        response_bytes_remaining(&response, total + 5, ModbusProto::Tcp).unwrap(),
+        0
+    );
+    assert_eq!(
+        response_bytes_remaining(&response[..5], 5, ModbusProto::Tcp)
+            .err()
+            .unwrap(),
+        ErrorKind::FrameBroken
+    );
+    // the minimal (exception) frame
+    let error = [1, 0x83, 2, 0xc0, 0xf1];
+    assert_eq!(
+        response_bytes_remaining(&error, 3, ModbusProto::Rtu).unwrap(),
+        2
+    );
+    assert_eq!(
+        response_bytes_remaining(&error, 5, ModbusProto::Rtu).unwrap(),
+        0
+    );
+    assert_eq!(
+        response_bytes_remaining(&error, 2, ModbusProto::Rtu)
+            .err()
+            .unwrap(),
+        ErrorKind::FrameBroken
+    );
+    let mut ascii = Vec::new();
+    generate_ascii_frame(&[1, 3, 2, 0, 1, 0xf9], &mut ascii).unwrap();
+    assert_eq!(
+        response_bytes_remaining(&ascii, 7, ModbusProto::Ascii).unwrap(),
+        ascii.len() - 7
+    );
+}
+
+#[test]
+fn test_std_frame_unsupported_function() {
+    let mut result = Vec::new();
+    let framebuf = gen_rtu_frame(&[1, 0x41, 0, 1, 0, 0]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    assert_eq!(frame.unsupported_function(), None);
+    frame.parse().unwrap();
+    assert_eq!(frame.unsupported_function(), Some(0x41));
+    assert_eq!(frame.error, consts::MODBUS_ERROR_ILLEGAL_FUNCTION);
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        result[..3],
+        [1, 0xc1, consts::MODBUS_ERROR_ILLEGAL_FUNCTION]
+    );
+    let framebuf = gen_rtu_frame(&[1, 3, 0, 0, 0, 1]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    assert_eq!(frame.unsupported_function(), None);
+}
+
+#[test]
+fn test_std_dump_restore() {
+    let mut ctx = ModbusStorageSmall::default();
+    for i in 0..1000 {
+        ctx.set_coil(i, rand::random()).unwrap();
+        ctx.set_discrete(i, rand::random()).unwrap();
+        ctx.set_input(i, rand::random()).unwrap();
+        ctx.set_holding(i, rand::random()).unwrap();
+    }
+    let mut dump = Vec::new();
+    ctx.dump_into(&mut dump).unwrap();
+    assert_eq!(dump.len(), ModbusStorageSmall::dump_size());
+    assert_eq!(dump.len(), 6000);
+    assert_eq!(dump[0], u8::from(ctx.get_coil(0).unwrap()));
+    assert_eq!(dump[2000..2002], ctx.get_input(0).unwrap().to_be_bytes());
+    assert_eq!(dump[5998..], ctx.get_holding(999).unwrap().to_be_bytes());
+    let mut restored = ModbusStorageSmall::default();
+    assert_eq!(
+        restored.restore_from(&dump[1..]).err().unwrap(),
+        ErrorKind::OOB
+    );
+    restored.restore_from(&dump).unwrap();
+    assert_eq!(restored.coils, ctx.coils);
+    assert_eq!(restored.discretes, ctx.discretes);
+    assert_eq!(restored.inputs, ctx.inputs);
+    assert_eq!(restored.holdings, ctx.holdings);
+}
+
+#[test]
+fn test_std_client_response_byte_count() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    let mut result = Vec::new();
+    let response = |data: &[u8]| {
+        let mut frame = data.to_vec();
+        frame.extend_from_slice(&State::<MODBUS>::calculate(data).to_le_bytes());
+        frame
+    };
+    mreq.generate_get_holdings(0, 2, &mut request).unwrap();
+    mreq.parse_u16(&response(&[1, 3, 4, 0, 1, 0, 2]), &mut result)
+        .unwrap();
+    assert_eq!(result, [1, 2]);
+    // claims 4 bytes, carries 2
+    assert_eq!(
+        mreq.parse_u16(&response(&[1, 3, 4, 0, 1]), &mut result)
+            .err()
+            .unwrap(),
+        ErrorKind::FrameBroken
+    );
+    // fewer registers than requested
+    assert_eq!(
+        mreq.parse_u16(&response(&[1, 3, 2, 0, 1]), &mut result)
+            .err()
+            .unwrap(),
+        ErrorKind::FrameBroken
+    );
+    // 9 coils need 2 bytes
+    mreq.generate_get_coils(0, 9, &mut request).unwrap();
+    let mut coils = Vec::new();
+    mreq.parse_bool(&response(&[1, 1, 2, 0xff, 1]), &mut coils)
+        .unwrap();
+    assert_eq!(coils, [true; 9]);
+    assert_eq!(
+        mreq.parse_bool(&response(&[1, 1, 1, 0xff]), &mut coils)
+            .err()
+            .unwrap(),
+        ErrorKind::FrameBroken
+    );
+    assert_eq!(
+        mreq.parse_bool(&response(&[1, 1, 3, 0xff, 1, 0]), &mut coils)
+            .err()
+            .unwrap(),
+        ErrorKind::FrameBroken
+    );
+    // write responses are not affected
+    mreq.generate_set_holding(0, 1, &mut request).unwrap();
+    mreq.parse_ok(&response(&[1, 6, 0, 0, 0, 1])).unwrap();
+}
+
+#[test]
+fn test_std_enron() {
+    let mut ctx = ModbusStorageSmall::default();
+    ctx.set_holdings_bulk(700, &[0x0001, 0xe240, 0xffff, 0xfffe])
+        .unwrap();
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        mreq.enron_mode(true);
+        let mut request = Vec::new();
+        mreq.generate_get_holdings(700, 2, &mut request).unwrap();
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response).enron(true);
+        frame.parse().unwrap();
+        assert_eq!(frame.count, 2);
+        frame.process_read(&ctx).unwrap();
+        frame.finalize_response().unwrap();
+        let mut result = Vec::new();
+        mreq.parse_u32(&response, &mut result).unwrap();
+        assert_eq!(result, [123_456, 0xffff_fffe]);
+        let mut result = Vec::new();
+        mreq.parse_u16(&response, &mut result).unwrap();
+        assert_eq!(result, [0x0001, 0xe240, 0xffff, 0xfffe]);
+        // the standard server returns 16-bit registers
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        frame.process_read(&ctx).unwrap();
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            mreq.parse_u32(&response, &mut Vec::new()).err().unwrap(),
+            ErrorKind::FrameBroken
+        );
+    }
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.enron_mode(true);
+    assert_eq!(
+        mreq.generate_get_holdings(0, 63, &mut request)
+            .err()
+            .unwrap(),
+        ErrorKind::OOB
+    );
+    mreq.enron_mode(false);
+    mreq.generate_get_holdings(0, 63, &mut request).unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response).enron(true);
+    frame.parse().unwrap();
+    assert_eq!(frame.error, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE);
+}
+
+#[test]
+fn test_std_frame_even_alignment() {
+    let mut ctx = ModbusStorageSmall::default();
+    ctx.set_holdings_from_f32(100, 1.5).unwrap();
+    ctx.set_holdings_from_f32(102, 2.5).unwrap();
+    let mut response = Vec::new();
+    let check = |data: &[u8], response: &mut Vec<u8>| -> u8 {
+        let framebuf = gen_rtu_frame(data);
+        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, response)
+            .require_even_alignment(100, 20);
+        frame.parse().unwrap();
+        frame.error
+    };
+    // odd start
+    assert_eq!(
+        check(&[1, 3, 0, 101, 0, 2], &mut response),
+        consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS
+    );
+    // odd count
+    assert_eq!(
+        check(&[1, 4, 0, 100, 0, 3], &mut response),
+        consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS
+    );
+    // intersects the window
+    assert_eq!(
+        check(&[1, 3, 0, 97, 0, 4], &mut response),
+        consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS
+    );
+    assert_eq!(
+        check(&[1, 16, 0, 101, 0, 2, 4, 0, 0, 0, 0], &mut response),
+        consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS
+    );
+    // aligned, single register and outside of the window
+    assert_eq!(check(&[1, 3, 0, 102, 0, 2], &mut response), 0);
+    assert_eq!(check(&[1, 3, 0, 101, 0, 1], &mut response), 0);
+    assert_eq!(check(&[1, 3, 0, 121, 0, 3], &mut response), 0);
+    let framebuf = gen_rtu_frame(&[1, 3, 0, 101, 0, 2]);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut response)
+        .require_even_alignment(100, 20);
+    frame.parse().unwrap();
+    assert!(!frame.processing_required);
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        response[..3],
+        [1, 0x83, consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS]
+    );
+    // off by default
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    assert_eq!(frame.error, 0);
+    // Enron mode: a 32-bit register takes two 16-bit ones
+    for (data, error) in [
+        (
+            [1, 3, 0, 101, 0, 1],
+            consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS,
+        ),
+        (
+            [1, 4, 0, 99, 0, 1],
+            consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS,
+        ),
+        ([1, 3, 0, 100, 0, 1], 0),
+        ([1, 3, 0, 102, 0, 2], 0),
+    ] {
+        let framebuf = gen_rtu_frame(&data);
+        let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut response)
+            .enron(true)
+            .require_even_alignment(100, 20);
+        frame.parse().unwrap();
+        assert_eq!(frame.error, error, "{:?}", data);
+    }
+}
+
+#[test]
+fn test_std_client_parse_slice_offsets() {
+    let mut ctx = CTX.write().unwrap();
+    ctx.set_holdings_bulk(300, &[0x1234, 0x5678]).unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    let mut response = Vec::new();
+    let mut process = |request: &[u8], response: &mut Vec<u8>| {
+        let mut frame = ModbusFrame::new(1, request, ModbusProto::Tcp, response);
+        frame.parse().unwrap();
+        if frame.readonly {
+            frame.process_read(&*ctx).unwrap();
+        } else {
+            frame.process_write(&mut *ctx).unwrap();
+        }
+        frame.finalize_response().unwrap();
+    };
+    mreq.generate_read_write_multiple_registers(300, 2, 310, &[1], &mut request)
+        .unwrap();
+    process(&request, &mut response);
+    assert_eq!(response[8], 4);
+    assert_eq!(
+        mreq.parse_slice(&response).unwrap(),
+        [0x12, 0x34, 0x56, 0x78]
+    );
+    mreq.generate_diagnostics(0, 0xabcd, &mut request).unwrap();
+    process(&request, &mut response);
+    assert_eq!(mreq.parse_slice(&response).unwrap(), [0, 0, 0xab, 0xcd]);
+    mreq.generate_get_holdings(300, 1, &mut request).unwrap();
+    process(&request, &mut response);
+    assert_eq!(mreq.parse_slice(&response).unwrap(), [0x12, 0x34]);
+}
+
+#[test]
+fn test_std_gateway_errors() {
+    for (code, err) in [
+        (0x0A, ErrorKind::GatewayPathUnavailable),
+        (0x0B, ErrorKind::GatewayTargetFailed),
+    ] {
+        assert_eq!(ErrorKind::from_modbus_error(code), err);
+        assert_eq!(err.to_modbus_error().unwrap(), code);
+        assert!(err.is_modbus_error());
+    }
+    assert_eq!(ErrorKind::from_modbus_error(0x09), ErrorKind::UnknownError);
+    assert_eq!(ErrorKind::from_modbus_error(0x10), ErrorKind::UnknownError);
+}
+
+#[test]
+fn test_std_retryable_errors() {
+    for code in 1..=0x0B {
+        let err = ErrorKind::from_modbus_error(code);
+        assert_eq!(
+            err.is_retryable(),
+            code == consts::MODBUS_ERROR_ACKNOWLEDGE
+                || code == consts::MODBUS_ERROR_SERVER_DEVICE_BUSY
+        );
+    }
+    assert_eq!(ErrorKind::from_modbus_error(5), ErrorKind::Acknowledge);
+    assert_eq!(ErrorKind::from_modbus_error(6), ErrorKind::SlaveDeviceBusy);
+    assert!(!ErrorKind::FrameBroken.is_retryable());
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    let mut response = Vec::new();
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    mreq.generate_error_response(consts::MODBUS_ERROR_SERVER_DEVICE_BUSY, &mut response)
+        .unwrap();
+    assert!(mreq.parse_ok(&response).err().unwrap().is_retryable());
+}
+
+#[test]
+fn test_std_packed_storage() {
+    let mut ctx = ModbusStorageSmall::default();
+    let mut packed = ModbusStoragePackedSmall::default();
+    let values: Vec<bool> = (0..1000).map(|_| rand::random()).collect();
+    ctx.set_coils_bulk(0, &values).unwrap();
+    packed.set_coils_bulk(0, &values).unwrap();
+    ctx.set_discretes_bulk(0, &values).unwrap();
+    packed.set_discretes_bulk(0, &values).unwrap();
+    for (reg, count) in [(0, 1000), (3, 17), (990, 10), (995, 6), (1000, 0)] {
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        assert_eq!(
+            ctx.get_coils_as_u8(reg, count, &mut a),
+            packed.get_coils_as_u8(reg, count, &mut b)
+        );
+        assert_eq!(a, b);
+        assert_eq!(
+            ctx.get_discretes_as_u8_bytes(reg, count, &mut a),
+            packed.get_discretes_as_u8_bytes(reg, count, &mut b)
+        );
+        assert_eq!(a, b);
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        assert_eq!(
+            ctx.get_coils_bulk(reg, count, &mut a),
+            packed.get_coils_bulk(reg, count, &mut b)
+        );
+        assert_eq!(a, b);
+        let mut a = [false; 6];
+        let mut b = [false; 6];
+        assert_eq!(
+            ctx.get_discretes_into(reg, &mut a),
+            packed.get_discretes_into(reg, &mut b)
+        );
+        assert_eq!(a, b);
+    }
+    for reg in [0, 7, 8, 999, 1000] {
+        assert_eq!(ctx.get_coil(reg), packed.get_coil(reg));
+        assert_eq!(ctx.set_coil(reg, true), packed.set_coil(reg, true));
+        assert_eq!(ctx.get_discrete(reg), packed.get_discrete(reg));
+        assert_eq!(
+            ctx.set_discrete(reg, false),
+            packed.set_discrete(reg, false)
+        );
+    }
+    // packed writes
+    for (reg, count, data) in [
+        (5, 11, &[0b1010_1101, 0b101][..]),
+        (100, 16, &[0xff, 0][..]),
+        (995, 5, &[0b1_0101][..]),
+        (996, 5, &[0xff][..]),
+        (200, 9, &[0xff][..]),
+    ] {
+        assert_eq!(
+            ctx.set_coils_from_u8(reg, count, data),
+            packed.set_coils_from_u8(reg, count, data)
+        );
+        assert_eq!(
+            ctx.set_discretes_from_u8(reg, count, data),
+            packed.set_discretes_from_u8(reg, count, data)
+        );
+        assert_eq!(
+            ctx.set_coils_from_u8_bytes(reg + 1, data),
+            packed.set_coils_from_u8_bytes(reg + 1, data)
+        );
+    }
+    let mut a = Vec::new();
+    let mut b = Vec::new();
+    ctx.get_coils_bulk(0, 1000, &mut a).unwrap();
+    packed.get_coils_bulk(0, 1000, &mut b).unwrap();
+    assert_eq!(a, b);
+    let mut a = Vec::new();
+    let mut b = Vec::new();
+    ctx.get_discretes_bulk(0, 1000, &mut a).unwrap();
+    packed.get_discretes_bulk(0, 1000, &mut b).unwrap();
+    assert_eq!(a, b);
+    // frames
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_set_coils_bulk(50, &[true, false, true], &mut request)
+        .unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    frame.process_write(&mut packed).unwrap();
+    mreq.generate_get_coils(50, 3, &mut request).unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&packed).unwrap();
+    frame.finalize_response().unwrap();
+    let mut result = Vec::new();
+    mreq.parse_bool(&response, &mut result).unwrap();
+    assert_eq!(result, [true, false, true]);
+    assert_eq!(core::mem::size_of::<ModbusStoragePackedSmall>(), 250 + 4000);
+}
+
+#[test]
+fn test_std_verify_frame_checksum() {
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu, ModbusProto::Ascii] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        let mut request = Vec::new();
+        mreq.generate_set_holdings_bulk(10, &[1, 2, 3], &mut request)
+            .unwrap();
+        verify_frame_checksum(&request, proto).unwrap();
+        let last = request.len() - 1;
+        request[last] ^= 0x55;
+        let expected = match proto {
+            // the payload is not checked, the header is
+            ModbusProto::Tcp => Ok(()),
+            ModbusProto::Ascii => Err(ErrorKind::FrameLRCError),
+            _ => Err(ErrorKind::FrameCRCError),
+        };
+        assert_eq!(verify_frame_checksum(&request, proto), expected);
+        // a frame with a byte missing
+        assert!(verify_frame_checksum(&request[..last], proto).is_err());
+    }
+    let mut frame = gen_tcp_frame(&[1, 3, 0, 0, 0, 1]);
+    assert_eq!(
+        verify_frame_checksum(&frame[..12], ModbusProto::Tcp),
+        Ok(())
+    );
+    frame[3] = 1;
+    assert_eq!(
+        verify_frame_checksum(&frame[..12], ModbusProto::Tcp),
+        Err(ErrorKind::FrameBroken)
+    );
+    assert_eq!(
+        verify_frame_checksum(&[1, 3], ModbusProto::Rtu),
+        Err(ErrorKind::FrameBroken)
+    );
+}
+
+#[test]
+fn test_std_frame_external_no_context() {
+    // read holdings
+    let request = gen_tcp_frame(&[1, 3, 0, 10, 0, 2]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert!(frame.processing_required);
+    let req = frame.read_request().unwrap();
+    assert_eq!(
+        req,
+        ReadRequest {
+            kind: ReadKind::Holdings,
+            address: 10,
+            count: 2
+        }
+    );
+    match frame.get_external_read().unwrap() {
+        Read::Words(words) => words.buf.copy_from_slice(&[0x12, 0x34, 0x56, 0x78]),
+        Read::Bits(_) => panic!("bits requested"),
+    }
+    frame.process_external_read(Ok(())).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(response[6..], [1, 3, 4, 0x12, 0x34, 0x56, 0x78]);
+    // read discretes
+    let request = gen_tcp_frame(&[1, 2, 0, 5, 0, 10]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    let req = frame.read_request().unwrap();
+    assert_eq!(req.kind, ReadKind::Discretes);
+    assert_eq!((req.address, req.count), (5, 10));
+    match frame.get_external_read().unwrap() {
+        Read::Bits(bits) => bits.buf.copy_from_slice(&[0b1010_0101, 0b11]),
+        Read::Words(_) => panic!("words requested"),
+    }
+    frame.process_external_read(Ok(())).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(response[6..], [1, 2, 2, 0b1010_0101, 0b11]);
+    // failed read
+    let request = gen_tcp_frame(&[1, 4, 0, 0, 0, 1]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.read_request().unwrap().kind, ReadKind::Inputs);
+    frame.get_external_read().unwrap();
+    frame
+        .process_external_read(Err(ErrorKind::SlaveDeviceBusy))
+        .unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        response[6..],
+        [1, 0x84, consts::MODBUS_ERROR_SERVER_DEVICE_BUSY]
+    );
+    // write holdings, the write data outlives the frame borrow
+    let request = gen_tcp_frame(&[1, 16, 0, 1, 0, 2, 4, 0, 7, 0, 8]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.read_request(), Err(ErrorKind::WriteCallOnReadFrame));
+    let write = frame.get_external_write().unwrap();
+    frame.process_external_write(Ok(())).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        write,
+        Write::Words(WriteWords {
+            address: 1,
+            count: 2,
+            data: &[0, 7, 0, 8]
+        })
+    );
+    assert_eq!(response[6..], [1, 16, 0, 1, 0, 2]);
+}
+
+#[test]
+fn test_std_get_bools_as_u8_boundary() {
+    let mut ctx = ModbusStorageSmall::new();
+    let mut packed = ModbusStoragePackedSmall::new();
+    let size = ctx.coils.len();
+    let reg = u16::try_from(size).unwrap() - 8;
+    for (i, v) in [true, false, true, true, false, false, true, true]
+        .iter()
+        .enumerate()
+    {
+        ctx.set_coil(reg + i as u16, *v).unwrap();
+        packed.set_coil(reg + i as u16, *v).unwrap();
+        ctx.set_discrete(reg + i as u16, *v).unwrap();
+        packed.set_discrete(reg + i as u16, *v).unwrap();
+    }
+    ctx.set_coil(reg - 1, true).unwrap();
+    packed.set_coil(reg - 1, true).unwrap();
+    // the last full byte
+    let mut result = Vec::new();
+    ctx.get_coils_as_u8(reg, 8, &mut result).unwrap();
+    assert_eq!(result, [0b1100_1101]);
+    result.clear();
+    packed.get_coils_as_u8(reg, 8, &mut result).unwrap();
+    assert_eq!(result, [0b1100_1101]);
+    result.clear();
+    ctx.get_discretes_as_u8(reg, 8, &mut result).unwrap();
+    assert_eq!(result, [0b1100_1101]);
+    // two full bytes ending at the context end
+    result.clear();
+    ctx.get_coils_as_u8(reg - 8, 16, &mut result).unwrap();
+    assert_eq!(result, [0b1000_0000, 0b1100_1101]);
+    result.clear();
+    packed.get_coils_as_u8(reg - 8, 16, &mut result).unwrap();
+    assert_eq!(result, [0b1000_0000, 0b1100_1101]);
+    // crossing the context end
+    for (r, count) in [(reg, 16), (reg + 1, 8), (reg - 7, 16)] {
+        result.clear();
+        assert_eq!(
+            ctx.get_coils_as_u8(r, count, &mut result),
+            Err(ErrorKind::OOBContext)
+        );
+        assert_eq!(
+            packed.get_coils_as_u8(r, count, &mut result),
+            Err(ErrorKind::OOBContext)
+        );
+        assert_eq!(
+            ctx.get_discretes_as_u8(r, count, &mut result),
+            Err(ErrorKind::OOBContext)
+        );
+        assert!(result.is_empty());
+    }
+}
+
+#[test]
+fn test_std_client_accept_any_unit_id() {
+    let mut mreq = ModbusRequest::new(5, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    // the device replies with unit id 0
+    let mut response = vec![0, 3, 2, 0x12, 0x34];
+    let crc = crc16(&response);
+    response.extend_from_slice(&crc.to_le_bytes());
+    let mut result = Vec::new();
+    assert_eq!(
+        mreq.parse_u16(&response, &mut result),
+        Err(ErrorKind::FrameBroken)
+    );
+    mreq.accept_any_unit_id(true);
+    mreq.parse_u16(&response, &mut result).unwrap();
+    assert_eq!(result, [0x1234]);
+    // CRC is still checked
+    let last = response.len() - 1;
+    response[last] ^= 0xff;
+    assert_eq!(
+        mreq.parse_u16(&response, &mut result),
+        Err(ErrorKind::FrameCRCError)
+    );
+    mreq.accept_any_unit_id(false);
+    response[last] ^= 0xff;
+    assert_eq!(
+        mreq.parse_u16(&response, &mut result),
+        Err(ErrorKind::FrameBroken)
+    );
+}
+
+#[test]
+fn test_std_frame_expected_response_len() {
+    let ctx = CTX.read().unwrap();
+    let pdus: [&[u8]; 3] = [
+        &[1, 1, 0, 0, 0, 13],
+        &[1, 3, 0, 0, 0, 2],
+        &[1, 16, 0, 0, 0, 2, 4, 0, 1, 0, 2],
+    ];
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu, ModbusProto::Ascii] {
+        for pdu in pdus {
+            let request = match proto {
+                ModbusProto::Tcp => gen_tcp_frame(pdu),
+                ModbusProto::Rtu => gen_rtu_frame(pdu),
+                _ => {
+                    let mut frame = [0; 256];
+                    frame[..pdu.len()].copy_from_slice(pdu);
+                    frame[pdu.len()] = lrc(pdu);
+                    frame
+                }
+            };
+            let mut response = Vec::new();
+            let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+            frame.parse().unwrap();
+            let expected = frame.expected_response_len().unwrap();
+            if frame.readonly {
+                frame.process_read(&*ctx).unwrap();
+            } else {
+                let mut ctx = ModbusStorageSmall::new();
+                frame.process_write(&mut ctx).unwrap();
+            }
+            frame.finalize_response().unwrap();
+            assert_eq!(response.len(), expected);
+        }
+    }
+    // 13 coils: unit, func, byte count, 2 data bytes, CRC
+    let request = gen_rtu_frame(&[1, 1, 0, 0, 0, 13]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.expected_response_len(), Some(7));
+    // the error is set by parse
+    let request = gen_tcp_frame(&[1, 3, 0, 0, 0, 0]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.expected_response_len(), None);
+}
+
+#[test]
+fn test_std_ascii_lrc_error() {
+    // the request from test_modbus_ascii with LRC F9 changed to F8
+    let request = [
+        0x3a, 0x30, 0x31, 0x30, 0x33, 0x30, 0x30, 0x30, 0x32, 0x30, 0x30, 0x30, 0x31, 0x46, 0x38,
+        0xd, 0xa,
+    ];
+    let mut framebuf: ModbusFrameBuf = [0; 256];
+    parse_ascii_frame(&request, request.len(), &mut framebuf, 0).unwrap();
+    let mut result = Vec::new();
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Ascii, &mut result);
+    assert_eq!(frame.parse(), Err(ErrorKind::FrameLRCError));
+    let mut counters = DiagnosticCounters::default();
+    counters.count(&frame, &Err(ErrorKind::FrameLRCError));
+    assert_eq!(counters.bus_communication_errors, 1);
+    // client
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Ascii);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(2, 1, &mut request).unwrap();
+    let mut response = vec![1, 3, 2, 0, 0];
+    response.push(lrc(&response).wrapping_add(1));
+    let mut values = Vec::new();
+    assert_eq!(
+        mreq.parse_u16(&response, &mut values),
+        Err(ErrorKind::FrameLRCError)
+    );
+}
+
+#[test]
+fn test_std_frame_broadcast_write() {
+    let mut ctx = ModbusStorageSmall::new();
+    let request = gen_rtu_frame(&[0, 6, 0, 5, 0x12, 0x34]);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    assert!(frame.was_broadcast);
+    assert!(frame.processing_required);
+    assert!(!frame.response_required);
+    frame.process_write(&mut ctx).unwrap();
+    assert_eq!(
+        frame.changes(),
+        Some(Changes::Holdings { reg: 5, count: 1 })
+    );
+    assert_eq!(ctx.get_holding(5).unwrap(), 0x1234);
+    // the unit addressed directly
+    let request = gen_rtu_frame(&[1, 6, 0, 5, 0, 1]);
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    assert!(!frame.was_broadcast);
+    assert!(frame.response_required);
+}
+
+#[test]
+fn test_std_frame_exception_status() {
+    let mut result = Vec::new();
+    let request = [1, 7];
+    let response = [0x77, 0x55, 0, 0, 0, 3, 1, 7, 0x6d];
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    assert!(frame.response_required);
+    assert!(frame.unsupported_function().is_none());
+    frame.process_exception_status(0x6d).unwrap();
+    assert_eq!(frame.error, 0);
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), response);
+    let framebuf = gen_rtu_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Rtu, &mut result);
+    frame.parse().unwrap();
+    frame.process_exception_status(0x6d).unwrap();
+    frame.finalize_response().unwrap();
+    check_rtu_response(&result, &response);
+    // not processed
+    let framebuf = gen_tcp_frame(&request);
+    let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut result);
+    frame.parse().unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(result.as_slice(), [0x77, 0x55, 0, 0, 0, 3, 1, 0x87, 1]);
+    // client
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        let mut request = Vec::new();
+        mreq.generate_read_exception_status(&mut request).unwrap();
+        assert_eq!(
+            guess_request_frame_len(&request, proto).unwrap(),
+            u8::try_from(request.len()).unwrap()
+        );
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        frame.process_exception_status(0b1000_0001).unwrap();
+        frame.finalize_response().unwrap();
+        assert_eq!(
+            guess_response_frame_len(&response, proto).unwrap(),
+            u8::try_from(response.len()).unwrap()
+        );
+        assert_eq!(mreq.parse_exception_status(&response).unwrap(), 0b1000_0001);
+        assert_eq!(
+            mreq.parse_response_typed(&response).unwrap(),
+            ResponseData::ExceptionStatus(0b1000_0001)
+        );
+    }
+}
+
+#[test]
+fn test_std_clear_range() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_coils_bulk(0, &[true; 10]).unwrap();
+    ctx.set_discretes_bulk(0, &[true; 10]).unwrap();
+    ctx.set_inputs_bulk(0, &[1; 10]).unwrap();
+    ctx.set_holdings_bulk(0, &[1; 10]).unwrap();
+    ctx.clear_coils_range(2, 3).unwrap();
+    ctx.clear_discretes_range(2, 3).unwrap();
+    ctx.clear_inputs_range(2, 3).unwrap();
+    ctx.clear_holdings_range(2, 3).unwrap();
+    for i in 0..10 {
+        let cleared = (2..5).contains(&i);
+        assert_eq!(ctx.coils[i], !cleared);
+        assert_eq!(ctx.discretes[i], !cleared);
+        assert_eq!(ctx.inputs[i], u16::from(!cleared));
+        assert_eq!(ctx.holdings[i], u16::from(!cleared));
+    }
+    let size = u16::try_from(ctx.holdings.len()).unwrap();
+    ctx.clear_holdings_range(size - 1, 1).unwrap();
+    ctx.clear_holdings_range(size, 0).unwrap();
+    assert_eq!(
+        ctx.clear_holdings_range(size - 1, 2),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(
+        ctx.clear_coils_range(u16::MAX, u16::MAX),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(ctx.holdings[9], 1);
+}
+
+#[test]
+fn test_std_storage_diff() {
+    let mut ctx = ModbusStorageSmall::new();
+    let snapshot = ctx.clone();
+    assert_eq!(ctx.diff(&snapshot).count(), 0);
+    ctx.set_holding(10, 1).unwrap();
+    assert_eq!(
+        ctx.diff(&snapshot).collect::<Vec<_>>(),
+        [Changes::Holdings { reg: 10, count: 1 }]
+    );
+    ctx.set_holdings_bulk(11, &[1, 2]).unwrap();
+    ctx.set_holding(999, 1).unwrap();
+    ctx.set_coils_bulk(0, &[true, true]).unwrap();
+    // not compared
+    ctx.set_input(1, 1).unwrap();
+    ctx.set_discrete(1, true).unwrap();
+    assert_eq!(
+        ctx.diff(&snapshot).collect::<Vec<_>>(),
+        [
+            Changes::Coils { reg: 0, count: 2 },
+            Changes::Holdings { reg: 10, count: 3 },
+            Changes::Holdings { reg: 999, count: 1 }
+        ]
+    );
+}
+
+#[test]
+fn test_std_frame_max_pdu() {
+    let mut ctx = ModbusStorageSmall::new();
+    // 29 registers: 6 + 58 = 64 bytes PDU
+    for (count, error) in [(29u16, 0), (30, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE)] {
+        let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+        let mut request = Vec::new();
+        let values: Vec<u16> = (0..count).collect();
+        mreq.generate_set_holdings_bulk(0, &values, &mut request)
+            .unwrap();
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response).max_pdu(64);
+        frame.parse().unwrap();
+        assert_eq!(frame.error, error);
+        assert_eq!(frame.processing_required, error == 0);
+        if frame.processing_required {
+            frame.process_write(&mut ctx).unwrap();
+        }
+        frame.finalize_response().unwrap();
+        mreq.parse_ok(&response).unwrap_or_else(|e| {
+            assert_eq!(e, ErrorKind::IllegalDataValue);
+        });
+    }
+    assert_eq!(ctx.get_holding(28).unwrap(), 28);
+    // the default limit
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_set_holdings_bulk(0, &[1; 120], &mut request)
+        .unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.error, 0);
+}
+
+#[test]
+fn test_std_ascii_lowercase() {
+    let upper = b":01030002000AF0\r\n";
+    let lower = b":01030002000af0\r\n";
+    let mut upper_buf: ModbusFrameBuf = [0; 256];
+    let mut lower_buf: ModbusFrameBuf = [0; 256];
+    let len = parse_ascii_frame(upper, upper.len(), &mut upper_buf, 0).unwrap();
+    assert_eq!(
+        parse_ascii_frame(lower, lower.len(), &mut lower_buf, 0).unwrap(),
+        len
+    );
+    assert_eq!(upper_buf, lower_buf);
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &lower_buf, ModbusProto::Ascii, &mut response);
+    frame.parse().unwrap();
+    assert_eq!((frame.reg, frame.count), (2, 10));
+    // generated frames are uppercase
+    let mut ascii = Vec::new();
+    generate_ascii_frame(&upper_buf[..len as usize], &mut ascii).unwrap();
+    assert_eq!(ascii, upper);
+}
+
+#[test]
+fn test_std_client_parse_coils_packed() {
+    let mut ctx = ModbusStorageSmall::new();
+    let coils = [
+        true, false, true, true, false, false, false, true, false, true, true, false, true,
+    ];
+    ctx.set_coils_bulk(5, &coils).unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_get_coils(5, 13, &mut request).unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    frame.finalize_response().unwrap();
+    let mut packed = Vec::new();
+    assert_eq!(mreq.parse_coils_packed(&response, &mut packed).unwrap(), 13);
+    assert_eq!(packed, [0b1000_1101, 0b1_0110]);
+    assert_eq!(packed, response[3..5]);
+}
+
+#[test]
+fn test_std_client_parse_write_ack() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_set_holdings_bulk(100, &[1, 2, 3], &mut request)
+        .unwrap();
+    let mut ctx = ModbusStorageSmall::new();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_write(&mut ctx).unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(mreq.parse_write_ack(&response).unwrap(), (100, 3));
+    // a buggy server echoes a different count
+    response[11] = 2;
+    mreq.parse_ok(&response).unwrap();
+    let (reg, count) = mreq.parse_write_ack(&response).unwrap();
+    assert_eq!(reg, mreq.reg);
+    assert_ne!(count, mreq.count);
+    // too short
+    assert_eq!(
+        mreq.parse_write_ack(&response[..10]),
+        Err(ErrorKind::FrameBroken)
+    );
+}
+
+#[test]
+fn test_std_compare_and_set_holding() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holding(10, 1).unwrap();
+    assert!(ctx.compare_and_set_holding(10, 1, 2).unwrap());
+    assert_eq!(ctx.get_holding(10).unwrap(), 2);
+    assert!(!ctx.compare_and_set_holding(10, 1, 3).unwrap());
+    assert_eq!(ctx.get_holding(10).unwrap(), 2);
+    let size = u16::try_from(ctx.holdings.len()).unwrap();
+    assert_eq!(
+        ctx.compare_and_set_holding(size, 0, 1),
+        Err(ErrorKind::OOBContext)
+    );
+    let mut packed = ModbusStoragePackedSmall::new();
+    assert!(packed.compare_and_set_holding(0, 0, 5).unwrap());
+    assert_eq!(packed.get_holding(0).unwrap(), 5);
+}
+
+#[test]
+fn test_std_frame_observer() {
+    #[derive(Default)]
+    struct Recorder {
+        requests: Vec<(u8, u16, u16)>,
+        responses: Vec<(u8, Option<u8>)>,
+    }
+    impl FrameObserver for Recorder {
+        fn on_request(&mut self, func: u8, reg: u16, count: u16) {
+            self.requests.push((func, reg, count));
+        }
+        fn on_response(&mut self, func: u8, error: Option<u8>) {
+            self.responses.push((func, error));
+        }
+    }
+    let mut ctx = ModbusStorageSmall::new();
+    let mut recorder = Recorder::default();
+    for pdu in [
+        &[1, 3, 0, 10, 0, 2][..],
+        &[1, 16, 0, 20, 0, 1, 2, 0, 5],
+        &[1, 3, 0x27, 0x10, 0, 1],
+        // another unit
+        &[2, 3, 0, 10, 0, 2],
+    ] {
+        let request = gen_tcp_frame(pdu);
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response)
+            .with_observer(&mut recorder);
+        frame.parse().unwrap();
+        if frame.processing_required {
+            if frame.readonly {
+                frame.process_read(&ctx).unwrap();
+            } else {
+                frame.process_write(&mut ctx).unwrap();
+            }
+        }
+        if frame.response_required {
+            frame.finalize_response().unwrap();
+        }
+    }
+    assert_eq!(recorder.requests, [(3, 10, 2), (16, 20, 1), (3, 10000, 1)]);
+    assert_eq!(
+        recorder.responses,
+        [
+            (3, None),
+            (16, None),
+            (3, Some(consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS))
+        ]
+    );
+}
+
+#[test]
+fn test_std_next_tcp_frame() {
+    let ctx = CTX.read().unwrap();
+    let mut stream = Vec::new();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    mreq.auto_increment_tr_id(true);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 2, &mut request).unwrap();
+    stream.extend_from_slice(&request);
+    mreq.generate_get_coils(0, 10, &mut request).unwrap();
+    stream.extend_from_slice(&request);
+    // the beginning of the third frame
+    stream.extend_from_slice(&request[..8]);
+    let (first, rest) = next_tcp_frame(&stream).unwrap();
+    let (second, rest) = next_tcp_frame(rest).unwrap();
+    assert_eq!(rest.len(), 8);
+    assert!(next_tcp_frame(rest).is_none());
+    for (frame_buf, func) in [(first, 3), (second, 1)] {
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, frame_buf, ModbusProto::Tcp, &mut response);
+        frame.parse().unwrap();
+        assert_eq!(frame.func, func);
+        frame.process_read(&*ctx).unwrap();
+        frame.finalize_response().unwrap();
+    }
+    assert_ne!(first[..2], second[..2]);
+    // broken header
+    assert!(next_tcp_frame(&[0, 1, 0, 1, 0, 6, 1, 3, 0, 0, 0, 1]).is_none());
+}
+
+#[test]
+fn test_std_context_u16_overflow() {
+    let ctx = ModbusStorageSmall::new();
+    let mut result = Vec::new();
+    assert_eq!(
+        ctx.get_holdings_bulk(u16::MAX, 2, &mut result),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(
+        ctx.get_holdings_as_u32_bulk(u16::MAX, 2, &mut Vec::new()),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(
+        ctx.get_holdings_as_u32_bulk(65000, 1000, &mut Vec::new()),
+        Err(ErrorKind::OOBContext)
+    );
+    let mut out = [0u16; 2];
+    assert_eq!(
+        ctx.get_holdings_into(u16::MAX, &mut out),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(
+        ctx.get_holdings_as_representable::<2, representations::U32BigEndian>(u16::MAX),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(
+        ctx.get_inputs_as_representable::<4, representations::U64BigEndian>(65534),
+        Err(ErrorKind::OOBContext)
+    );
+    // a context, which covers the whole address space
+    let full = crate::server::storage::ModbusStorage::<0, 0, 0, 65536>::new();
+    assert_eq!(
+        full.get_holdings_as_representable::<2, representations::U32BigEndian>(u16::MAX),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(
+        full.get_holdings_as_representable::<2, representations::U32BigEndian>(65534)
+            .unwrap(),
+        representations::U32BigEndian(0)
+    );
+}
+
+#[test]
+fn test_std_client_parse_header() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holdings_bulk(10, &[1, 2]).unwrap();
+    // read response
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(10, 2, &mut request).unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    frame.finalize_response().unwrap();
+    let header = mreq.parse_header(&response).unwrap();
+    assert_eq!(
+        header,
+        ResponseHeader {
+            unit_id: 1,
+            func: consts::MODBUS_GET_HOLDINGS,
+            byte_count: Some(4),
+            frame_start: 6,
+            frame_end: 13,
+        }
+    );
+    assert_eq!(header.data_start(), 9);
+    assert_eq!(mreq.parse_slice(&response).unwrap(), &[0, 1, 0, 2]);
+    // write response, RTU
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_set_holdings_bulk(10, &[3, 4], &mut request)
+        .unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    frame.process_write(&mut ctx).unwrap();
+    frame.finalize_response().unwrap();
+    let header = mreq.parse_header(&response).unwrap();
+    assert_eq!(
+        header,
+        ResponseHeader {
+            unit_id: 1,
+            func: consts::MODBUS_SET_HOLDINGS_BULK,
+            byte_count: None,
+            frame_start: 0,
+            frame_end: 6,
+        }
+    );
+    assert_eq!(header.data_start(), 2);
+    assert_eq!(mreq.parse_slice(&response).unwrap(), &[0, 10, 0, 2]);
+    // Modbus error
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+    frame.parse().unwrap();
+    frame
+        .set_modbus_error_if_unset(&ErrorKind::IllegalDataAddress)
+        .unwrap();
+    frame.finalize_response().unwrap();
+    assert_eq!(
+        mreq.parse_header(&response),
+        Err(ErrorKind::IllegalDataAddress)
+    );
+}
+
+#[test]
+fn test_std_offset_storage() {
+    use crate::server::offset::OffsetStorage;
+    let mut ctx = OffsetStorage::new(ModbusStorageSmall::new())
+        .coils_base(1)
+        .discretes_base(10001)
+        .inputs_base(30001)
+        .holdings_base(40001);
+    ctx.set_coil(1, true).unwrap();
+    ctx.set_discrete(10002, true).unwrap();
+    ctx.set_input(30001, 5).unwrap();
+    ctx.set_holdings_bulk(40001, &[1, 2, 3]).unwrap();
+    ctx.set_holdings_from_u32(40010, 0x1234_5678).unwrap();
+    assert!(ctx.inner().coils[0]);
+    assert!(ctx.inner().discretes[1]);
+    assert_eq!(ctx.inner().inputs[0], 5);
+    assert_eq!(ctx.inner().holdings[..3], [1, 2, 3]);
+    assert_eq!(ctx.inner().holdings[9..11], [0x1234, 0x5678]);
+    assert_eq!(ctx.get_holdings_as_u32(40010).unwrap(), 0x1234_5678);
+    let mut result = Vec::new();
+    ctx.get_holdings_bulk(40002, 2, &mut result).unwrap();
+    assert_eq!(result, [2, 3]);
+    // below the base
+    assert_eq!(ctx.get_coil(0), Err(ErrorKind::OOBContext));
+    assert_eq!(ctx.get_discrete(10000), Err(ErrorKind::OOBContext));
+    assert_eq!(ctx.set_input(30000, 1), Err(ErrorKind::OOBContext));
+    assert_eq!(ctx.get_holding(40000), Err(ErrorKind::OOBContext));
+    // above the inner context
+    let size = u16::try_from(ctx.inner().holdings.len()).unwrap();
+    ctx.set_holding(40000 + size, 1).unwrap();
+    assert_eq!(ctx.set_holding(40001 + size, 1), Err(ErrorKind::OOBContext));
+    assert_eq!(
+        ctx.get_holdings_bulk(40000 + size, 2, &mut result),
+        Err(ErrorKind::OOBContext)
+    );
+    // frame processing uses the logical addresses
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(40001, 3, &mut request).unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    frame.finalize_response().unwrap();
+    let mut values = Vec::new();
+    mreq.parse_u16(&response, &mut values).unwrap();
+    assert_eq!(values, [1, 2, 3]);
+}
+
+#[test]
+fn test_std_ascii_encoder() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holdings_bulk(0, &[0x1234, 0xabcd, 7]).unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Ascii);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 3, &mut request).unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Ascii, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    frame.finalize_response().unwrap();
+    let mut expected = Vec::new();
+    generate_ascii_frame(&response, &mut expected).unwrap();
+    // the binary response contains LRC, the encoder calculates it
+    let mut out = Vec::new();
+    let mut encoder = AsciiEncoder::new(&mut out);
+    encoder.start().unwrap();
+    for b in &response[..response.len() - 1] {
+        encoder.push_byte(*b).unwrap();
+    }
+    encoder.finish().unwrap();
+    assert_eq!(out, expected);
+    // streaming through a small buffer, drained after every byte
+    let mut buf = [0u8; 4];
+    let mut sent = Vec::new();
+    let mut writer = SliceWriter::new(&mut buf);
+    let mut encoder = AsciiEncoder::new(&mut writer);
+    encoder.start().unwrap();
+    for b in &response[..response.len() - 1] {
+        encoder.push_byte(*b).unwrap();
+        sent.extend_from_slice(encoder.output().as_slice());
+        encoder.output().clear();
+    }
+    encoder.finish().unwrap();
+    sent.extend_from_slice(encoder.output().as_slice());
+    assert_eq!(sent, expected);
+}
+
+#[test]
+fn test_std_client_parse_ok_detailed() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    // vendor-specific exception code
+    let mut response = vec![1, 0x83, 0x20];
+    response.extend_from_slice(&crc16(&response).to_le_bytes());
+    assert_eq!(mreq.parse_ok(&response), Err(ErrorKind::UnknownError));
+    assert_eq!(
+        mreq.parse_ok_detailed(&response),
+        Err((ErrorKind::UnknownError, 0x20))
+    );
+    // standard exception code
+    let mut response = vec![1, 0x83, 0x02];
+    response.extend_from_slice(&crc16(&response).to_le_bytes());
+    assert_eq!(
+        mreq.parse_ok_detailed(&response),
+        Err((ErrorKind::IllegalDataAddress, 0x02))
+    );
+    // not an exception
+    response[3] ^= 0xff;
+    assert_eq!(
+        mreq.parse_ok_detailed(&response),
+        Err((ErrorKind::FrameCRCError, 0))
+    );
+    // TCP
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    let response = [0, 1, 0, 0, 0, 3, 1, 0x83, 0x20];
+    assert_eq!(
+        mreq.parse_ok_detailed(&response),
+        Err((ErrorKind::UnknownError, 0x20))
+    );
+}
+
+#[test]
+fn test_std_storage_with_defaults() {
+    let ctx = ModbusStorageSmall::with_defaults(true, false, 1, 0xffff);
+    let mut holdings = Vec::new();
+    ctx.get_holdings_bulk(0, u16::try_from(ctx.holdings.len()).unwrap(), &mut holdings)
+        .unwrap();
+    assert!(holdings.iter().all(|v| *v == 0xffff));
+    assert!(ctx.coils.iter().all(|v| *v));
+    assert!(ctx.discretes.iter().all(|v| !*v));
+    assert!(ctx.inputs.iter().all(|v| *v == 1));
+}
+
+#[test]
+fn test_std_frame_bulk_write_byte_count_mismatch() {
+    let mut ctx = ModbusStorageSmall::new();
+    for (data, valid) in [
+        // FC15, 10 coils need 2 bytes
+        (&[1, 15, 0, 0, 0, 10, 1, 0xff][..], false),
+        (&[1, 15, 0, 0, 0, 10, 3, 0xff, 0xff, 0xff][..], false),
+        (&[1, 15, 0, 0, 0, 10, 2, 0xff, 0xff][..], true),
+        // FC16, 2 registers need 4 bytes
+        (&[1, 16, 0, 0, 0, 2, 2, 0, 1][..], false),
+        (&[1, 16, 0, 0, 0, 2, 6, 0, 1, 0, 2, 0, 3][..], false),
+        (&[1, 16, 0, 0, 0, 2, 4, 0, 1, 0, 2][..], true),
+    ] {
+        ctx.clear_all();
+        let frame_buf = gen_tcp_frame(data);
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &frame_buf, ModbusProto::Tcp, &mut response);
+        frame.parse().unwrap();
+        if valid {
+            assert_eq!(frame.error, 0);
+            frame.process_write(&mut ctx).unwrap();
+        } else {
+            assert_eq!(frame.error, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE);
+            assert!(!frame.processing_required);
+        }
+        frame.finalize_response().unwrap();
+        if valid {
+            assert_eq!(response[7], data[1]);
+        } else {
+            assert_eq!(response[7], data[1] | 0x80);
+            assert_eq!(response[8], consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE);
+            assert!(ctx.coils.iter().all(|v| !*v));
+            assert!(ctx.holdings.iter().all(|v| *v == 0));
+        }
+    }
+}
+
+#[test]
+fn test_std_next_rtu_frame() {
+    let ctx = CTX.read().unwrap();
+    let mut stream = Vec::new();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::RtuOverTcp);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 2, &mut request).unwrap();
+    stream.extend_from_slice(&request);
+    mreq.generate_set_holdings_bulk(0, &[1, 2, 3], &mut request)
+        .unwrap();
+    stream.extend_from_slice(&request);
+    // the beginning of the third frame
+    stream.extend_from_slice(&request[..5]);
+    let (first, rest) = next_rtu_request_frame(&stream).unwrap().unwrap();
+    let (second, rest) = next_rtu_request_frame(rest).unwrap().unwrap();
+    assert_eq!(first.len(), 8);
+    assert_eq!(second, request.as_slice());
+    assert_eq!(rest.len(), 5);
+    assert_eq!(next_rtu_request_frame(rest), Ok(None));
+    assert_eq!(next_rtu_request_frame(&rest[..1]), Ok(None));
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, first, ModbusProto::RtuOverTcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.func, 3);
+    frame.process_read(&*ctx).unwrap();
+    frame.finalize_response().unwrap();
+    // responses
+    let mut responses = response.clone();
+    responses.extend_from_slice(&response);
+    let (first, rest) = next_rtu_response_frame(&responses).unwrap().unwrap();
+    assert_eq!(first, response.as_slice());
+    let (second, rest) = next_rtu_response_frame(rest).unwrap().unwrap();
+    assert_eq!(second, response.as_slice());
+    assert!(rest.is_empty());
+    assert_eq!(next_rtu_response_frame(&responses[..2]), Ok(None));
+    // out of sync
+    assert_eq!(
+        next_rtu_request_frame(&stream[1..]),
+        Err(ErrorKind::FrameCRCError)
+    );
+    let mut broken = response.clone();
+    broken[3] ^= 0xff;
+    assert_eq!(
+        next_rtu_response_frame(&broken),
+        Err(ErrorKind::FrameCRCError)
+    );
+    // unknown function, the response length can not be guessed
+    assert_eq!(
+        next_rtu_response_frame(&[1, 0x5a, 0]),
+        Err(ErrorKind::FrameBroken)
+    );
+}
+
+#[test]
+fn test_std_client_generate_from_write() {
+    let mut upstream = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    upstream
+        .generate_set_holdings_bulk(100, &[0x1234, 0x5678, 0x9abc], &mut request)
+        .unwrap();
+    let mut response = Vec::new();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    let write = frame.get_external_write().unwrap();
+    assert!(matches!(write, Write::Words(_)));
+    let mut downstream = ModbusRequest::new(7, ModbusProto::Rtu);
+    let mut forwarded = Vec::new();
+    downstream
+        .generate_from_write(&write, &mut forwarded)
+        .unwrap();
+    // the same PDU, different unit id and the checksum
+    assert_eq!(forwarded[0], 7);
+    assert_eq!(forwarded[1..forwarded.len() - 2], request[7..]);
+    assert_eq!(downstream.func, consts::MODBUS_SET_HOLDINGS_BULK);
+    // single coil
+    upstream.generate_set_coil(5, true, &mut request).unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    let write = frame.get_external_write().unwrap();
+    downstream
+        .generate_from_write(&write, &mut forwarded)
+        .unwrap();
+    assert_eq!(forwarded[1..forwarded.len() - 2], request[7..]);
+    // multiple coils
+    upstream
+        .generate_set_coils_bulk(
+            3,
+            &[true, false, true, true, false, false, true, true, true],
+            &mut request,
+        )
+        .unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    let write = frame.get_external_write().unwrap();
+    downstream
+        .generate_from_write(&write, &mut forwarded)
+        .unwrap();
+    assert_eq!(forwarded[1..forwarded.len() - 2], request[7..]);
+    // broken descriptor
+    let write = Write::Words(WriteWords {
+        address: 0,
+        count: 2,
+        data: &[0, 1],
+    });
+    assert_eq!(
+        downstream.generate_from_write(&write, &mut forwarded),
+        Err(ErrorKind::OOB)
+    );
+}
+
+#[test]
+fn test_std_context_holdings_string() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holdings_from_str(10, "Device-42").unwrap();
+    assert_eq!(ctx.get_holding(14).unwrap(), u16::from_be_bytes([b'2', 0]));
+    let mut result = String::new();
+    ctx.get_holdings_as_string(10, 8, &mut result).unwrap();
+    assert_eq!(result, "Device-42");
+    // the same as the client
+    let mut response = Vec::new();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(10, 5, &mut request).unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    frame.process_read(&ctx).unwrap();
+    let mut parsed = String::new();
+    mreq.parse_string(&response, &mut parsed).unwrap();
+    assert_eq!(parsed, result);
+    assert_eq!(
+        ctx.set_holdings_from_str(998, "Device-42"),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(ctx.get_holding(998).unwrap(), 0);
+    ctx.set_holding(20, 0xfffe).unwrap();
+    assert_eq!(
+        ctx.get_holdings_as_string(20, 1, &mut result),
+        Err(ErrorKind::Utf8Error)
+    );
+}
+
+#[test]
+fn test_std_client_parse_bool_padding() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_get_coils(0, 10, &mut request).unwrap();
+    // conforming response, the padding is zero
+    let mut response = vec![1, 1, 2, 0b1000_0001, 0b0000_0010];
+    response.extend_from_slice(&crc16(&response).to_le_bytes());
+    let mut strict = Vec::new();
+    mreq.parse_bool_exact(&response, &mut strict).unwrap();
+    let mut lenient = Vec::new();
+    mreq.parse_bool(&response, &mut lenient).unwrap();
+    assert_eq!(strict, lenient);
+    assert_eq!(strict.len(), 10);
+    assert!(strict[0] && strict[7] && strict[9] && !strict[8]);
+    // the padding is set
+    let mut response = vec![1, 1, 2, 0b1000_0001, 0b0100_0010];
+    response.extend_from_slice(&crc16(&response).to_le_bytes());
+    assert_eq!(
+        mreq.parse_bool_exact(&response, &mut Vec::new()),
+        Err(ErrorKind::FrameBroken)
+    );
+    let mut lenient = Vec::new();
+    mreq.parse_bool(&response, &mut lenient).unwrap();
+    assert_eq!(lenient, strict);
+    let mut lenient_u8 = Vec::new();
+    mreq.parse_bool_u8(&response, &mut lenient_u8).unwrap();
+    assert_eq!(lenient_u8.len(), 10);
+    assert_eq!(lenient_u8[9], 1);
+}
+
+#[test]
+fn test_std_process_frame_full() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holdings_bulk(10, &[1, 2]).unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    let mut response = Vec::new();
+    // read
+    mreq.generate_get_holdings(10, 2, &mut request).unwrap();
+    let outcome =
+        process_frame_full(1, &request, ModbusProto::Rtu, &mut ctx, &mut response).unwrap();
+    assert_eq!(
+        outcome,
+        FrameOutcome {
+            response_required: true,
+            changes: None
+        }
+    );
+    let mut result = Vec::new();
+    mreq.parse_u16(&response, &mut result).unwrap();
+    assert_eq!(result, [1, 2]);
+    // write
+    mreq.generate_set_holdings_bulk(20, &[3, 4, 5], &mut request)
+        .unwrap();
+    let outcome =
+        process_frame_full(1, &request, ModbusProto::Rtu, &mut ctx, &mut response).unwrap();
+    assert!(outcome.response_required);
+    assert_eq!(
+        outcome.changes,
+        Some(Changes::Holdings { reg: 20, count: 3 })
+    );
+    mreq.parse_ok(&response).unwrap();
+    assert_eq!(ctx.get_holding(22).unwrap(), 5);
+    // failed write
+    mreq.generate_set_holding(5000, 1, &mut request).unwrap();
+    let outcome =
+        process_frame_full(1, &request, ModbusProto::Rtu, &mut ctx, &mut response).unwrap();
+    assert!(outcome.response_required);
+    assert_eq!(outcome.changes, None);
+    assert_eq!(mreq.parse_ok(&response), Err(ErrorKind::IllegalDataAddress));
+    // another unit
+    let outcome =
+        process_frame_full(2, &request, ModbusProto::Rtu, &mut ctx, &mut response).unwrap();
+    assert!(!outcome.response_required);
+    assert!(response.is_empty());
+}
+
+#[test]
+fn test_std_frame_write_target() {
+    let mut ctx = ModbusStorageSmall::new();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut request = Vec::new();
+    let mut response = Vec::new();
+    mreq.generate_set_holdings_bulk(100, &[1, 2, 3, 4], &mut request)
+        .unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.write_target(), Some((RegKind::Holding, 100, 4)));
+    // veto
+    frame
+        .set_modbus_error_if_unset(&ErrorKind::IllegalDataAddress)
+        .unwrap();
+    assert_eq!(frame.write_target(), None);
+    frame.finalize_response().unwrap();
+    assert_eq!(mreq.parse_ok(&response), Err(ErrorKind::IllegalDataAddress));
+    assert_eq!(ctx.get_holding(100).unwrap(), 0);
+    mreq.generate_set_coil(7, true, &mut request).unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.write_target(), Some((RegKind::Coil, 7, 1)));
+    frame.process_write(&mut ctx).unwrap();
+    assert!(ctx.get_coil(7).unwrap());
+    mreq.generate_read_write_multiple_registers(0, 2, 50, &[1, 2, 3], &mut request)
+        .unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.write_target(), Some((RegKind::Holding, 50, 3)));
+    mreq.generate_get_holdings(100, 4, &mut request).unwrap();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert_eq!(frame.write_target(), None);
+}
+
+#[test]
+fn test_std_client_parse_ok_retryable() {
+    let mut mreq = ModbusRequestBuilder::new()
+        .proto(ModbusProto::Rtu)
+        .retries(2)
+        .build();
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    let mut response = vec![1, 3, 2, 0, 5];
+    response.extend_from_slice(&crc16(&response).to_le_bytes());
+    let mut broken = response.clone();
+    broken[4] = 6;
+    for attempt in 1..=2 {
+        assert_eq!(
+            mreq.parse_ok_retryable(&broken),
+            Err(RetryDecision {
+                retry: true,
+                attempt,
+                error: ErrorKind::FrameCRCError
+            })
+        );
+    }
+    let decision = mreq.parse_ok_retryable(&broken).unwrap_err();
+    assert!(!decision.retry);
+    assert_eq!(decision.attempt, 3);
+    assert_eq!(mreq.attempt(), 0);
+    // the counter is reset on success
+    mreq.parse_ok_retryable(&broken).unwrap_err();
+    assert_eq!(mreq.attempt(), 1);
+    mreq.parse_ok_retryable(&response).unwrap();
+    assert_eq!(mreq.attempt(), 0);
+    // exceptions
+    for (code, retry) in [
+        (consts::MODBUS_ERROR_ILLEGAL_DATA_ADDRESS, false),
+        (6, true),
+    ] {
+        let mut error = Vec::new();
+        mreq.generate_error_response(code, &mut error).unwrap();
+        assert_eq!(mreq.parse_ok_retryable(&error).unwrap_err().retry, retry);
+    }
+    // no retries by default
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    assert!(!mreq.parse_ok_retryable(&broken).unwrap_err().retry);
+}
+
+#[test]
+fn test_std_context_holding_bcd() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holding_bcd(10, 1234).unwrap();
+    assert_eq!(ctx.get_holding(10).unwrap(), 0x1234);
+    assert_eq!(ctx.get_holding_bcd(10).unwrap(), 1234);
+    ctx.set_holding_bcd(11, 9999).unwrap();
+    assert_eq!(ctx.get_holding(11).unwrap(), 0x9999);
+    assert_eq!(ctx.get_holding_bcd(11).unwrap(), 9999);
+    ctx.set_holding_bcd(12, 7).unwrap();
+    assert_eq!(ctx.get_holding(12).unwrap(), 0x0007);
+    assert_eq!(
+        ctx.set_holding_bcd(10, 10000),
+        Err(ErrorKind::IllegalDataValue)
+    );
+    assert_eq!(ctx.get_holding(10).unwrap(), 0x1234);
+    ctx.set_holding(13, 0x1A).unwrap();
+    assert_eq!(ctx.get_holding_bcd(13), Err(ErrorKind::IllegalDataValue));
+    assert_eq!(ctx.get_holding_bcd(5000), Err(ErrorKind::OOBContext));
+}
+
+#[test]
+fn test_std_modbus_map() {
+    modbus_map! {
+        Temperature: holding @ 100 as f32;
+        Mode: holding @ 102 as u16;
+        Counter: input @ 10 as u64;
+        Running: coil @ 5 as bool;
+        Alarm: discrete @ 6 as bool;
+    }
+    let mut ctx = ModbusStorageSmall::new();
+    Temperature::set(&mut ctx, 1234.5).unwrap();
+    Mode::set(&mut ctx, 7).unwrap();
+    Counter::set(&mut ctx, 0x0102_0304_0506_0708).unwrap();
+    Running::set(&mut ctx, true).unwrap();
+    Alarm::set(&mut ctx, true).unwrap();
+    assert_eq!(Temperature::get(&ctx).unwrap(), 1234.5);
+    assert_eq!(ctx.get_holdings_as_f32(100).unwrap(), 1234.5);
+    assert_eq!(Mode::get(&ctx).unwrap(), 7);
+    assert_eq!(ctx.holdings[102], 7);
+    assert_eq!(Counter::get(&ctx).unwrap(), 0x0102_0304_0506_0708);
+    assert_eq!(ctx.inputs[10..14], [0x0102, 0x0304, 0x0506, 0x0708]);
+    assert!(Running::get(&ctx).unwrap());
+    assert!(ctx.coils[5]);
+    assert!(Alarm::get(&ctx).unwrap());
+    assert_eq!((Temperature::REG, Temperature::COUNT), (100, 2));
+    assert_eq!((Mode::REG, Mode::COUNT), (102, 1));
+    assert_eq!(Counter::COUNT, 4);
+    assert_eq!(Running::COUNT, 1);
+    // the compile-time range check
+    assert!(server::map::check(&[(3, 100, 2), (3, 102, 1), (2, 101, 4)]));
+    assert!(!server::map::check(&[(3, 100, 2), (3, 101, 1)]));
+    assert!(!server::map::check(&[(0, 5, 1), (3, 0, 1), (0, 5, 1)]));
+    assert!(server::map::check(&[(3, 65535, 1)]));
+    assert!(!server::map::check(&[(3, 65535, 2)]));
+}
+
+#[test]
+fn test_std_context_f32_byte_order() {
+    let mut ctx = ModbusStorageSmall::new();
+    let value: f32 = 1234.567;
+    // 0x449A_5225
+    assert_eq!(value.to_bits().to_be_bytes(), [0x44, 0x9A, 0x52, 0x25]);
+    assert_eq!(FloatByteOrder::default(), FloatByteOrder::ABCD);
+    for (order, regs) in [
+        (FloatByteOrder::ABCD, [0x449A, 0x5225]),
+        (FloatByteOrder::CDAB, [0x5225, 0x449A]),
+        (FloatByteOrder::BADC, [0x9A44, 0x2552]),
+        (FloatByteOrder::DCBA, [0x2552, 0x9A44]),
+    ] {
+        ctx.set_holdings_from_f32_ordered(10, value, order).unwrap();
+        assert_eq!(ctx.holdings[10..12], regs, "{:?}", order);
+        assert_eq!(ctx.get_holdings_as_f32_ordered(10, order).unwrap(), value);
+    }
+    ctx.set_holdings_from_f32(10, value).unwrap();
+    assert_eq!(
+        ctx.get_holdings_as_f32_ordered(10, FloatByteOrder::ABCD)
+            .unwrap(),
+        value
+    );
+    assert_eq!(
+        ctx.set_holdings_from_f32_ordered(u16::MAX, value, FloatByteOrder::DCBA),
+        Err(ErrorKind::OOBContext)
+    );
+}
+
+#[test]
+fn test_std_supported_functions() {
+    let supported = supported_functions();
+    assert_eq!(
+        supported,
+        [1, 2, 3, 4, 5, 6, 7, 8, 11, 15, 16, 17, 22, 23, 43]
+    );
+    for func in [9, 10, 12, 13, 14, 20, 21, 24, 42, 0x5A] {
+        assert!(!supported.contains(&func));
+    }
+    // the list matches what the parser handles
+    for func in 1..0x80 {
+        let mut request = [0; 32];
+        request[..12].copy_from_slice(&[0, 1, 0, 0, 0, 6, 1, func, 0x0e, 1, 0, 1]);
+        let mut result = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut result);
+        if frame.parse().is_ok() {
+            assert_eq!(
+                frame.unsupported_function().is_none(),
+                supported.contains(&func),
+                "function {}",
+                func
+            );
+        } else {
+            assert!(supported.contains(&func), "function {}", func);
+        }
+    }
+}
+
+#[test]
+fn test_std_client_refresh_tr_id() {
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 0x00ff);
+    mreq.auto_increment_tr_id(true);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(100, 1, &mut request).unwrap();
+    let generated = request.clone();
+    mreq.refresh_tr_id(&mut request).unwrap();
+    assert_eq!(mreq.tr_id, 0x0101);
+    assert_eq!(request[..2], [0x01, 0x01]);
+    assert_ne!(request[..2], generated[..2]);
+    assert_eq!(request[2..], generated[2..]);
+    // the response to the refreshed request is accepted
+    let response = [0x01, 0x01, 0, 0, 0, 5, 1, 3, 2, 0, 7];
+    let mut result = Vec::new();
+    mreq.parse_u16(&response, &mut result).unwrap();
+    assert_eq!(result, [7]);
+    // without auto increment, the current id is written
+    mreq.auto_increment_tr_id(false);
+    mreq.tr_id = 0x1234;
+    mreq.refresh_tr_id(&mut request).unwrap();
+    assert_eq!(request[..2], [0x12, 0x34]);
+    assert_eq!(request[2..], generated[2..]);
+    assert_eq!(mreq.refresh_tr_id(&mut [0]), Err(ErrorKind::OOB));
+    // nothing to refresh for RTU
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    mreq.auto_increment_tr_id(true);
+    mreq.generate_get_holdings(100, 10, &mut request).unwrap();
+    let generated = request.clone();
+    mreq.refresh_tr_id(&mut request).unwrap();
+    assert_eq!(request, generated);
+}
+
+#[test]
+fn test_std_ascii_frame_terminator() {
+    let mut buf: ModbusFrameBuf = [0; 256];
+    // missing terminator
+    let frame = b":01030002000AF0";
+    assert_eq!(
+        parse_ascii_frame(frame, frame.len(), &mut buf, 0),
+        Err(ErrorKind::FrameBroken)
+    );
+    // the terminator is beyond data_len
+    let frame = b":01030002000AF0\r\n";
+    assert_eq!(
+        parse_ascii_frame(frame, frame.len() - 2, &mut buf, 0),
+        Err(ErrorKind::FrameBroken)
+    );
+    assert_eq!(parse_ascii_frame(frame, frame.len(), &mut buf, 0), Ok(7));
+    // truncated in the middle of a byte
+    let frame = b":01030002000AF";
+    assert_eq!(
+        parse_ascii_frame(frame, frame.len(), &mut buf, 0),
+        Err(ErrorKind::FrameBroken)
+    );
+    // partial parsing in chunks
+    let mut buf: ModbusFrameBuf = [0; 256];
+    assert_eq!(parse_ascii_frame_partial(b":010300", 7, &mut buf, 0), Ok(3));
+    assert_eq!(parse_ascii_frame(b"02000AF0\r\n", 10, &mut buf, 3), Ok(4));
+    assert_eq!(buf[..7], [0x01, 0x03, 0x00, 0x02, 0x00, 0x0A, 0xF0]);
+    // exactly at the frame buffer boundary
+    let mut frame = vec![b':'];
+    frame.extend_from_slice(&[b'A'; 255 * 2]);
+    frame.extend_from_slice(b"\r\n");
+    assert_eq!(parse_ascii_frame(&frame, frame.len(), &mut buf, 0), Ok(255));
+    assert!(buf[..255].iter().all(|b| *b == 0xAA));
+    let mut frame = vec![b':'];
+    frame.extend_from_slice(&[b'A'; 256 * 2]);
+    frame.extend_from_slice(b"\r\n");
+    assert_eq!(
+        parse_ascii_frame(&frame, frame.len(), &mut buf, 0),
+        Err(ErrorKind::OOB)
+    );
+}
+
+#[test]
+fn test_std_client_reset() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holding(0, 7).unwrap();
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    let mut holdings_response = Vec::new();
+    let mut result = Vec::new();
+    // generate / parse bracket a transaction
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    process_frame_full(
+        1,
+        &request,
+        ModbusProto::Rtu,
+        &mut ctx,
+        &mut holdings_response,
+    )
+    .unwrap();
+    mreq.parse_u16(&holdings_response, &mut result).unwrap();
+    assert_eq!(result, [7]);
+    // a response to a different function is rejected
+    mreq.generate_get_coils(0, 1, &mut request).unwrap();
+    let mut bools = Vec::new();
+    assert!(mreq.parse_bool(&holdings_response, &mut bools).is_err());
+    // after the reset, nothing is accepted
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    mreq.reset();
+    assert_eq!((mreq.func, mreq.reg, mreq.count), (0, 0, 0));
+    result.clear();
+    assert!(mreq.parse_u16(&holdings_response, &mut result).is_err());
+    assert!(result.is_empty());
+    // the request is reusable
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    mreq.parse_u16(&holdings_response, &mut result).unwrap();
+    assert_eq!(result, [7]);
+}
+
+#[test]
+fn test_std_context_i16_bulk() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_inputs_bulk(10, &[0xFFFF, 0x8000, 0x7FFF, 5])
+        .unwrap();
+    ctx.set_holdings_bulk(20, &[0xFFFE, 1]).unwrap();
+    let mut result: Vec<i16> = Vec::new();
+    ctx.get_inputs_as_i16_bulk(10, 4, &mut result).unwrap();
+    assert_eq!(result, [-1, i16::MIN, i16::MAX, 5]);
+    result.clear();
+    ctx.get_holdings_as_i16_bulk(20, 2, &mut result).unwrap();
+    assert_eq!(result, [-2, 1]);
+    // nothing is appended on errors
+    result.clear();
+    assert_eq!(
+        ctx.get_inputs_as_i16_bulk(999, 2, &mut result),
+        Err(ErrorKind::OOBContext)
+    );
+    assert!(result.is_empty());
+    // the same values as parsed by the client
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    let mut response = Vec::new();
+    mreq.generate_get_inputs(10, 4, &mut request).unwrap();
+    process_frame_full(1, &request, ModbusProto::Rtu, &mut ctx, &mut response).unwrap();
+    let mut parsed = Vec::new();
+    mreq.parse_i16(&response, &mut parsed).unwrap();
+    result.clear();
+    ctx.get_inputs_as_i16_bulk(10, 4, &mut result).unwrap();
+    assert_eq!(parsed, result);
+}
+
+#[test]
+fn test_std_translate_frame() {
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 0x1234);
+    let mut tcp = Vec::new();
+    mreq.generate_set_holdings_bulk(10, &[1, 2, 3], &mut tcp)
+        .unwrap();
+    // TCP -> RTU -> TCP
+    let mut rtu = Vec::new();
+    translate_frame(&tcp, ModbusProto::Tcp, ModbusProto::Rtu, 0, &mut rtu).unwrap();
+    let mut rtu_mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut expected = Vec::new();
+    rtu_mreq
+        .generate_set_holdings_bulk(10, &[1, 2, 3], &mut expected)
+        .unwrap();
+    assert_eq!(rtu, expected);
+    let mut tcp_back = Vec::new();
+    translate_frame(
+        &rtu,
+        ModbusProto::Rtu,
+        ModbusProto::Tcp,
+        0x1234,
+        &mut tcp_back,
+    )
+    .unwrap();
+    assert_eq!(tcp_back, tcp);
+    // the transaction id is supplied by the caller
+    translate_frame(
+        &rtu,
+        ModbusProto::Rtu,
+        ModbusProto::Udp,
+        0x0102,
+        &mut tcp_back,
+    )
+    .unwrap();
+    assert_eq!(tcp_back[..2], [0x01, 0x02]);
+    assert_eq!(tcp_back[2..], tcp[2..]);
+    // the translated request is processed by the RTU server
+    let mut ctx = ModbusStorageSmall::new();
+    let mut rtu_response = Vec::new();
+    process_frame_full(1, &rtu, ModbusProto::Rtu, &mut ctx, &mut rtu_response).unwrap();
+    assert_eq!(ctx.holdings[10..13], [1, 2, 3]);
+    // and the response is translated back
+    let mut tcp_response = Vec::new();
+    translate_frame(
+        &rtu_response,
+        ModbusProto::Rtu,
+        ModbusProto::Tcp,
+        mreq.tr_id,
+        &mut tcp_response,
+    )
+    .unwrap();
+    mreq.parse_ok(&tcp_response).unwrap();
+    // ASCII (binary frames with LRC)
+    let mut ascii = Vec::new();
+    translate_frame(&tcp, ModbusProto::Tcp, ModbusProto::Ascii, 0, &mut ascii).unwrap();
+    let mut ascii_mreq = ModbusRequest::new(1, ModbusProto::Ascii);
+    ascii_mreq
+        .generate_set_holdings_bulk(10, &[1, 2, 3], &mut expected)
+        .unwrap();
+    assert_eq!(ascii, expected);
+    translate_frame(
+        &ascii,
+        ModbusProto::Ascii,
+        ModbusProto::Rtu,
+        0,
+        &mut tcp_back,
+    )
+    .unwrap();
+    assert_eq!(tcp_back, rtu);
+    // broken frames
+    rtu[3] ^= 0xff;
+    assert_eq!(
+        translate_frame(&rtu, ModbusProto::Rtu, ModbusProto::Tcp, 1, &mut tcp_back),
+        Err(ErrorKind::FrameCRCError)
+    );
+    assert_eq!(
+        translate_frame(
+            &tcp[..tcp.len() - 1],
+            ModbusProto::Tcp,
+            ModbusProto::Rtu,
+            0,
+            &mut tcp_back
+        ),
+        Err(ErrorKind::FrameBroken)
+    );
+}
+
+#[test]
+fn test_std_memory_parity_error() {
+    assert_eq!(
+        ErrorKind::from_modbus_error(consts::MODBUS_ERROR_MEMORY_PARITY_ERROR),
+        ErrorKind::MemoryParityError
+    );
+    assert_eq!(
+        ErrorKind::MemoryParityError.to_modbus_error(),
+        Ok(consts::MODBUS_ERROR_MEMORY_PARITY_ERROR)
+    );
+    assert!(ErrorKind::MemoryParityError.is_modbus_error());
+    assert!(!ErrorKind::MemoryParityError.is_retryable());
+    for code in 1..=0x0B {
+        let err = ErrorKind::from_modbus_error(code);
+        if err != ErrorKind::UnknownError {
+            assert_eq!(err.to_modbus_error(), Ok(code));
+        }
+    }
+    // an exception response is interpreted by the client
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    let mut response = Vec::new();
+    mreq.generate_error_response(consts::MODBUS_ERROR_MEMORY_PARITY_ERROR, &mut response)
+        .unwrap();
+    assert_eq!(mreq.parse_ok(&response), Err(ErrorKind::MemoryParityError));
+}
+
+#[test]
+fn test_std_client_read_file_record() {
+    // the spec example
+    let groups = [
+        FileRecordRequest {
+            file_number: 4,
+            record_number: 1,
+            record_length: 2,
+        },
+        FileRecordRequest {
+            file_number: 3,
+            record_number: 9,
+            record_length: 2,
+        },
+    ];
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    mreq.generate_read_file_record(&groups, &mut request)
+        .unwrap();
+    assert_eq!(
+        request[..request.len() - 2],
+        [
+            0x01, 0x14, 0x0E, 0x06, 0x00, 0x04, 0x00, 0x01, 0x00, 0x02, 0x06, 0x00, 0x03, 0x00,
+            0x09, 0x00, 0x02
+        ]
+    );
+    assert_eq!(
+        request[request.len() - 2..],
+        crc16(&request[..request.len() - 2]).to_le_bytes()
+    );
+    let mut response = vec![
+        0x01, 0x14, 0x0C, 0x05, 0x06, 0x0D, 0xFE, 0x00, 0x20, 0x05, 0x06, 0x33, 0xCD, 0x00, 0x40,
+    ];
+    response.extend_from_slice(&crc16(&response).to_le_bytes());
+    assert_eq!(
+        guess_response_frame_len(&response[..3], ModbusProto::Rtu).unwrap(),
+        17
+    );
+    let mut records = Vec::new();
+    mreq.parse_file_records(&response, &mut records).unwrap();
+    assert_eq!(
+        records,
+        [&[0x0D, 0xFE, 0x00, 0x20][..], &[0x33, 0xCD, 0x00, 0x40][..]]
+    );
+    // TCP
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 5);
+    mreq.generate_read_file_record(&groups[..1], &mut request)
+        .unwrap();
+    assert_eq!(
+        request,
+        [0, 5, 0, 0, 0, 10, 0x01, 0x14, 0x07, 0x06, 0x00, 0x04, 0x00, 0x01, 0x00, 0x02]
+    );
+    let response = [
+        0, 5, 0, 0, 0, 9, 0x01, 0x14, 0x06, 0x05, 0x06, 0x0D, 0xFE, 0x00, 0x20,
+    ];
+    records.clear();
+    mreq.parse_file_records(&response, &mut records).unwrap();
+    assert_eq!(records, [&[0x0D, 0xFE, 0x00, 0x20][..]]);
+    // the number of groups must match the request
+    let response = [
+        0, 5, 0, 0, 0, 14, 0x01, 0x14, 0x0C, 0x05, 0x06, 0x0D, 0xFE, 0x00, 0x20, 0x05, 0x06, 0x33,
+        0xCD, 0x00, 0x40,
+    ];
+    assert_eq!(
+        mreq.parse_file_records(&response, &mut records),
+        Err(ErrorKind::FrameBroken)
+    );
+    // wrong reference type
+    let response = [
+        0, 5, 0, 0, 0, 9, 0x01, 0x14, 0x06, 0x05, 0x07, 0x0D, 0xFE, 0x00, 0x20,
+    ];
+    assert_eq!(
+        mreq.parse_file_records(&response, &mut records),
+        Err(ErrorKind::FrameBroken)
+    );
+    // exceptions
+    let mut response = Vec::new();
+    mreq.generate_error_response(0x08, &mut response).unwrap();
+    assert_eq!(
+        mreq.parse_file_records(&response, &mut records),
+        Err(ErrorKind::MemoryParityError)
+    );
+    // invalid requests
+    assert_eq!(
+        mreq.generate_read_file_record(&[], &mut request),
+        Err(ErrorKind::OOB)
+    );
+    let group = FileRecordRequest {
+        file_number: 1,
+        record_number: 10000,
+        record_length: 1,
+    };
+    assert_eq!(
+        mreq.generate_read_file_record(&[group], &mut request),
+        Err(ErrorKind::OOB)
+    );
+    let group = FileRecordRequest {
+        file_number: 1,
+        record_number: 0,
+        record_length: 122,
+    };
+    assert_eq!(
+        mreq.generate_read_file_record(&[group], &mut request),
+        Err(ErrorKind::OOB)
+    );
+    let group = FileRecordRequest {
+        file_number: 1,
+        record_number: 0,
+        record_length: 0,
+    };
+    mreq.generate_read_file_record(&[group; 35], &mut request)
+        .unwrap();
+    assert_eq!(request[8], 245);
+    assert_eq!(
+        mreq.generate_read_file_record(&[group; 36], &mut request),
+        Err(ErrorKind::OOB)
+    );
+}
+
+#[test]
+fn test_std_write_multiple_zero_count() {
+    let mut ctx = ModbusStorageSmall::new();
+    for (request, func) in [
+        // FC15, count 0, byte count 0
+        (vec![1, 15, 0, 10, 0, 0, 0], 15),
+        // FC16, count 0, byte count 0
+        (vec![1, 16, 0, 10, 0, 0, 0], 16),
+    ] {
+        let mut request = request;
+        let crc = crc16(&request);
+        request.extend_from_slice(&crc.to_le_bytes());
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut response);
+        frame.parse().unwrap();
+        assert!(frame.response_required);
+        assert!(!frame.processing_required);
+        assert_eq!(frame.error, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE);
+        assert_eq!(frame.write_target(), None);
         frame.finalize_response().unwrap();
-        let mut result = String::new();
-        mreq.parse_string(&response, &mut result).unwrap();
-        assert_eq!(result, holdstr);
+        assert_eq!(
+            response[..3],
+            [1, func | 0x80, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE]
+        );
+        // nothing is written with process_frame_full
+        let outcome =
+            process_frame_full(1, &request, ModbusProto::Rtu, &mut ctx, &mut response).unwrap();
+        assert_eq!(outcome.changes, None);
+        assert_eq!(
+            response[..3],
+            [1, func | 0x80, consts::MODBUS_ERROR_ILLEGAL_DATA_VALUE]
+        );
+    }
+}
 
-        // reading inputs
-        ctx.clear_holdings();
-        ctx.clear_inputs();
-        for c in 200..200 + holdings.len() {
-            ctx.set_input(u16::try_from(c).unwrap(), holdings[c - 200])
-                .unwrap();
+#[cfg(feature = "async")]
+#[test]
+fn test_std_process_async() {
+    use crate::server::async_context::{AsyncModbusContext, ContextFuture};
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake};
+
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
         }
-        let mut mreq = ModbusRequest::new(3, *proto);
-        let mut request = Vec::new();
-        mreq.generate_get_inputs(200, u16::try_from(holdings.len()).unwrap(), &mut request)
-            .unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
+    }
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = std::pin::pin!(fut);
+        let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(v) = fut.as_mut().poll(&mut cx) {
+                return v;
             }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
-        } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
+            std::thread::park();
         }
-        let mut frame = ModbusFrame::new(3, &framebuf, *proto, &mut response);
+    }
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    // a mock storage, which returns canned holdings
+    struct MockDb {
+        holdings: Vec<u16>,
+        coils: Vec<bool>,
+    }
+
+    impl MockDb {
+        async fn fetch<T: Copy>(data: &[T], reg: u16, out: &mut [T]) -> Result<(), ErrorKind> {
+            let reg = usize::from(reg);
+            let values = data
+                .get(reg..reg + out.len())
+                .ok_or(ErrorKind::OOBContext)?;
+            out.copy_from_slice(values);
+            Ok(())
+        }
+
+        async fn store<T: Copy>(data: &mut [T], reg: u16, values: &[T]) -> Result<(), ErrorKind> {
+            let reg = usize::from(reg);
+            data.get_mut(reg..reg + values.len())
+                .ok_or(ErrorKind::OOBContext)?
+                .copy_from_slice(values);
+            Ok(())
+        }
+    }
+
+    impl AsyncModbusContext for MockDb {
+        fn get_coils_into<'a>(&'a self, reg: u16, out: &'a mut [bool]) -> ContextFuture<'a, ()> {
+            Box::pin(Self::fetch(&self.coils, reg, out))
+        }
+        fn get_discretes_into<'a>(
+            &'a self,
+            _reg: u16,
+            _out: &'a mut [bool],
+        ) -> ContextFuture<'a, ()> {
+            Box::pin(async { Err(ErrorKind::SlaveDeviceFailure) })
+        }
+        fn get_inputs_into<'a>(&'a self, _reg: u16, _out: &'a mut [u16]) -> ContextFuture<'a, ()> {
+            Box::pin(async { Err(ErrorKind::CommunicationError) })
+        }
+        fn get_holdings_into<'a>(&'a self, reg: u16, out: &'a mut [u16]) -> ContextFuture<'a, ()> {
+            Box::pin(Self::fetch(&self.holdings, reg, out))
+        }
+        fn set_coils_bulk<'a>(&'a mut self, reg: u16, values: &'a [bool]) -> ContextFuture<'a, ()> {
+            Box::pin(Self::store(&mut self.coils, reg, values))
+        }
+        fn set_holdings_bulk<'a>(
+            &'a mut self,
+            reg: u16,
+            values: &'a [u16],
+        ) -> ContextFuture<'a, ()> {
+            Box::pin(Self::store(&mut self.holdings, reg, values))
+        }
+    }
+
+    let mut db = MockDb {
+        holdings: (0..100).collect(),
+        coils: vec![false; 100],
+    };
+    let mut ctx = ModbusStorageSmall::new();
+    for (i, v) in db.holdings.iter().enumerate() {
+        ctx.set_holding(u16::try_from(i).unwrap(), *v).unwrap();
+    }
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        let mut request = Vec::new();
+        // read holdings
+        mreq.generate_get_holdings(10, 3, &mut request).unwrap();
+        let mut response = Vec::new();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
         frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(frame.readonly);
-        frame.process_read(&*ctx).unwrap();
-        assert_eq!(frame.error, 0);
+        let fut = frame.process_read_async(&db);
+        assert_send(&fut);
+        block_on(fut).unwrap();
         frame.finalize_response().unwrap();
+        let mut expected = Vec::new();
+        process_frame_full(1, &request, proto, &mut ctx, &mut expected).unwrap();
+        assert_eq!(response, expected);
         let mut result = Vec::new();
         mreq.parse_u16(&response, &mut result).unwrap();
-        assert_eq!(result, holdings);
-
-        // set single holding
-        ctx.clear_holdings();
-        ctx.clear_inputs();
-        let mut mreq = ModbusRequest::new(4, *proto);
-        let mut request = Vec::new();
-        mreq.generate_set_holding(100, 7777, &mut request).unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
-        } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
-        }
-        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+        assert_eq!(result, [10, 11, 12]);
+        // out of the storage
+        mreq.generate_get_holdings(99, 2, &mut request).unwrap();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
         frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(!frame.readonly);
-        frame.process_write(&mut *ctx).unwrap();
-        assert_eq!(frame.error, 0);
+        block_on(frame.process_read_async(&db)).unwrap();
+        frame.finalize_response().unwrap();
+        assert_eq!(mreq.parse_ok(&response), Err(ErrorKind::IllegalDataAddress));
+        // Modbus errors are reported to the client, other errors are returned
+        mreq.generate_get_discretes(0, 2, &mut request).unwrap();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        block_on(frame.process_read_async(&db)).unwrap();
+        frame.finalize_response().unwrap();
+        assert_eq!(mreq.parse_ok(&response), Err(ErrorKind::SlaveDeviceFailure));
+        mreq.generate_get_inputs(0, 2, &mut request).unwrap();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        assert_eq!(
+            block_on(frame.process_read_async(&db)),
+            Err(ErrorKind::CommunicationError)
+        );
+        // write holdings
+        mreq.generate_set_holdings_bulk(20, &[1, 2], &mut request)
+            .unwrap();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        assert_eq!(
+            block_on(frame.process_read_async(&db)),
+            Err(ErrorKind::WriteCallOnReadFrame)
+        );
+        block_on(frame.process_write_async(&mut db)).unwrap();
         frame.finalize_response().unwrap();
         mreq.parse_ok(&response).unwrap();
-        assert!(ctx.get_coil(100).unwrap());
-
-        // set holding oob
-        let mut mreq = ModbusRequest::new(4, *proto);
-        let mut request = Vec::new();
-        mreq.generate_set_holding(10001, 0x7777, &mut request)
+        assert_eq!(db.holdings[20..22], [1, 2]);
+        // write and read coils
+        mreq.generate_set_coils_bulk(5, &[true, false, true], &mut request)
             .unwrap();
-        let mut response = Vec::new();
-        let mut framebuf: ModbusFrameBuf = [0; 256];
-        if *proto == ModbusProto::Rtu {
-            let mut ascii_frame = Vec::new();
-            generate_ascii_frame(&request, &mut ascii_frame).unwrap();
-            for c in &mut framebuf {
-                *c = 0;
-            }
-            parse_ascii_frame(&ascii_frame, ascii_frame.len(), &mut framebuf, 0).unwrap();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        block_on(frame.process_write_async(&mut db)).unwrap();
+        frame.finalize_response().unwrap();
+        mreq.parse_ok(&response).unwrap();
+        assert_eq!(db.coils[4..9], [false, true, false, true, false]);
+        mreq.generate_set_coil(8, true, &mut request).unwrap();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        block_on(frame.process_write_async(&mut db)).unwrap();
+        frame.finalize_response().unwrap();
+        mreq.parse_ok(&response).unwrap();
+        mreq.generate_get_coils(4, 6, &mut request).unwrap();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut response);
+        frame.parse().unwrap();
+        block_on(frame.process_read_async(&db)).unwrap();
+        frame.finalize_response().unwrap();
+        let mut coils = Vec::new();
+        mreq.parse_bool(&response, &mut coils).unwrap();
+        assert_eq!(coils, [false, true, false, true, true, false]);
+        db.coils.fill(false);
+        db.holdings = (0..100).collect();
+    }
+    // counts above the function limit get Illegal Data Value, nothing is read
+    let mreq = ModbusRequest::new_tcp_udp(1, 1);
+    let request = [0, 1, 0, 0, 0, 6, 1, 3, 0, 0, 0, 200];
+    let mut response = Vec::new();
+    for enron in [false, true] {
+        let request = if enron {
+            [0, 1, 0, 0, 0, 6, 1, 3, 0, 0, 0, 100]
         } else {
-            framebuf[..request.len()].copy_from_slice(&request[..]);
-        }
-        let mut frame = ModbusFrame::new(4, &framebuf, *proto, &mut response);
+            request
+        };
+        response.clear();
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response).enron(enron);
         frame.parse().unwrap();
-        assert!(frame.response_required);
-        assert!(frame.processing_required);
-        assert_eq!(frame.error, 0);
-        assert!(!frame.readonly);
-        frame.process_write(&mut *ctx).unwrap();
-        assert_eq!(frame.error, 2);
+        block_on(frame.process_read_async(&db)).unwrap();
         frame.finalize_response().unwrap();
+        assert_eq!(mreq.parse_ok(&response), Err(ErrorKind::IllegalDataValue));
+    }
+    // frames for other units are not processed
+    let request = [0, 1, 0, 0, 0, 6, 2, 3, 0, 0, 0, 1];
+    response.clear();
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response);
+    frame.parse().unwrap();
+    assert!(!frame.processing_required);
+    block_on(frame.process_read_async(&db)).unwrap();
+    assert!(response.is_empty());
+    // lenient out-of-context reads are zero-filled
+    let request = [0, 1, 0, 0, 0, 6, 1, 3, 0, 98, 0, 4];
+    response.clear();
+    let mut frame =
+        ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut response).lenient_oob(true);
+    frame.parse().unwrap();
+    block_on(frame.process_read_async(&db)).unwrap();
+    frame.finalize_response().unwrap();
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 1);
+    let mut req = Vec::new();
+    mreq.generate_get_holdings(98, 4, &mut req).unwrap();
+    let mut result = Vec::new();
+    mreq.parse_u16(&response, &mut result).unwrap();
+    assert_eq!(result, [98, 99, 0, 0]);
+}
+
+#[test]
+fn test_std_inspect() {
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 7);
+    let mut request = Vec::new();
+    mreq.generate_get_holdings(100, 5, &mut request).unwrap();
+    let info = inspect(1, &request, ModbusProto::Tcp).unwrap();
+    assert_eq!(
+        info,
+        FrameInfo {
+            unit_id: 1,
+            func: 3,
+            reg: 100,
+            count: 5,
+            readonly: true,
+            is_broadcast: false,
+        }
+    );
+    // writes
+    let mut mreq = ModbusRequest::new(0, ModbusProto::Rtu);
+    mreq.generate_set_holdings_bulk(20, &[1, 2, 3], &mut request)
+        .unwrap();
+    let info = inspect(5, &request, ModbusProto::Rtu).unwrap();
+    assert_eq!(
+        (info.unit_id, info.func, info.reg, info.count),
+        (0, 16, 20, 3)
+    );
+    assert!(!info.readonly);
+    assert!(info.is_broadcast);
+    // other units
+    let mut mreq = ModbusRequest::new(2, ModbusProto::Rtu);
+    mreq.generate_get_coils(1, 1, &mut request).unwrap();
+    let info = inspect(1, &request, ModbusProto::Rtu).unwrap();
+    assert_eq!((info.unit_id, info.func), (2, 0));
+    assert_eq!(inspect(2, &request, ModbusProto::Rtu).unwrap().func, 1);
+    // broken frames
+    request[2] ^= 0xff;
+    assert_eq!(
+        inspect(2, &request, ModbusProto::Rtu),
+        Err(ErrorKind::FrameCRCError)
+    );
+}
+
+#[test]
+fn test_std_set_coils_from_u8_storages_agree() {
+    let mut ctx = ModbusStorageSmall::new();
+    let mut packed = ModbusStoragePackedSmall::new();
+    // 5 coils from one byte, the high bits are ignored
+    ctx.set_coils_from_u8(10, 5, &[0b1111_0101]).unwrap();
+    packed.set_coils_from_u8(10, 5, &[0b1111_0101]).unwrap();
+    ctx.set_discretes_from_u8(10, 5, &[0b1111_0101]).unwrap();
+    packed.set_discretes_from_u8(10, 5, &[0b1111_0101]).unwrap();
+    let expected = [false, true, false, true, false, true, false, false];
+    let mut coils = [false; 8];
+    let mut packed_coils = [false; 8];
+    ctx.get_coils_into(9, &mut coils).unwrap();
+    packed.get_coils_into(9, &mut packed_coils).unwrap();
+    assert_eq!(coils, expected);
+    assert_eq!(packed_coils, expected);
+    ctx.get_discretes_into(9, &mut coils).unwrap();
+    packed.get_discretes_into(9, &mut packed_coils).unwrap();
+    assert_eq!(coils, expected);
+    assert_eq!(packed_coils, expected);
+    // extra bytes are ignored
+    ctx.set_coils_from_u8(20, 8, &[0xff, 0xff]).unwrap();
+    packed.set_coils_from_u8(20, 8, &[0xff, 0xff]).unwrap();
+    assert!(!ctx.get_coil(28).unwrap());
+    assert!(!packed.get_coil(28).unwrap());
+    // not enough data, nothing is set
+    for count in [9, 16] {
         assert_eq!(
-            mreq.parse_ok(&response).err().unwrap(),
-            ErrorKind::IllegalDataAddress
+            ctx.set_coils_from_u8(30, count, &[0xff]),
+            Err(ErrorKind::OOB)
+        );
+        assert_eq!(
+            packed.set_coils_from_u8(30, count, &[0xff]),
+            Err(ErrorKind::OOB)
+        );
+        assert_eq!(
+            ctx.set_discretes_from_u8(30, count, &[0xff]),
+            Err(ErrorKind::OOB)
+        );
+        assert_eq!(
+            packed.set_discretes_from_u8(30, count, &[0xff]),
+            Err(ErrorKind::OOB)
         );
     }
+    assert!(!ctx.get_coil(30).unwrap());
+    assert!(!packed.get_coil(30).unwrap());
+    assert!(!ctx.get_discrete(30).unwrap());
+    assert!(!packed.get_discrete(30).unwrap());
+    // the context range is checked first
+    assert_eq!(
+        ctx.set_coils_from_u8(999, 9, &[0xff]),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(
+        packed.set_coils_from_u8(999, 9, &[0xff]),
+        Err(ErrorKind::OOBContext)
+    );
+    // zero count
+    ctx.set_coils_from_u8(40, 0, &[]).unwrap();
+    packed.set_coils_from_u8(40, 0, &[]).unwrap();
+}
+
+#[test]
+fn test_std_supported_functions_classified() {
+    // every supported function must be classified as read or write (ModbusFrame::readonly),
+    // when a function is added to supported_functions, add it here as well
+    let classified: &[(u8, bool, &[u8])] = &[
+        (consts::MODBUS_GET_COILS, true, &[0, 0, 0, 1]),
+        (consts::MODBUS_GET_DISCRETES, true, &[0, 0, 0, 1]),
+        (consts::MODBUS_GET_HOLDINGS, true, &[0, 0, 0, 1]),
+        (consts::MODBUS_GET_INPUTS, true, &[0, 0, 0, 1]),
+        (consts::MODBUS_SET_COIL, false, &[0, 0, 0xff, 0]),
+        (consts::MODBUS_SET_HOLDING, false, &[0, 0, 0, 1]),
+        (consts::MODBUS_READ_EXCEPTION_STATUS, true, &[]),
+        (consts::MODBUS_DIAGNOSTICS, true, &[0, 0, 0x12, 0x34]),
+        (consts::MODBUS_GET_COMM_EVENT_COUNTER, true, &[]),
+        (consts::MODBUS_SET_COILS_BULK, false, &[0, 0, 0, 1, 1, 1]),
+        (
+            consts::MODBUS_SET_HOLDINGS_BULK,
+            false,
+            &[0, 0, 0, 1, 2, 0, 1],
+        ),
+        (consts::MODBUS_REPORT_SERVER_ID, true, &[]),
+        (
+            consts::MODBUS_MASK_WRITE_REGISTER,
+            false,
+            &[0, 0, 0xff, 0xff, 0, 0],
+        ),
+        (
+            consts::MODBUS_READ_WRITE_MULTIPLE_REGISTERS,
+            false,
+            &[0, 0, 0, 1, 0, 0, 0, 1, 2, 0, 1],
+        ),
+        (
+            consts::MODBUS_ENCAPSULATED_INTERFACE_TRANSPORT,
+            true,
+            &[14, 1, 0],
+        ),
+    ];
+    assert_eq!(
+        supported_functions(),
+        classified
+            .iter()
+            .map(|(func, _, _)| *func)
+            .collect::<Vec<u8>>()
+    );
+    for (func, readonly, data) in classified {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut frame = vec![0, 1, 0, 0, 0, data.len() as u8 + 2, 1, *func];
+        frame.extend_from_slice(data);
+        let info = inspect(1, &frame, ModbusProto::Tcp).unwrap();
+        assert_eq!(info.func, *func);
+        assert_eq!(info.readonly, *readonly, "function {}", func);
+    }
+}
+
+#[test]
+fn test_std_client_parse_scaled_f32() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holdings_bulk(10, &[1234, 56]).unwrap();
+    for proto in [ModbusProto::Tcp, ModbusProto::Rtu, ModbusProto::Ascii] {
+        let mut mreq = ModbusRequest::new(1, proto);
+        let mut request = Vec::new();
+        let mut response = Vec::new();
+        mreq.generate_get_holdings(10, 2, &mut request).unwrap();
+        process_frame_full(1, &request, proto, &mut ctx, &mut response).unwrap();
+        let mut result: Vec<f32> = Vec::new();
+        mreq.parse_scaled_f32(&response, 0.1, &mut result).unwrap();
+        assert_eq!(result.len(), 2);
+        assert!((result[0] - 123.4).abs() < 1e-4);
+        assert!((result[1] - 5.6).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_std_set_holdings_from_iter() {
+    let mut ctx = ModbusStorageSmall::new();
+    ctx.set_holdings_from_iter(10, (0..5).map(|i| i * 10))
+        .unwrap();
+    assert_eq!(ctx.holdings[10..15], [0, 10, 20, 30, 40]);
+    assert_eq!(ctx.holdings[15], 0);
+    // empty iterator
+    ctx.set_holdings_from_iter(999, core::iter::empty())
+        .unwrap();
+    // stops at the end of the context, the previous values are written
+    let last = u16::try_from(ctx.holdings.len()).unwrap() - 2;
+    assert_eq!(
+        ctx.set_holdings_from_iter(last, [1, 2, 3].into_iter()),
+        Err(ErrorKind::OOBContext)
+    );
+    assert_eq!(ctx.holdings[usize::from(last)..], [1, 2]);
+    // the same for the packed storage
+    let mut packed = ModbusStoragePackedSmall::new();
+    packed
+        .set_holdings_from_iter(10, (0..5).map(|i| i * 10))
+        .unwrap();
+    let mut holdings = [0; 5];
+    packed.get_holdings_into(10, &mut holdings).unwrap();
+    assert_eq!(holdings, [0, 10, 20, 30, 40]);
+}
+
+#[test]
+fn test_std_frame_truncated_datagrams() {
+    let mut ctx = ModbusStorageSmall::new();
+    let mut result = Vec::new();
+    for proto in [ModbusProto::Tcp, ModbusProto::Udp] {
+        for request in [
+            // no byte count
+            &[0, 1, 0, 0, 0, 6, 1, 0x10, 0, 0, 0, 1][..],
+            &[0, 1, 0, 0, 0, 6, 1, 0x0f, 0, 0, 0, 1][..],
+            // no data
+            &[0, 1, 0, 0, 0, 7, 1, 0x10, 0, 0, 0, 1, 2][..],
+            &[0, 1, 0, 0, 0, 7, 1, 0x0f, 0, 0, 0, 1, 1][..],
+            // partial data
+            &[0, 1, 0, 0, 0, 8, 1, 0x10, 0, 0, 0, 1, 2, 0][..],
+            // no AND / OR masks
+            &[0, 1, 0, 0, 0, 6, 1, 0x16, 0, 0, 0xff, 0xff][..],
+            // no write address, count and byte count
+            &[0, 1, 0, 0, 0, 6, 1, 0x17, 0, 0, 0, 1][..],
+            // no write data
+            &[0, 1, 0, 0, 0, 11, 1, 0x17, 0, 0, 0, 1, 0, 0, 0, 1, 2][..],
+        ] {
+            let mut frame = ModbusFrame::new(1, request, proto, &mut result);
+            assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+            assert!(!frame.processing_required);
+        }
+        // complete frames are processed
+        let request = [0, 1, 0, 0, 0, 9, 1, 0x10, 0, 0, 0, 1, 2, 0x12, 0x34];
+        result.clear();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut result);
+        frame.parse().unwrap();
+        frame.process_write(&mut ctx).unwrap();
+        assert_eq!(ctx.get_holding(0).unwrap(), 0x1234);
+        let request = [
+            0, 1, 0, 0, 0, 13, 1, 0x17, 0, 0, 0, 1, 0, 1, 0, 1, 2, 0x56, 0x78,
+        ];
+        result.clear();
+        let mut frame = ModbusFrame::new(1, &request, proto, &mut result);
+        frame.parse().unwrap();
+        frame.process_write(&mut ctx).unwrap();
+        assert_eq!(ctx.get_holding(1).unwrap(), 0x5678);
+    }
+}
+
+#[test]
+fn test_std_client_response_data_offset() {
+    // every function, generated by the client, must have the response data offset defined, when
+    // a generator is added, add it here as well
+    let mut mreq = ModbusRequest::new_tcp_udp(1, 1);
+    let mut request = Vec::new();
+    let mut funcs = Vec::new();
+    mreq.generate_get_coils(0, 1, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_get_discretes(0, 1, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_get_holdings(0, 1, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_get_inputs(0, 1, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_set_coil(0, true, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_set_holding(0, 1, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_mask_write_register(0, 0xff, 0, &mut request)
+        .unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_set_holdings_bulk(0, &[1], &mut request)
+        .unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_read_write_multiple_registers(0, 1, 0, &[1], &mut request)
+        .unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_set_coils_bulk(0, &[true], &mut request)
+        .unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_diagnostics(0, 0x1234, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_read_exception_status(&mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_get_comm_event_counter(&mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_report_server_id(&mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_read_device_id(1, 0, &mut request).unwrap();
+    funcs.push(mreq.func);
+    mreq.generate_read_file_record(
+        &[FileRecordRequest {
+            file_number: 1,
+            record_number: 0,
+            record_length: 1,
+        }],
+        &mut request,
+    )
+    .unwrap();
+    funcs.push(mreq.func);
+    for func in funcs {
+        let offset = response_data_offset(func).unwrap_or_else(|| panic!("function {}", func));
+        // a response with a byte count (one byte of coils or one register), or 4 data bytes
+        let pdu: &[u8] = match func {
+            1 | 2 => &[1, 1],
+            _ if offset == 3 => &[2, 0, 1],
+            _ => &[0, 0, 0, 1],
+        };
+        mreq.func = func;
+        mreq.count = 1;
+        let response = gen_tcp_frame(&[&[1, func][..], pdu].concat());
+        let mut response = response.to_vec();
+        response.truncate(8 + pdu.len());
+        response[0..2].copy_from_slice(&mreq.tr_id.to_be_bytes());
+        let header = mreq.parse_header(&response).unwrap();
+        assert_eq!(header.data_start(), 6 + offset, "function {}", func);
+    }
+    // unknown functions are not guessed
+    mreq.func = 0x5a;
+    let response = [0, 1, 0, 0, 0, 5, 1, 0x5a, 2, 0, 1];
+    assert_eq!(
+        mreq.parse_header(&response),
+        Err(ErrorKind::IllegalFunction)
+    );
+}
+
+#[test]
+fn test_std_frame_rw_multiple_byte_count_overflow() {
+    let mut result = Vec::new();
+    // the frame length does not fit into u8
+    let request = [0, 1, 0, 0, 0, 11, 1, 23, 0, 0, 0, 1, 0, 0, 0, 1, 0xf5];
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut result);
+    assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+    let mut request: ModbusFrameBuf = [0; 256];
+    request[..11].copy_from_slice(&[1, 23, 0, 0, 0, 1, 0, 0, 0, 1, 0xf5]);
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut result);
+    assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+    // the checksum is out of the buffer
+    request[10] = 0xf4;
+    let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut result);
+    assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+    let mut frame = ModbusFrame::new(1, &request[..20], ModbusProto::Rtu, &mut result);
+    assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+}
+
+#[test]
+fn test_std_frame_write_multiple_byte_count_overflow() {
+    let mut result = Vec::new();
+    for func in [15, 16] {
+        // the frame length does not fit into u8
+        let request = [1, func, 0, 0, 0, 1, 0xfa];
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut result);
+        assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Ascii, &mut result);
+        assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+        // the checksum is out of the buffer
+        let mut request: ModbusFrameBuf = [0; 256];
+        request[..7].copy_from_slice(&[1, func, 0, 0, 0, 1, 0xf8]);
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Rtu, &mut result);
+        assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+        let request = [0, 1, 0, 0, 0, 7, 1, func, 0, 0, 0, 1, 0xfa];
+        let mut frame = ModbusFrame::new(1, &request, ModbusProto::Tcp, &mut result);
+        assert_eq!(frame.parse(), Err(ErrorKind::FrameBroken));
+    }
+}
+
+fn feed_all<'a>(acc: &'a mut FrameAccumulator, data: &[u8]) -> FeedResult<'a> {
+    let (last, head) = data.split_last().unwrap();
+    for b in head {
+        assert_eq!(acc.feed(*b), FeedResult::NeedMore);
+    }
+    acc.feed(*last)
+}
+
+#[test]
+fn test_std_frame_accumulator() {
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Rtu);
+    let mut request = Vec::new();
+    // rtu
+    let mut acc = FrameAccumulator::new(ModbusProto::Rtu);
+    mreq.generate_set_holdings_bulk(2, &[1, 2, 3], &mut request)
+        .unwrap();
+    assert_eq!(
+        feed_all(&mut acc, &request),
+        FeedResult::Complete(request.as_slice())
+    );
+    // the next frame starts after completion
+    mreq.generate_get_holdings(0, 5, &mut request).unwrap();
+    assert_eq!(
+        feed_all(&mut acc, &request),
+        FeedResult::Complete(request.as_slice())
+    );
+    mreq.generate_report_server_id(&mut request).unwrap();
+    assert_eq!(
+        feed_all(&mut acc, &request),
+        FeedResult::Complete(request.as_slice())
+    );
+    // the frame is too long
+    for b in [1, 16, 0, 0, 0, 0x7f] {
+        assert_eq!(acc.feed(b), FeedResult::NeedMore);
+    }
+    assert_eq!(acc.feed(0xfe), FeedResult::Broken);
+    // rtu response
+    let mut acc = FrameAccumulator::new_response(ModbusProto::Rtu);
+    let response = [1, 3, 4, 0, 1, 0, 2, 0x2a, 0x32];
+    assert_eq!(
+        feed_all(&mut acc, &response),
+        FeedResult::Complete(&response)
+    );
+    // tcp
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Tcp);
+    let mut acc = FrameAccumulator::new(ModbusProto::Tcp);
+    mreq.generate_set_coils_bulk(0, &[true; 20], &mut request)
+        .unwrap();
+    assert_eq!(
+        feed_all(&mut acc, &request),
+        FeedResult::Complete(request.as_slice())
+    );
+    for b in [0, 1, 0, 1] {
+        assert_eq!(acc.feed(b), FeedResult::NeedMore);
+    }
+    assert_eq!(acc.feed(0), FeedResult::NeedMore);
+    assert_eq!(acc.feed(0), FeedResult::Broken);
+    // the length is above the frame limit
+    for mut acc in [
+        FrameAccumulator::new(ModbusProto::Tcp),
+        FrameAccumulator::new_response(ModbusProto::Tcp),
+    ] {
+        for b in [0, 1, 0, 0, 0xff] {
+            assert_eq!(acc.feed(b), FeedResult::NeedMore);
+        }
+        assert_eq!(acc.feed(0xff), FeedResult::Broken);
+    }
+    let header = [0, 1, 0, 0, 0xff, 0xff];
+    assert_eq!(
+        guess_request_frame_len(&header, ModbusProto::Tcp),
+        Err(ErrorKind::FrameBroken)
+    );
+    assert_eq!(
+        guess_response_frame_len(&header, ModbusProto::Tcp),
+        Err(ErrorKind::FrameBroken)
+    );
+    assert_eq!(
+        response_bytes_remaining(&header, 6, ModbusProto::Tcp),
+        Err(ErrorKind::FrameBroken)
+    );
+    // ascii
+    let mut mreq = ModbusRequest::new(1, ModbusProto::Ascii);
+    let mut acc = FrameAccumulator::new(ModbusProto::Ascii);
+    mreq.generate_set_holding(1, 0x1234, &mut request).unwrap();
+    let mut ascii_frame = Vec::new();
+    generate_ascii_frame(&request, &mut ascii_frame).unwrap();
+    assert_eq!(
+        feed_all(&mut acc, &ascii_frame),
+        FeedResult::Complete(request.as_slice())
+    );
+    assert_eq!(acc.feed(b'0'), FeedResult::Broken);
+    assert_eq!(acc.feed(b':'), FeedResult::NeedMore);
+    assert_eq!(acc.feed(b'Z'), FeedResult::Broken);
+}
+
+#[cfg(feature = "defmt")]
+#[test]
+fn test_std_defmt_format() {
+    fn assert_format<T: defmt::Format>() {}
+    assert_format::<ErrorKind>();
+    assert_format::<ModbusProto>();
+    assert_format::<FeedResult>();
+    assert_format::<ModbusRequest>();
+    assert_format::<DeviceIdInfo>();
+    assert_format::<RetryDecision>();
+    assert_format::<FileRecordRequest>();
+    assert_format::<ResponseDataRef>();
+    assert_format::<WordOrder>();
+    assert_format::<FloatByteOrder>();
+    assert_format::<DeviceIdentification>();
+    assert_format::<DiagnosticCounters>();
+    assert_format::<CommEventCounters>();
+    assert_format::<Changes>();
+    assert_format::<RegValue>();
+    assert_format::<RegKind>();
+    assert_format::<FrameInfo>();
+    assert_format::<Write>();
+    assert_format::<Read>();
 }
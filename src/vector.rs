@@ -14,6 +14,184 @@ pub trait VectorTrait<T: Copy> {
     fn replace(&mut self, index: usize, value: T);
 }
 
+/// [`VectorTrait`] over a caller-provided slice with a running length, the recommended sink
+/// when no allocator is available (no extra dependencies are required)
+///
+/// Pushing over the slice capacity returns [`ErrorKind::OOB`]
+///
+/// ```
+/// use rmodbus::{ModbusFrameBuf, ModbusProto, SliceWriter, server::ModbusFrame};
+///
+/// let framebuf: ModbusFrameBuf = [0; 256];
+/// let mut buf = [0u8; 256];
+/// let mut response = SliceWriter::new(&mut buf);
+/// let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut response);
+/// ```
+#[derive(Debug)]
+pub struct SliceWriter<'a, T: Copy = u8> {
+    buf: &'a mut [T],
+    len: usize,
+}
+
+impl<'a, T: Copy> SliceWriter<'a, T> {
+    pub fn new(buf: &'a mut [T]) -> Self {
+        Self { buf, len: 0 }
+    }
+    /// Max number of elements
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+}
+
+impl<T: Copy> VectorTrait<T> for SliceWriter<'_, T> {
+    #[inline]
+    fn push(&mut self, value: T) -> Result<(), ErrorKind> {
+        let el = self.buf.get_mut(self.len).ok_or(ErrorKind::OOB)?;
+        *el = value;
+        self.len += 1;
+        Ok(())
+    }
+    #[inline]
+    fn extend(&mut self, values: &[T]) -> Result<(), ErrorKind> {
+        let new_len = self.len + values.len();
+        if new_len > self.buf.len() {
+            return Err(ErrorKind::OOB);
+        }
+        self.buf[self.len..new_len].copy_from_slice(values);
+        self.len = new_len;
+        Ok(())
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    #[inline]
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+    fn cut_end(&mut self, len_to_cut: usize, _value: T) {
+        self.len = self.len.saturating_sub(len_to_cut);
+    }
+    #[inline]
+    fn as_slice(&self) -> &[T] {
+        &self.buf[..self.len]
+    }
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.buf[..self.len]
+    }
+    fn resize(&mut self, new_len: usize, value: T) -> Result<(), ErrorKind> {
+        if new_len > self.buf.len() {
+            return Err(ErrorKind::OOB);
+        }
+        if new_len > self.len {
+            self.buf[self.len..new_len].fill(value);
+        }
+        self.len = new_len;
+        Ok(())
+    }
+    #[inline]
+    fn replace(&mut self, index: usize, value: T) {
+        self.as_mut_slice()[index] = value;
+    }
+}
+
+/// Owned, heap-free response buffer, large enough for any Modbus frame
+///
+/// Unlike [`SliceWriter`], the buffer is not borrowed, so a finalized response can be copied /
+/// moved (e.g. to a send queue) independently of the frame
+///
+/// ```
+/// use rmodbus::{ModbusFrameBuf, ModbusProto, ResponseBuf, server::ModbusFrame};
+///
+/// let framebuf: ModbusFrameBuf = [0; 256];
+/// let mut response = ResponseBuf::new();
+/// let mut frame = ModbusFrame::new(1, &framebuf, ModbusProto::Tcp, &mut response);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ResponseBuf {
+    buf: [u8; 256],
+    len: usize,
+}
+
+impl Default for ResponseBuf {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResponseBuf {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; 256],
+            len: 0,
+        }
+    }
+    /// The response bytes to send
+    #[inline]
+    pub fn as_sendable(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+    /// Runs a [`SliceWriter`] over the buffer, so both share the same bounds checks
+    #[inline]
+    fn with_writer<R>(&mut self, f: impl FnOnce(&mut SliceWriter<'_, u8>) -> R) -> R {
+        let mut writer = SliceWriter {
+            buf: &mut self.buf,
+            len: self.len,
+        };
+        let result = f(&mut writer);
+        self.len = writer.len;
+        result
+    }
+}
+
+impl VectorTrait<u8> for ResponseBuf {
+    #[inline]
+    fn push(&mut self, value: u8) -> Result<(), ErrorKind> {
+        self.with_writer(|w| w.push(value))
+    }
+    #[inline]
+    fn extend(&mut self, values: &[u8]) -> Result<(), ErrorKind> {
+        self.with_writer(|w| w.extend(values))
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    #[inline]
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+    fn cut_end(&mut self, len_to_cut: usize, value: u8) {
+        self.with_writer(|w| w.cut_end(len_to_cut, value));
+    }
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.len]
+    }
+    fn resize(&mut self, new_len: usize, value: u8) -> Result<(), ErrorKind> {
+        self.with_writer(|w| w.resize(new_len, value))
+    }
+    #[inline]
+    fn replace(&mut self, index: usize, value: u8) {
+        self.with_writer(|w| w.replace(index, value));
+    }
+}
+
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 #[cfg(all(feature = "alloc", not(feature = "std")))]